@@ -4,13 +4,15 @@ use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, Map, Symbol, Vec}
 mod distribution;
 mod eligibility;
 mod external;
+mod pause;
 mod tracking;
 mod types;
 
 pub use distribution::*;
+pub use pause::*;
 pub use tracking::*;
 
-use types::{AirdropError, AirdropEvent, DataKey, EventStats};
+use types::{AirdropError, AirdropEvent, DataKey, EventStats, PauseScope};
 
 #[cfg(test)]
 mod test;
@@ -64,6 +66,7 @@ impl AirdropContract {
         max_total_amount: Option<i128>,
     ) -> Result<u64, AirdropError> {
         admin.require_auth();
+        ensure_not_paused(&env, "create_airdrop")?;
 
         // Validate event configuration
         if name == Symbol::new(&env, "") || conditions.len() == 0 || amount == 0 {
@@ -128,6 +131,7 @@ impl AirdropContract {
 
     /// User claims tokens for an airdrop event.
     pub fn claim_airdrop(env: Env, user: Address, event_id: u64) -> Result<(), AirdropError> {
+        ensure_not_paused(&env, "claim_airdrop")?;
         claim_tokens(env, user, event_id)
     }
 
@@ -138,6 +142,7 @@ impl AirdropContract {
         event_id: u64,
         users: Vec<Address>,
     ) -> Result<(), AirdropError> {
+        ensure_not_paused(&env, "distribute_batch")?;
         distribute_batch(env, admin, event_id, users)
     }
 
@@ -253,6 +258,37 @@ impl AirdropContract {
         Ok(())
     }
 
+    /// Set the address (e.g. governance) allowed to pause the contract besides the admin.
+    /// Once set, only the current authority may replace it.
+    pub fn set_pause_authority(
+        env: Env,
+        caller: Address,
+        authority: Address,
+    ) -> Result<(), AirdropError> {
+        set_pause_authority(env, caller, authority)
+    }
+
+    /// Pause the whole contract or a single function until `until`, after which it lifts itself.
+    /// A pause may last at most `MAX_PAUSE_DURATION`.
+    pub fn pause(
+        env: Env,
+        caller: Address,
+        scope: PauseScope,
+        until: u64,
+    ) -> Result<(), AirdropError> {
+        pause(env, caller, scope, until)
+    }
+
+    /// Lift a pause before it expires. Pauses set by the pause authority can only be lifted by it.
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), AirdropError> {
+        unpause(env, caller, scope)
+    }
+
+    /// Check whether a function is currently paused.
+    pub fn is_paused(env: Env, function: Symbol) -> bool {
+        is_paused(&env, &function)
+    }
+
     /// Finalize an airdrop event.
     pub fn finalize_event(env: Env, admin: Address, event_id: u64) -> Result<(), AirdropError> {
         internal_finalize_event(env, admin, event_id)
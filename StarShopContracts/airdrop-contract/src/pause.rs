@@ -0,0 +1,133 @@
+use crate::types::*;
+use soroban_sdk::{Address, Env, Symbol};
+
+/// Longest pause that may be requested, in seconds.
+pub const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
+
+/// Set the address allowed to pause the contract besides the admin.
+///
+/// The admin appoints the first authority; after that only the current
+/// authority may replace itself.
+pub fn set_pause_authority(
+    env: Env,
+    caller: Address,
+    authority: Address,
+) -> Result<(), AirdropError> {
+    caller.require_auth();
+
+    let expected: Address = match get_pause_authority(&env) {
+        Some(current) => current,
+        None => env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(AirdropError::Unauthorized)?,
+    };
+    if expected != caller {
+        return Err(AirdropError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::PauseAuthority, &authority);
+    env.events()
+        .publish((Symbol::new(&env, "PauseAuthoritySet"), caller), authority);
+    Ok(())
+}
+
+/// Pause the contract or a single function until the given timestamp.
+pub fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) -> Result<(), AirdropError> {
+    require_pauser(&env, &caller)?;
+
+    // Claims resume on their own at `until`, which may be at most MAX_PAUSE_DURATION away
+    let now = env.ledger().timestamp();
+    if until <= now || until - now > MAX_PAUSE_DURATION {
+        return Err(AirdropError::InvalidEventConfig);
+    }
+
+    // The admin cannot re-time a freeze the pause authority placed on this scope
+    let by_authority = get_pause_authority(&env).as_ref() == Some(&caller);
+    if !by_authority && is_authority_pause(&env, &scope) {
+        return Err(AirdropError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Paused(scope.clone()), &until);
+    env.storage()
+        .persistent()
+        .set(&DataKey::PausedByAuthority(scope.clone()), &by_authority);
+    env.events()
+        .publish((Symbol::new(&env, "Paused"), caller, scope), until);
+    Ok(())
+}
+
+/// Lift a pause before it expires.
+pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), AirdropError> {
+    require_pauser(&env, &caller)?;
+
+    // A freeze placed by the pause authority stays until the authority lifts it or it expires
+    if get_pause_authority(&env).as_ref() != Some(&caller) && is_authority_pause(&env, &scope) {
+        return Err(AirdropError::Unauthorized);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Paused(scope.clone()));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PausedByAuthority(scope.clone()));
+    env.events()
+        .publish((Symbol::new(&env, "Unpaused"), caller, scope), true);
+    Ok(())
+}
+
+/// Check whether a function is paused, either on its own or through a contract-wide pause.
+pub fn is_paused(env: &Env, function: &Symbol) -> bool {
+    is_scope_paused(env, PauseScope::Contract)
+        || is_scope_paused(env, PauseScope::Function(function.clone()))
+}
+
+/// Fail if a function is currently paused.
+pub fn ensure_not_paused(env: &Env, function: &str) -> Result<(), AirdropError> {
+    if is_paused(env, &Symbol::new(env, function)) {
+        return Err(AirdropError::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Check whether a pause scope is still in effect.
+fn is_scope_paused(env: &Env, scope: PauseScope) -> bool {
+    let until: Option<u64> = env.storage().persistent().get(&DataKey::Paused(scope));
+    match until {
+        Some(until) => env.ledger().timestamp() < until,
+        None => false,
+    }
+}
+
+/// Check whether a pause scope is in effect and was set by the pause authority.
+fn is_authority_pause(env: &Env, scope: &PauseScope) -> bool {
+    let by_authority: bool = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PausedByAuthority(scope.clone()))
+        .unwrap_or(false);
+    by_authority && is_scope_paused(env, scope.clone())
+}
+
+/// Get the current pause authority, if one has been set.
+fn get_pause_authority(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::PauseAuthority)
+}
+
+/// Ensure the caller is the admin or the pause authority.
+fn require_pauser(env: &Env, caller: &Address) -> Result<(), AirdropError> {
+    caller.require_auth();
+
+    let admin: Option<Address> = env.storage().persistent().get(&DataKey::Admin);
+    let authority = get_pause_authority(env);
+    if admin.as_ref() != Some(caller) && authority.as_ref() != Some(caller) {
+        return Err(AirdropError::Unauthorized);
+    }
+    Ok(())
+}
@@ -8,7 +8,8 @@ use soroban_sdk::{
 };
 
 use super::{
-    types::{AirdropError, AirdropEvent, DataKey, EventStats},
+    pause::MAX_PAUSE_DURATION,
+    types::{AirdropError, AirdropEvent, DataKey, EventStats, PauseScope},
     AirdropContract, AirdropContractClient,
 };
 
@@ -1273,6 +1274,102 @@ fn test_pause_resume_event() {
     assert_eq!(result, Err(Ok(AirdropError::InvalidEventConfig)));
 }
 
+#[test]
+fn test_emergency_pause_by_authority() {
+    let (env, airdrop_contract) = create_test_env();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let user = Address::generate(&env);
+    let (token_address, _) = setup_token(&env);
+    let client = AirdropContractClient::new(&env, &airdrop_contract);
+
+    client.initialize(&admin, &None);
+    client.set_pause_authority(&admin, &governance);
+
+    // Only the admin or the pause authority can pause
+    let claim = Symbol::new(&env, "claim_airdrop");
+    let until = env.ledger().timestamp() + 100;
+    let result = client.try_pause(&user, &PauseScope::Function(claim.clone()), &until);
+    assert_eq!(result, Err(Ok(AirdropError::Unauthorized)));
+
+    // Pausing a single function leaves the others available
+    client.pause(&governance, &PauseScope::Function(claim.clone()), &until);
+    assert!(client.is_paused(&claim));
+    assert!(!client.is_paused(&Symbol::new(&env, "create_airdrop")));
+    let result = client.try_claim_airdrop(&user, &1);
+    assert_eq!(result, Err(Ok(AirdropError::ContractPaused)));
+
+    // A contract-wide pause covers every guarded function
+    client.pause(&governance, &PauseScope::Contract, &(until - 50));
+    let conditions = Map::from_array(&env, [(Symbol::new(&env, "referrals"), 3u64)]);
+    let now = env.ledger().timestamp();
+    let result = client.try_create_airdrop(
+        &admin,
+        &Symbol::new(&env, "Airdrop1"),
+        &Bytes::from_slice(&env, b"Test airdrop"),
+        &conditions,
+        &1000,
+        &token_address,
+        &now,
+        &(now + 1000),
+        &None,
+        &None,
+    );
+    assert_eq!(result, Err(Ok(AirdropError::ContractPaused)));
+
+    // Pauses lift themselves once they expire
+    env.ledger().with_mut(|li| li.timestamp = until);
+    assert!(!client.is_paused(&claim));
+    assert!(!client.is_paused(&Symbol::new(&env, "create_airdrop")));
+
+    // Or earlier, when lifted explicitly
+    client.pause(&admin, &PauseScope::Contract, &(until + 100));
+    assert!(client.is_paused(&claim));
+    client.unpause(&governance, &PauseScope::Contract);
+    assert!(!client.is_paused(&claim));
+}
+
+#[test]
+fn test_pause_authority_keeps_control_of_its_pauses() {
+    let (env, airdrop_contract) = create_test_env();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let new_governance = Address::generate(&env);
+    let client = AirdropContractClient::new(&env, &airdrop_contract);
+
+    client.initialize(&admin, &None);
+    client.set_pause_authority(&admin, &governance);
+
+    // Once set, the admin can no longer replace the pause authority
+    let result = client.try_set_pause_authority(&admin, &admin);
+    assert_eq!(result, Err(Ok(AirdropError::Unauthorized)));
+
+    // Pauses are capped at the maximum duration
+    let now = env.ledger().timestamp();
+    let result = client.try_pause(
+        &governance,
+        &PauseScope::Contract,
+        &(now + MAX_PAUSE_DURATION + 1),
+    );
+    assert_eq!(result, Err(Ok(AirdropError::InvalidEventConfig)));
+
+    // The admin cannot lift or shorten a pause set by the authority
+    let claim = Symbol::new(&env, "claim_airdrop");
+    client.pause(&governance, &PauseScope::Contract, &(now + MAX_PAUSE_DURATION));
+    let result = client.try_unpause(&admin, &PauseScope::Contract);
+    assert_eq!(result, Err(Ok(AirdropError::Unauthorized)));
+    let result = client.try_pause(&admin, &PauseScope::Contract, &(now + 1));
+    assert_eq!(result, Err(Ok(AirdropError::Unauthorized)));
+    assert!(client.is_paused(&claim));
+
+    // The authority can lift its own pause and hand over to a successor
+    client.unpause(&governance, &PauseScope::Contract);
+    assert!(!client.is_paused(&claim));
+    client.set_pause_authority(&governance, &new_governance);
+    let result = client.try_pause(&governance, &PauseScope::Contract, &(now + 100));
+    assert_eq!(result, Err(Ok(AirdropError::Unauthorized)));
+}
+
 #[test]
 fn test_event_status_override() {
     let (env, airdrop_contract) = create_test_env();
@@ -51,6 +51,22 @@ pub enum DataKey {
     ProviderRegistry(Symbol),
     /// Key for the list of users who claimed an airdrop, identified by event ID.
     ClaimedUsers(u64),
+    /// Key for the address allowed to pause the contract besides the admin (e.g. governance).
+    PauseAuthority,
+    /// Key for the timestamp a pause lasts until, identified by its scope.
+    Paused(PauseScope),
+    /// Key marking a pause as set by the pause authority, identified by its scope.
+    PausedByAuthority(PauseScope),
+}
+
+/// What an emergency pause applies to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseScope {
+    /// Every guarded function of the contract.
+    Contract,
+    /// A single guarded function, identified by its name.
+    Function(Symbol),
 }
 
 /// Error codes for the airdrop contract.
@@ -73,4 +89,5 @@ pub enum AirdropError {
     EventInactive = 13,
     CapExceeded = 14,
     InvalidEventConfig = 15,
+    ContractPaused = 16,
 }
@@ -2,12 +2,14 @@
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
 
 mod funding;
+mod pause;
 mod product;
 mod rewards;
 mod tracking;
 mod types;
 
 pub use funding::*;
+pub use pause::*;
 pub use product::*;
 pub use rewards::*;
 pub use tracking::*;
@@ -47,6 +49,7 @@ impl CrowdfundingCollective {
         reward_tiers: Vec<RewardTier>,
         milestones: Vec<Milestone>,
     ) -> u32 {
+        pause::require_not_paused(&env, "create_product");
         product::create_product(
             env,
             creator,
@@ -61,19 +64,23 @@ impl CrowdfundingCollective {
 
     // Funding functions
     pub fn contribute(env: Env, contributor: Address, product_id: u32, amount: u64) {
+        pause::require_not_paused(&env, "contribute");
         funding::contribute(env, contributor, product_id, amount)
     }
 
     pub fn distribute_funds(env: Env, caller: Address, product_id: u32) {
+        pause::require_not_paused(&env, "distribute_funds");
         funding::distribute_funds(env, caller, product_id)
     }
 
     pub fn refund_contributors(env: Env, caller: Address, product_id: u32) {
+        pause::require_not_paused(&env, "refund_contributors");
         funding::refund_contributors(env, caller, product_id)
     }
 
     // Reward functions
     pub fn claim_reward(env: Env, contributor: Address, product_id: u32) {
+        pause::require_not_paused(&env, "claim_reward");
         rewards::claim_reward(env, contributor, product_id)
     }
 
@@ -82,6 +89,23 @@ impl CrowdfundingCollective {
         tracking::update_milestone(env, creator, product_id, milestone_id)
     }
 
+    // Emergency pause functions
+    pub fn set_pause_authority(env: Env, caller: Address, authority: Address) {
+        pause::set_pause_authority(env, caller, authority)
+    }
+
+    pub fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) {
+        pause::pause(env, caller, scope, until)
+    }
+
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) {
+        pause::unpause(env, caller, scope)
+    }
+
+    pub fn is_paused(env: Env, function: Symbol) -> bool {
+        pause::is_paused(&env, &function)
+    }
+
     pub fn get_product(env: Env, product_id: u32) -> Product {
         product::get_product(env, product_id)
     }
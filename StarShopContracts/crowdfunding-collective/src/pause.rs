@@ -0,0 +1,128 @@
+use crate::types::*;
+use soroban_sdk::{Address, Env, Symbol};
+
+// Longest pause that may be requested, in seconds
+pub const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
+
+// The admin appoints the first pause authority; after that only the authority may replace itself
+pub fn set_pause_authority(env: Env, caller: Address, authority: Address) {
+    caller.require_auth();
+
+    match get_pause_authority(&env) {
+        Some(current) => {
+            if caller != current {
+                panic!("Only the pause authority can replace itself");
+            }
+        }
+        None => {
+            let stored_admin: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Admin)
+                .expect("Contract not initialized");
+            if caller != stored_admin {
+                panic!("Only the admin can set the pause authority");
+            }
+        }
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::PauseAuthority, &authority);
+
+    env.events()
+        .publish((Symbol::new(&env, "PauseAuthoritySet"),), authority);
+}
+
+pub fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) {
+    require_pauser(&env, &caller);
+
+    // Contributions and payouts resume by themselves at `until`
+    let now = env.ledger().timestamp();
+    if until <= now {
+        panic!("Pause must end in the future");
+    }
+    if until - now > MAX_PAUSE_DURATION {
+        panic!("Pause exceeds the maximum duration");
+    }
+
+    // Backers rely on a governance pause holding, so the admin may not shorten or extend it
+    let by_authority = get_pause_authority(&env).as_ref() == Some(&caller);
+    if !by_authority && is_authority_pause(&env, &scope) {
+        panic!("Only the pause authority can change its pause");
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::Paused(scope.clone()), &until);
+    env.storage()
+        .instance()
+        .set(&DataKey::PausedByAuthority(scope.clone()), &by_authority);
+
+    env.events()
+        .publish((Symbol::new(&env, "Paused"),), (scope, until));
+}
+
+pub fn unpause(env: Env, caller: Address, scope: PauseScope) {
+    require_pauser(&env, &caller);
+
+    // Likewise the admin may not lift it early
+    if get_pause_authority(&env).as_ref() != Some(&caller) && is_authority_pause(&env, &scope) {
+        panic!("Only the pause authority can change its pause");
+    }
+
+    env.storage()
+        .instance()
+        .remove(&DataKey::Paused(scope.clone()));
+    env.storage()
+        .instance()
+        .remove(&DataKey::PausedByAuthority(scope.clone()));
+
+    env.events()
+        .publish((Symbol::new(&env, "Unpaused"),), scope);
+}
+
+// Paused on its own or through a contract-wide pause
+pub fn is_paused(env: &Env, function: &Symbol) -> bool {
+    is_scope_paused(env, PauseScope::Contract)
+        || is_scope_paused(env, PauseScope::Function(function.clone()))
+}
+
+pub fn require_not_paused(env: &Env, function: &str) {
+    if is_paused(env, &Symbol::new(env, function)) {
+        panic!("Function is paused");
+    }
+}
+
+fn is_scope_paused(env: &Env, scope: PauseScope) -> bool {
+    let until: Option<u64> = env.storage().instance().get(&DataKey::Paused(scope));
+    match until {
+        Some(until) => env.ledger().timestamp() < until,
+        None => false,
+    }
+}
+
+// In effect and set by the pause authority
+fn is_authority_pause(env: &Env, scope: &PauseScope) -> bool {
+    let by_authority: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::PausedByAuthority(scope.clone()))
+        .unwrap_or(false);
+    by_authority && is_scope_paused(env, scope.clone())
+}
+
+fn get_pause_authority(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PauseAuthority)
+}
+
+// Only the admin or the pause authority may pause
+fn require_pauser(env: &Env, caller: &Address) {
+    caller.require_auth();
+
+    let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+    let authority = get_pause_authority(env);
+    if admin.as_ref() != Some(caller) && authority.as_ref() != Some(caller) {
+        panic!("Only the admin or pause authority can pause");
+    }
+}
@@ -8,6 +8,7 @@ use soroban_sdk::{
     Env,
     IntoVal, // For converting values for mock auth args
     String,
+    Symbol,
     Vec,
 };
 
@@ -1255,3 +1256,139 @@ fn test_get_product_not_found_panics() {
     let test = CrowdfundingTest::setup();
     test.client.get_product(&999u32);
 }
+
+// Helper to set up a contract with a pause authority, using mocked auths
+fn setup_pausable(env: &Env) -> (CrowdfundingCollectiveClient<'_>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(CrowdfundingCollective, ());
+    let client = CrowdfundingCollectiveClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let governance = Address::generate(env);
+
+    client.initialize(&admin);
+    client.set_pause_authority(&admin, &governance);
+    (client, admin, governance)
+}
+
+#[test]
+#[should_panic(expected = "Function is paused")]
+fn test_paused_function_rejects_calls() {
+    let env = Env::default();
+    let (client, _, governance) = setup_pausable(&env);
+    let creator = Address::generate(&env);
+    let contributor = Address::generate(&env);
+
+    let until = env.ledger().timestamp() + 100;
+    let contribute = Symbol::new(&env, "contribute");
+    client.pause(&governance, &PauseScope::Function(contribute), &until);
+
+    // Other functions stay available while contributions are paused
+    let deadline = env.ledger().timestamp() + 1000;
+    let product_id = client.create_product(
+        &creator,
+        &String::from_str(&env, "Test Product"),
+        &String::from_str(&env, "A great product for testing"),
+        &1000,
+        &deadline,
+        &vec![
+            &env,
+            RewardTier {
+                id: 1,
+                min_contribution: 50,
+                description: String::from_str(&env, "Basic Reward"),
+                discount: 5,
+            },
+        ],
+        &vec![
+            &env,
+            Milestone {
+                id: 0,
+                description: String::from_str(&env, "Phase 1"),
+                target_date: deadline + 100,
+                completed: false,
+            },
+        ],
+    );
+
+    client.contribute(&contributor, &product_id, &100);
+}
+
+#[test]
+fn test_pause_expires_or_is_lifted() {
+    let env = Env::default();
+    let (client, admin, governance) = setup_pausable(&env);
+    let contribute = Symbol::new(&env, "contribute");
+
+    // A contract-wide pause covers every guarded function until lifted
+    let until = env.ledger().timestamp() + 100;
+    client.pause(&admin, &PauseScope::Contract, &until);
+    assert!(client.is_paused(&contribute));
+    assert!(client.is_paused(&Symbol::new(&env, "claim_reward")));
+    client.unpause(&admin, &PauseScope::Contract);
+    assert!(!client.is_paused(&contribute));
+
+    // A function pause lifts itself once it expires
+    client.pause(&governance, &PauseScope::Function(contribute.clone()), &until);
+    assert!(client.is_paused(&contribute));
+    advance_ledger_time(&env, 100);
+    assert!(!client.is_paused(&contribute));
+}
+
+#[test]
+#[should_panic(expected = "Only the admin or pause authority can pause")]
+fn test_pause_unauthorized() {
+    let env = Env::default();
+    let (client, _, _) = setup_pausable(&env);
+    let stranger = Address::generate(&env);
+
+    let until = env.ledger().timestamp() + 100;
+    client.pause(&stranger, &PauseScope::Contract, &until);
+}
+
+#[test]
+#[should_panic(expected = "Pause exceeds the maximum duration")]
+fn test_pause_exceeds_max_duration() {
+    let env = Env::default();
+    let (client, _, governance) = setup_pausable(&env);
+
+    let until = env.ledger().timestamp() + pause::MAX_PAUSE_DURATION + 1;
+    client.pause(&governance, &PauseScope::Contract, &until);
+}
+
+#[test]
+#[should_panic(expected = "Only the pause authority can change its pause")]
+fn test_admin_cannot_lift_authority_pause() {
+    let env = Env::default();
+    let (client, admin, governance) = setup_pausable(&env);
+
+    let until = env.ledger().timestamp() + pause::MAX_PAUSE_DURATION;
+    client.pause(&governance, &PauseScope::Contract, &until);
+    client.unpause(&admin, &PauseScope::Contract);
+}
+
+#[test]
+#[should_panic(expected = "Only the pause authority can replace itself")]
+fn test_admin_cannot_replace_pause_authority() {
+    let env = Env::default();
+    let (client, admin, _) = setup_pausable(&env);
+
+    client.set_pause_authority(&admin, &admin);
+}
+
+#[test]
+fn test_pause_authority_hands_over() {
+    let env = Env::default();
+    let (client, _, governance) = setup_pausable(&env);
+    let new_governance = Address::generate(&env);
+    let contribute = Symbol::new(&env, "contribute");
+
+    // The authority lifts its own pause and appoints a successor
+    let until = env.ledger().timestamp() + 100;
+    client.pause(&governance, &PauseScope::Contract, &until);
+    client.unpause(&governance, &PauseScope::Contract);
+    assert!(!client.is_paused(&contribute));
+
+    client.set_pause_authority(&governance, &new_governance);
+    client.pause(&new_governance, &PauseScope::Contract, &until);
+    assert!(client.is_paused(&contribute));
+}
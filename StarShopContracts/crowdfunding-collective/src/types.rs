@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, String, Symbol};
 
 #[contracttype]
 pub enum DataKey {
@@ -10,6 +10,16 @@ pub enum DataKey {
     NextProductId,               // Counter for product IDs
     ContributionsTotal(u32),     // Product ID -> Total contributed amount
     RewardClaimed(u32, Address), // Track reward claims (product_id, contributor)
+    PauseAuthority,              // Address allowed to pause besides the admin
+    Paused(PauseScope),          // Pause scope -> Timestamp the pause lasts until
+    PausedByAuthority(PauseScope), // Pause scope -> Whether the pause authority set it
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum PauseScope {
+    Contract,         // Every guarded function
+    Function(Symbol), // A single guarded function, by name
 }
 
 #[contracttype]
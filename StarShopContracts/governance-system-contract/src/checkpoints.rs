@@ -0,0 +1,252 @@
+use crate::types::{Checkpoint, Error, TOKEN_KEY, TOTAL_CHECKPOINTS_KEY};
+use crate::utils::get_governance_op_key;
+use soroban_sdk::{symbol_short, token::TokenClient, Address, Bytes, Env, Symbol};
+
+/// VotingPowerLedger tracks governance tokens deposited for voting
+/// Every balance change is checkpointed by ledger sequence so that proposals
+/// can read the voting power each holder had when their snapshot was taken
+pub struct VotingPowerLedger;
+
+impl VotingPowerLedger {
+    /// Deposit governance tokens to gain voting power
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address depositing tokens
+    /// * `amount` - The amount of tokens to deposit
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn deposit(env: &Env, holder: &Address, amount: i128) -> Result<(), Error> {
+        holder.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Move the tokens into the governance contract
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&TOKEN_KEY)
+            .ok_or(Error::NotInitialized)?;
+        let token_client = TokenClient::new(env, &token);
+        token_client.transfer(holder, &env.current_contract_address(), &amount);
+
+        // Checkpoint the holder's balance and the total deposited power
+        let balance = Self::get_balance(env, holder) + amount;
+        Self::write_checkpoint(env, &Self::get_holder_key(env, holder), balance);
+        let total = Self::get_total(env) + amount;
+        Self::write_checkpoint(env, &TOTAL_CHECKPOINTS_KEY, total);
+
+        env.events().publish(
+            (symbol_short!("power"), symbol_short!("deposit")),
+            (holder, amount, balance),
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw deposited governance tokens, reducing voting power
+    /// Proposals that were snapshotted earlier keep the old balance
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address withdrawing tokens
+    /// * `amount` - The amount of tokens to withdraw
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn withdraw(env: &Env, holder: &Address, amount: i128) -> Result<(), Error> {
+        holder.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let balance = Self::get_balance(env, holder);
+        if amount > balance {
+            return Err(Error::InsufficientDeposit);
+        }
+
+        // Checkpoint the reduced balances before releasing the tokens
+        let balance = balance - amount;
+        Self::write_checkpoint(env, &Self::get_holder_key(env, holder), balance);
+        let total = Self::get_total(env) - amount;
+        Self::write_checkpoint(env, &TOTAL_CHECKPOINTS_KEY, total);
+
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&TOKEN_KEY)
+            .ok_or(Error::NotInitialized)?;
+        let token_client = TokenClient::new(env, &token);
+        token_client.transfer(&env.current_contract_address(), holder, &amount);
+
+        env.events().publish(
+            (symbol_short!("power"), symbol_short!("withdraw")),
+            (holder, amount, balance),
+        );
+
+        Ok(())
+    }
+
+    /// Get the current deposited balance of a holder
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address to check
+    ///
+    /// # Returns
+    /// * `i128` - The current deposited balance
+    pub fn get_balance(env: &Env, holder: &Address) -> i128 {
+        Self::latest(env, &Self::get_holder_key(env, holder))
+    }
+
+    /// Get the deposited balance of a holder as of a ledger sequence
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address to check
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `i128` - The balance recorded at or before the ledger
+    pub fn get_balance_at(env: &Env, holder: &Address, ledger: u32) -> i128 {
+        Self::lookup(env, &Self::get_holder_key(env, holder), ledger)
+    }
+
+    /// Get the current total deposited voting power
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `i128` - The total deposited voting power
+    pub fn get_total(env: &Env) -> i128 {
+        Self::latest(env, &TOTAL_CHECKPOINTS_KEY)
+    }
+
+    /// Get the total deposited voting power as of a ledger sequence
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `i128` - The total recorded at or before the ledger
+    pub fn get_total_at(env: &Env, ledger: u32) -> i128 {
+        Self::lookup(env, &TOTAL_CHECKPOINTS_KEY, ledger)
+    }
+
+    /// Get the balance of the most recent checkpoint
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the checkpoint history
+    ///
+    /// # Returns
+    /// * `i128` - The latest balance, or 0 if none was recorded yet
+    fn latest(env: &Env, key: &Symbol) -> i128 {
+        match Self::get_count(env, key) {
+            0 => 0,
+            count => Self::get_checkpoint(env, key, count - 1).balance,
+        }
+    }
+
+    /// Find the balance recorded at or before a ledger using binary search
+    /// over the individually stored checkpoints
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the checkpoint history
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `i128` - The matching balance, or 0 if none was recorded yet
+    fn lookup(env: &Env, key: &Symbol, ledger: u32) -> i128 {
+        let mut low = 0u32;
+        let mut high = Self::get_count(env, key);
+
+        // Find the first checkpoint recorded after the requested ledger
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::get_checkpoint(env, key, mid).ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        if low == 0 {
+            0
+        } else {
+            Self::get_checkpoint(env, key, low - 1).balance
+        }
+    }
+
+    /// Record a new balance for the current ledger
+    /// Each checkpoint is its own storage entry, so histories can grow without
+    /// making every update more expensive; changes within one ledger collapse
+    /// into a single checkpoint
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the checkpoint history
+    /// * `balance` - The new balance
+    fn write_checkpoint(env: &Env, key: &Symbol, balance: i128) {
+        let ledger = env.ledger().sequence();
+        let count = Self::get_count(env, key);
+
+        let checkpoint = Checkpoint { ledger, balance };
+        if count > 0 && Self::get_checkpoint(env, key, count - 1).ledger == ledger {
+            env.storage()
+                .persistent()
+                .set(&(key.clone(), count - 1), &checkpoint);
+        } else {
+            env.storage().persistent().set(&(key.clone(), count), &checkpoint);
+            env.storage().persistent().set(key, &(count + 1));
+        }
+    }
+
+    /// Get the number of checkpoints stored under a key
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the checkpoint history
+    ///
+    /// # Returns
+    /// * `u32` - The number of checkpoints
+    fn get_count(env: &Env, key: &Symbol) -> u32 {
+        env.storage().persistent().get(key).unwrap_or(0)
+    }
+
+    /// Get one checkpoint of a history
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the checkpoint history
+    /// * `index` - The position of the checkpoint, oldest first
+    ///
+    /// # Returns
+    /// * `Checkpoint` - The stored checkpoint
+    fn get_checkpoint(env: &Env, key: &Symbol, index: u32) -> Checkpoint {
+        env.storage()
+            .persistent()
+            .get(&(key.clone(), index))
+            .unwrap()
+    }
+
+    /// Generate a storage key for a holder's checkpoints
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address of the holder
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_holder_key(env: &Env, holder: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"CKPT_");
+        get_governance_op_key(env, key_bytes, 0, holder)
+    }
+}
@@ -0,0 +1,493 @@
+use crate::proposals::ProposalManager;
+use crate::types::{
+    ChoiceConfig, ChoiceResult, ChoiceVote, Error, ProposalStatus, TallyMethod, VotingConfig,
+    MAX_CHOICE_OPTIONS, MIN_CHOICE_OPTIONS,
+};
+use crate::utils::{get_governance_op_key, get_key_str};
+use crate::voting::VotingSystem;
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, Symbol, Vec};
+
+/// ChoiceVoting handles proposals with more than two options
+/// Supports plurality, approval and instant-runoff ranked choice tallies
+pub struct ChoiceVoting;
+
+impl ChoiceVoting {
+    /// Validate and store the options of a multi-choice proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `config` - The options and tally method
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the options are invalid
+    pub fn init(env: &Env, proposal_id: u32, config: &ChoiceConfig) -> Result<(), Error> {
+        Self::validate_config(config)?;
+
+        let key = Self::get_config_key(env, proposal_id);
+        env.storage().instance().set(&key, config);
+        Ok(())
+    }
+
+    /// Check that a choice configuration has a valid number of distinct options
+    ///
+    /// # Arguments
+    /// * `config` - The options and tally method
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the options are invalid
+    pub fn validate_config(config: &ChoiceConfig) -> Result<(), Error> {
+        let count = config.options.len();
+        if !(MIN_CHOICE_OPTIONS..=MAX_CHOICE_OPTIONS).contains(&count) {
+            return Err(Error::InvalidChoice);
+        }
+
+        // Option labels must be unique
+        for i in 0..count {
+            for j in (i + 1)..count {
+                if config.options.get_unchecked(i) == config.options.get_unchecked(j) {
+                    return Err(Error::InvalidChoice);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the options of a multi-choice proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Option<ChoiceConfig>` - The configuration, or None for yes/no proposals
+    pub fn get_config(env: &Env, proposal_id: u32) -> Option<ChoiceConfig> {
+        let key = Self::get_config_key(env, proposal_id);
+        env.storage().instance().get(&key)
+    }
+
+    /// Cast a ballot on a multi-choice proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal being voted on
+    /// * `voter` - The address of the voter
+    /// * `choices` - The selected option indexes, ordered by preference for ranked ballots
+    /// * `weight` - The voting weight of the voter
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn cast_vote(
+        env: &Env,
+        proposal_id: u32,
+        voter: &Address,
+        choices: Vec<u32>,
+        weight: i128,
+    ) -> Result<(), Error> {
+        // Require authentication from the voter
+        voter.require_auth();
+
+        let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+        let config = Self::get_config(env, proposal_id).ok_or(Error::WrongVoteType)?;
+
+        // Ballots are only accepted during the voting period
+        if env.ledger().timestamp() >= proposal.activated_at + proposal.voting_config.duration {
+            return Err(Error::InvalidVotingPeriod);
+        }
+
+        VotingSystem::check_voter_eligibility(env, &proposal, voter)?;
+        Self::validate_ballot(&config, &choices)?;
+
+        // Check if the voter has already voted
+        let key = Self::get_vote_key(env, proposal_id, voter);
+        if env.storage().instance().has(&key) {
+            return Err(Error::AlreadyVoted);
+        }
+
+        // Create the ballot and store it
+        let vote = ChoiceVote {
+            voter: voter.clone(),
+            choices: choices.clone(),
+            weight,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&key, &vote);
+
+        // Index the voter so the tally can find every ballot
+        let ballots_key = Self::get_ballots_key(env, proposal_id);
+        let ballot_count = Self::get_ballot_count(env, &ballots_key);
+        env.storage()
+            .persistent()
+            .set(&(ballots_key.clone(), ballot_count), voter);
+        env.storage().persistent().set(&ballots_key, &(ballot_count + 1));
+
+        // Each voter counts once toward quorum regardless of how many options they pick
+        VotingSystem::record_participation(env, proposal_id, weight);
+
+        env.events().publish(
+            (symbol_short!("vote"), symbol_short!("choice")),
+            (proposal_id, voter, choices, weight),
+        );
+
+        Ok(())
+    }
+
+    /// Get the ballot an address has cast on a multi-choice proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Option<ChoiceVote>` - The ballot, if any
+    pub fn get_vote(env: &Env, proposal_id: u32, voter: &Address) -> Option<ChoiceVote> {
+        let key = Self::get_vote_key(env, proposal_id, voter);
+        env.storage().instance().get(&key)
+    }
+
+    /// Replace the weight of a ballot that has already been cast
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - The address of the voter
+    /// * `weight` - The new voting weight
+    pub fn reweight_ballot(env: &Env, proposal_id: u32, voter: &Address, weight: i128) {
+        let key = Self::get_vote_key(env, proposal_id, voter);
+        let mut vote: ChoiceVote = match env.storage().instance().get(&key) {
+            Some(vote) => vote,
+            None => return,
+        };
+        let previous_weight = vote.weight;
+        if previous_weight == weight {
+            return;
+        }
+        vote.weight = weight;
+        env.storage().instance().set(&key, &vote);
+
+        VotingSystem::replace_participation(env, proposal_id, previous_weight, weight);
+    }
+
+    /// Get all ballots cast on a multi-choice proposal
+    /// Rebuilt from each indexed voter's ballot, so a reweighted ballot is read as it stands
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<ChoiceVote>` - The ballots in the order they were cast
+    pub fn get_ballots(env: &Env, proposal_id: u32) -> Vec<ChoiceVote> {
+        let key = Self::get_ballots_key(env, proposal_id);
+        let mut ballots = vec![env];
+        for index in 0..Self::get_ballot_count(env, &key) {
+            let voter: Address = env
+                .storage()
+                .persistent()
+                .get(&(key.clone(), index))
+                .unwrap();
+            if let Some(vote) = Self::get_vote(env, proposal_id, &voter) {
+                ballots.push_back(vote);
+            }
+        }
+        ballots
+    }
+
+    /// Tally a multi-choice proposal with its configured method
+    ///
+    /// Each method measures quorum on the votes it counts:
+    /// * Plurality - the option with the most votes wins; a tie for first has no winner.
+    ///   Quorum is measured on the votes for the winning option
+    /// * Approval - the most approved option wins if approved by `threshold` of participation.
+    ///   Quorum is measured on participation, counting each voter once however many
+    ///   options they approve
+    /// * Ranked choice - options are eliminated until one holds a majority of continuing ballots.
+    ///   Quorum is measured on the ballots still continuing in the final round
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `choice_config` - The options and tally method
+    /// * `config` - The voting configuration
+    ///
+    /// # Returns
+    /// * `Result<ChoiceResult, Error>` - The tally result or an error
+    pub fn tally(
+        env: &Env,
+        proposal_id: u32,
+        choice_config: &ChoiceConfig,
+        config: &VotingConfig,
+    ) -> Result<ChoiceResult, Error> {
+        let ballots = Self::get_ballots(env, proposal_id);
+        let count = choice_config.options.len();
+
+        let (winner, tallies, rounds, quorum) = match choice_config.method {
+            TallyMethod::Plurality | TallyMethod::Approval => {
+                // Every selected option receives the voter's full weight
+                let mut tallies = [0u128; MAX_CHOICE_OPTIONS as usize];
+                let mut backers = [0u128; MAX_CHOICE_OPTIONS as usize];
+                for ballot in ballots.iter() {
+                    for choice in ballot.choices.iter() {
+                        tallies[choice as usize] += ballot.weight as u128;
+                        backers[choice as usize] += 1;
+                    }
+                }
+
+                let mut winner =
+                    Self::unique_max(&tallies, count, &[false; MAX_CHOICE_OPTIONS as usize]);
+
+                let quorum = if choice_config.method == TallyMethod::Approval {
+                    // Approval winners also need support from `threshold` of participation
+                    let total_votes = VotingSystem::get_total_votes(env, proposal_id);
+                    if let Some(index) = winner {
+                        if total_votes == 0
+                            || tallies[index as usize] * 10000 / total_votes < config.threshold
+                        {
+                            winner = None;
+                        }
+                    }
+
+                    VotingSystem::has_quorum(env, proposal_id, config)?
+                } else {
+                    match winner {
+                        Some(index) => VotingSystem::meets_quorum(
+                            env,
+                            proposal_id,
+                            config,
+                            tallies[index as usize],
+                            backers[index as usize],
+                        )?,
+                        None => false,
+                    }
+                };
+
+                (winner, tallies, 1, quorum)
+            }
+            TallyMethod::RankedChoice => {
+                let (winner, tallies, rounds, continuing, continuing_ballots) =
+                    Self::instant_runoff(&ballots, count);
+                let quorum = VotingSystem::meets_quorum(
+                    env,
+                    proposal_id,
+                    config,
+                    continuing,
+                    continuing_ballots,
+                )?;
+
+                (winner, tallies, rounds, quorum)
+            }
+        };
+
+        // Without quorum no option wins
+        let winner = if quorum { winner } else { None };
+
+        let mut result_tallies = vec![env];
+        for i in 0..count {
+            result_tallies.push_back(tallies[i as usize]);
+        }
+
+        Ok(ChoiceResult {
+            winner,
+            tallies: result_tallies,
+            rounds,
+        })
+    }
+
+    /// Resolve ranked ballots by instant runoff
+    /// Each round counts ballots for their highest-ranked remaining option and eliminates
+    /// the weakest options until one holds a strict majority of the continuing ballots
+    ///
+    /// # Arguments
+    /// * `ballots` - The ranked ballots
+    /// * `count` - The number of options
+    ///
+    /// # Returns
+    /// * `(Option<u32>, [u128; MAX_CHOICE_OPTIONS], u32, u128, u128)` - Winner, final round
+    ///   tallies, rounds, and the weight and number of ballots continuing in the final round
+    fn instant_runoff(
+        ballots: &Vec<ChoiceVote>,
+        count: u32,
+    ) -> (Option<u32>, [u128; MAX_CHOICE_OPTIONS as usize], u32, u128, u128) {
+        let mut eliminated = [false; MAX_CHOICE_OPTIONS as usize];
+        let mut rounds = 0u32;
+
+        loop {
+            rounds += 1;
+
+            // Count each ballot for its highest-ranked option still in the race
+            let mut tallies = [0u128; MAX_CHOICE_OPTIONS as usize];
+            let mut continuing = 0u128;
+            let mut continuing_ballots = 0u128;
+            for ballot in ballots.iter() {
+                if let Some(choice) = ballot.choices.iter().find(|c| !eliminated[*c as usize]) {
+                    tallies[choice as usize] += ballot.weight as u128;
+                    continuing += ballot.weight as u128;
+                    continuing_ballots += 1;
+                }
+            }
+
+            if continuing == 0 {
+                return (None, tallies, rounds, continuing, continuing_ballots);
+            }
+
+            // A strict majority of continuing ballots wins
+            if let Some(leader) = Self::unique_max(&tallies, count, &eliminated) {
+                if tallies[leader as usize] * 2 > continuing {
+                    return (Some(leader), tallies, rounds, continuing, continuing_ballots);
+                }
+            }
+
+            // Eliminate every option tied for the lowest count
+            let mut lowest = u128::MAX;
+            for i in 0..count as usize {
+                if !eliminated[i] && tallies[i] < lowest {
+                    lowest = tallies[i];
+                }
+            }
+            let mut remaining = 0u32;
+            for i in 0..count as usize {
+                if !eliminated[i] && tallies[i] != lowest {
+                    remaining += 1;
+                }
+            }
+
+            // If every remaining option is tied there is no winner
+            if remaining == 0 {
+                return (None, tallies, rounds, continuing, continuing_ballots);
+            }
+            for i in 0..count as usize {
+                if !eliminated[i] && tallies[i] == lowest {
+                    eliminated[i] = true;
+                }
+            }
+        }
+    }
+
+    /// Find the option with strictly the most votes
+    ///
+    /// # Arguments
+    /// * `tallies` - Votes per option
+    /// * `count` - The number of options
+    /// * `excluded` - Options to ignore
+    ///
+    /// # Returns
+    /// * `Option<u32>` - The leading option, or None if nobody leads alone
+    fn unique_max(
+        tallies: &[u128; MAX_CHOICE_OPTIONS as usize],
+        count: u32,
+        excluded: &[bool; MAX_CHOICE_OPTIONS as usize],
+    ) -> Option<u32> {
+        let mut leader: Option<u32> = None;
+        let mut tied = false;
+        for i in 0..count {
+            if excluded[i as usize] || tallies[i as usize] == 0 {
+                continue;
+            }
+            match leader {
+                Some(l) if tallies[i as usize] == tallies[l as usize] => tied = true,
+                Some(l) if tallies[i as usize] < tallies[l as usize] => {}
+                _ => {
+                    leader = Some(i);
+                    tied = false;
+                }
+            }
+        }
+
+        if tied {
+            None
+        } else {
+            leader
+        }
+    }
+
+    /// Check a ballot against the proposal's tally method
+    ///
+    /// # Arguments
+    /// * `config` - The options and tally method
+    /// * `choices` - The selected option indexes
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the ballot is malformed
+    fn validate_ballot(config: &ChoiceConfig, choices: &Vec<u32>) -> Result<(), Error> {
+        let count = config.options.len();
+        if choices.is_empty() || choices.len() > count {
+            return Err(Error::InvalidChoice);
+        }
+        if config.method == TallyMethod::Plurality && choices.len() != 1 {
+            return Err(Error::InvalidChoice);
+        }
+
+        // Every choice must be a known option and appear only once
+        for i in 0..choices.len() {
+            let choice = choices.get_unchecked(i);
+            if choice >= count {
+                return Err(Error::InvalidChoice);
+            }
+            for j in (i + 1)..choices.len() {
+                if choices.get_unchecked(j) == choice {
+                    return Err(Error::InvalidChoice);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generate a storage key for a proposal's choice configuration
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_config_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"CHOICE_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
+    /// Get the number of voters indexed under a proposal's ballot key
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `key` - The storage key of the proposal's ballots
+    ///
+    /// # Returns
+    /// * `u32` - The number of ballots cast
+    fn get_ballot_count(env: &Env, key: &Symbol) -> u32 {
+        env.storage().persistent().get(key).unwrap_or(0)
+    }
+
+    /// Generate a storage key for a proposal's ballots
+    /// The ballot count is stored under the key and each voter under `(key, index)`
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_ballots_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"BALLOTS_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
+    /// Generate a storage key for a voter's ballot
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_vote_key(env: &Env, proposal_id: u32, voter: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"CVOTE_");
+        get_governance_op_key(env, key_bytes, proposal_id, voter)
+    }
+}
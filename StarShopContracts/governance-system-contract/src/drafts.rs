@@ -0,0 +1,284 @@
+use crate::checkpoints::VotingPowerLedger;
+use crate::choices::ChoiceVoting;
+use crate::proposals::ProposalManager;
+use crate::types::{
+    Action, Error, Proposal, ProposalRevision, ProposalStatus, ProposalType, Sponsorship,
+    SponsorshipConfig, SPONSORSHIP_KEY,
+};
+use crate::utils::get_key_str;
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, String, Symbol, Vec};
+
+/// DraftManager runs the sponsorship phase of draft proposals
+/// A draft collects co-sponsors and may be amended by its proposer; it can only be
+/// activated once it is sufficiently sponsored and its discussion period has elapsed
+pub struct DraftManager;
+
+impl DraftManager {
+    /// Get the sponsorship and discussion requirements
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `SponsorshipConfig` - The stored requirements, or no requirements if never set
+    pub fn get_config(env: &Env) -> SponsorshipConfig {
+        env.storage()
+            .instance()
+            .get(&SPONSORSHIP_KEY)
+            .unwrap_or(SponsorshipConfig {
+                min_sponsors: 0,
+                min_sponsor_weight: 0,
+                discussion_period: 0,
+            })
+    }
+
+    /// Update the sponsorship and discussion requirements
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `config` - The new requirements
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the weight threshold is negative
+    pub fn set_config(env: &Env, config: &SponsorshipConfig) -> Result<(), Error> {
+        if config.min_sponsor_weight < 0 {
+            return Err(Error::InvalidProposalInput);
+        }
+
+        env.storage().instance().set(&SPONSORSHIP_KEY, config);
+        env.events().publish(
+            (symbol_short!("draft"), symbol_short!("config")),
+            config.clone(),
+        );
+        Ok(())
+    }
+
+    /// Co-sponsor a draft proposal
+    /// The recorded weight is the sponsor's deposited voting power at the time of sponsoring;
+    /// readiness is checked against the power sponsors hold at activation
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `sponsor` - The co-sponsoring address
+    /// * `proposal_id` - The ID of the draft
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The number of co-sponsors so far or an error
+    pub fn sponsor(env: &Env, sponsor: &Address, proposal_id: u32) -> Result<u32, Error> {
+        sponsor.require_auth();
+
+        let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        if proposal.status != ProposalStatus::Draft {
+            return Err(Error::InvalidProposalStatus);
+        }
+        // The proposer already backs their own draft
+        if proposal.proposer == *sponsor {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut sponsors = Self::get_sponsors(env, proposal_id);
+        if sponsors.iter().any(|sponsorship| sponsorship.sponsor == *sponsor) {
+            return Err(Error::AlreadySponsored);
+        }
+
+        let weight = VotingPowerLedger::get_balance(env, sponsor);
+        sponsors.push_back(Sponsorship {
+            sponsor: sponsor.clone(),
+            weight,
+            timestamp: env.ledger().timestamp(),
+        });
+        let key = Self::get_sponsors_key(env, proposal_id);
+        env.storage().instance().set(&key, &sponsors);
+
+        env.events().publish(
+            (symbol_short!("draft"), symbol_short!("sponsor")),
+            (proposal_id, sponsor, weight),
+        );
+
+        Ok(sponsors.len())
+    }
+
+    /// Publish an amended version of a draft proposal
+    /// Amending clears existing sponsorships, as sponsors backed the previous text,
+    /// and restarts the discussion period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposer` - The proposer of the draft
+    /// * `proposal_id` - The ID of the draft
+    /// * `description` - The amended description
+    /// * `metadata_hash` - The amended metadata hash
+    /// * `actions` - The amended actions
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The new revision number or an error
+    pub fn amend(
+        env: &Env,
+        proposer: &Address,
+        proposal_id: u32,
+        description: Symbol,
+        metadata_hash: String,
+        actions: Vec<Action>,
+    ) -> Result<u32, Error> {
+        proposer.require_auth();
+
+        let mut proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        if proposal.proposer != *proposer {
+            return Err(Error::Unauthorized);
+        }
+        if proposal.status != ProposalStatus::Draft {
+            return Err(Error::InvalidProposalStatus);
+        }
+
+        // Multi-choice proposals carry no actions
+        if ChoiceVoting::get_config(env, proposal_id).is_some() {
+            if !actions.is_empty() {
+                return Err(Error::InvalidAction);
+            }
+        } else {
+            ProposalManager::validate_actions(env, &proposal.proposal_type, &actions)?;
+        }
+
+        proposal.description = description;
+        proposal.metadata_hash = metadata_hash;
+        proposal.actions = actions;
+        ProposalManager::save_proposal(env, &proposal);
+
+        let revision = Self::record_revision(env, &proposal);
+        env.storage()
+            .instance()
+            .remove(&Self::get_sponsors_key(env, proposal_id));
+
+        env.events().publish(
+            (symbol_short!("draft"), symbol_short!("amended")),
+            (proposal_id, revision),
+        );
+
+        Ok(revision)
+    }
+
+    /// Record the current content of a proposal as its next revision
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal to record
+    ///
+    /// # Returns
+    /// * `u32` - The number of the recorded revision
+    pub fn record_revision(env: &Env, proposal: &Proposal) -> u32 {
+        let mut revisions = Self::get_revisions(env, proposal.id);
+        let revision = revisions.len() + 1;
+        revisions.push_back(ProposalRevision {
+            revision,
+            description: proposal.description.clone(),
+            metadata_hash: proposal.metadata_hash.clone(),
+            actions: proposal.actions.clone(),
+            published_at: env.ledger().timestamp(),
+        });
+        let key = Self::get_revisions_key(env, proposal.id);
+        env.storage().instance().set(&key, &revisions);
+        revision
+    }
+
+    /// Ensure a draft may be activated
+    /// Emergency proposals take the fast track and skip the sponsorship phase
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The draft to check
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the draft is not ready
+    pub fn check_ready(env: &Env, proposal: &Proposal) -> Result<(), Error> {
+        if proposal.proposal_type == ProposalType::EmergencyAction {
+            return Ok(());
+        }
+
+        let config = Self::get_config(env);
+
+        // The discussion period runs from the latest revision
+        let published_at = Self::get_revisions(env, proposal.id)
+            .last()
+            .map(|revision| revision.published_at)
+            .unwrap_or(proposal.created_at);
+        if env.ledger().timestamp() < published_at + config.discussion_period {
+            return Err(Error::DiscussionPeriodActive);
+        }
+
+        if config.min_sponsors == 0 && config.min_sponsor_weight == 0 {
+            return Ok(());
+        }
+
+        // Weigh sponsors by the power they hold now, so tokens moved between
+        // accounts after sponsoring are only counted once
+        let sponsors = Self::get_sponsors(env, proposal.id);
+        let weight: i128 = sponsors
+            .iter()
+            .map(|sponsorship| VotingPowerLedger::get_balance(env, &sponsorship.sponsor))
+            .sum();
+        let enough_sponsors = config.min_sponsors > 0 && sponsors.len() >= config.min_sponsors;
+        let enough_weight = config.min_sponsor_weight > 0 && weight >= config.min_sponsor_weight;
+        if !enough_sponsors && !enough_weight {
+            return Err(Error::InsufficientSponsorship);
+        }
+        Ok(())
+    }
+
+    /// Get the co-sponsors of a draft proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<Sponsorship>` - The co-sponsors of the current revision
+    pub fn get_sponsors(env: &Env, proposal_id: u32) -> Vec<Sponsorship> {
+        let key = Self::get_sponsors_key(env, proposal_id);
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Get the published revisions of a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<ProposalRevision>` - The revisions, oldest first
+    pub fn get_revisions(env: &Env, proposal_id: u32) -> Vec<ProposalRevision> {
+        let key = Self::get_revisions_key(env, proposal_id);
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Generate a storage key for a draft's co-sponsors
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_sponsors_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"SPON_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
+    /// Generate a storage key for a proposal's revisions
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_revisions_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"REV_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::execution::ExecutionEngine;
+use crate::proposals::ProposalManager;
+use crate::types::{
+    Action, EmergencyConfig, Error, ProposalStatus, ProposalType, DEFAULT_EMERGENCY_APPROVALS,
+    DEFAULT_EMERGENCY_DURATION, DEFAULT_MAX_PAUSE_DURATION, EMERGENCY_KEY,
+};
+use crate::utils::get_key_str;
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, Symbol, Vec};
+
+/// EmergencyManager runs the fast-track path for emergency proposals
+/// Emergency proposals may only pause or unpause contracts, vote over a short period,
+/// and execute as soon as enough moderators approve them
+pub struct EmergencyManager;
+
+impl EmergencyManager {
+    /// Get the emergency fast-track settings
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `EmergencyConfig` - The stored settings or the defaults
+    pub fn get_config(env: &Env) -> EmergencyConfig {
+        env.storage()
+            .instance()
+            .get(&EMERGENCY_KEY)
+            .unwrap_or(EmergencyConfig {
+                voting_duration: DEFAULT_EMERGENCY_DURATION,
+                approvals: DEFAULT_EMERGENCY_APPROVALS,
+                max_pause_duration: DEFAULT_MAX_PAUSE_DURATION,
+            })
+    }
+
+    /// Update the emergency fast-track settings
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `config` - The new settings
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if a setting is zero
+    pub fn set_config(env: &Env, config: &EmergencyConfig) -> Result<(), Error> {
+        if config.voting_duration == 0 || config.approvals == 0 || config.max_pause_duration == 0
+        {
+            return Err(Error::InvalidEmergencyConfig);
+        }
+
+        env.storage().instance().set(&EMERGENCY_KEY, config);
+        env.events().publish(
+            (symbol_short!("emergency"), symbol_short!("config")),
+            config.clone(),
+        );
+        Ok(())
+    }
+
+    /// Ensure an emergency proposal only pauses or unpauses contracts
+    ///
+    /// # Arguments
+    /// * `actions` - The actions of the proposal
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if another action is present
+    pub fn validate_actions(actions: &Vec<Action>) -> Result<(), Error> {
+        if actions.is_empty() {
+            return Err(Error::InvalidAction);
+        }
+
+        for action in actions.iter() {
+            if !matches!(action, Action::Pause(..) | Action::Unpause(..)) {
+                return Err(Error::InvalidAction);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a moderator's approval of an active emergency proposal
+    /// Once the approval threshold is reached the proposal passes and its actions
+    /// are executed immediately, skipping the rest of the vote and the timelock
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `moderator` - The approving moderator
+    /// * `proposal_id` - The ID of the emergency proposal
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The number of approvals recorded so far or an error
+    pub fn approve(env: &Env, moderator: &Address, proposal_id: u32) -> Result<u32, Error> {
+        moderator.require_auth();
+        if !ProposalManager::is_moderator(env, moderator) {
+            return Err(Error::Unauthorized);
+        }
+
+        let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        if proposal.proposal_type != ProposalType::EmergencyAction {
+            return Err(Error::NotEmergencyProposal);
+        }
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+
+        let mut approvals = Self::get_approvals(env, proposal_id);
+        if approvals.contains(moderator) {
+            return Err(Error::AlreadyApproved);
+        }
+        approvals.push_back(moderator.clone());
+        let key = Self::get_approvals_key(env, proposal_id);
+        env.storage().instance().set(&key, &approvals);
+
+        env.events().publish(
+            (symbol_short!("emergency"), symbol_short!("approve")),
+            (proposal_id, moderator, approvals.len()),
+        );
+
+        // Enough moderators agree: pass and execute without waiting
+        if approvals.len() >= Self::get_config(env).approvals {
+            ProposalManager::mark_passed(env, proposal_id)?;
+            ExecutionEngine::run_actions(env, moderator, &proposal);
+            ProposalManager::mark_executed(env, proposal_id)?;
+        }
+
+        Ok(approvals.len())
+    }
+
+    /// Get the moderators that approved an emergency proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The approving moderators
+    pub fn get_approvals(env: &Env, proposal_id: u32) -> Vec<Address> {
+        let key = Self::get_approvals_key(env, proposal_id);
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Generate a storage key for an emergency proposal's approvals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_approvals_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"EAPP_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+}
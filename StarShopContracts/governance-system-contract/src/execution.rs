@@ -1,31 +1,113 @@
+use crate::emergency::EmergencyManager;
+use crate::proposals::ProposalManager;
+use crate::treasury::Treasury;
 use crate::types::{
-    Action, Error, Proposal, ProposalStatus, AUCTION_KEY, MODERATOR_KEY, REFERRAL_KEY,
+    Action, ActionResult, AllowedCall, Error, Proposal, ProposalStatus, ALLOWLIST_KEY,
+    AUCTION_KEY, DEFAULT_GRACE_PERIOD, GRACE_PERIOD_KEY, MODERATOR_KEY, REFERRAL_KEY,
     REQUIREMENTS_KEY,
 };
-use soroban_sdk::{symbol_short, vec, Address, Env, IntoVal, Symbol, Vec};
+use crate::utils::get_key_str;
+use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, IntoVal, Symbol, Val, Vec};
 
 /// ExecutionEngine is responsible for executing approved proposals
 /// Handles the execution logic for different types of governance actions
 pub struct ExecutionEngine;
 
 impl ExecutionEngine {
-    /// Check if the execution delay period has passed for a proposal
+    /// Queue a passed proposal in the timelock
+    /// The proposal becomes executable once its ETA is reached
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal to queue
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - The ETA timestamp or an error
+    pub fn queue(env: &Env, proposal: &Proposal) -> Result<u64, Error> {
+        ProposalManager::mark_queued(env, proposal.id)?;
+
+        // Record when the proposal becomes executable
+        let eta = env.ledger().timestamp() + proposal.voting_config.execution_delay;
+        let key = Self::get_eta_key(env, proposal.id);
+        env.storage().instance().set(&key, &eta);
+
+        env.events().publish(
+            (symbol_short!("proposal"), symbol_short!("queued")),
+            (proposal.id, eta),
+        );
+
+        Ok(eta)
+    }
+
+    /// Get the ETA recorded when a proposal was queued
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The ETA timestamp if the proposal was queued
+    pub fn get_eta(env: &Env, proposal_id: u32) -> Option<u64> {
+        let key = Self::get_eta_key(env, proposal_id);
+        env.storage().instance().get(&key)
+    }
+
+    /// Get the time a queued proposal stays executable after its ETA
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `u64` - The grace period in seconds
+    pub fn get_grace_period(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&GRACE_PERIOD_KEY)
+            .unwrap_or(DEFAULT_GRACE_PERIOD)
+    }
+
+    /// Check that a queued proposal is inside its execution window [ETA, ETA + grace period]
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `proposal` - The proposal to check
     ///
     /// # Returns
-    /// * `Result<bool, Error>` - True if execution delay has passed, false otherwise
-    pub fn check_execution_delay(env: &Env, proposal: &Proposal) -> Result<bool, Error> {
+    /// * `Result<(), Error>` - Success or an error if outside the window
+    pub fn check_timelock(env: &Env, proposal: &Proposal) -> Result<(), Error> {
+        let eta = Self::get_eta(env, proposal.id).ok_or(Error::ProposalNotExecutable)?;
         let current_time = env.ledger().timestamp();
-        let voting_end_time = proposal.activated_at + proposal.voting_config.duration;
-        let execution_time = voting_end_time + proposal.voting_config.execution_delay;
 
-        Ok(current_time >= execution_time)
+        if current_time < eta {
+            return Err(Error::ExecutionDelayNotMet);
+        }
+        if current_time > eta + Self::get_grace_period(env) {
+            return Err(Error::ProposalExpired);
+        }
+
+        Ok(())
     }
 
-    /// Execute an approved proposal
+    /// Expire a queued proposal whose execution window has passed
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal to expire
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn expire(env: &Env, proposal: &Proposal) -> Result<(), Error> {
+        if proposal.status != ProposalStatus::Queued {
+            return Err(Error::InvalidProposalStatus);
+        }
+
+        match Self::check_timelock(env, proposal) {
+            Err(Error::ProposalExpired) => ProposalManager::mark_expired(env, proposal.id),
+            _ => Err(Error::ExecutionWindowOpen),
+        }
+    }
+
+    /// Execute an approved proposal once its timelock has elapsed
     ///
     /// # Arguments
     /// * `env` - The environment object
@@ -33,39 +115,200 @@ impl ExecutionEngine {
     /// * `proposal` - The proposal to execute
     ///
     /// # Returns
-    /// * `Result<(), Error>` - Success or an error
-    pub fn execute(env: &Env, executor: &Address, proposal: &Proposal) -> Result<(), Error> {
+    /// * `Result<Vec<ActionResult>, Error>` - The outcome of each action or an error
+    pub fn execute(
+        env: &Env,
+        executor: &Address,
+        proposal: &Proposal,
+    ) -> Result<Vec<ActionResult>, Error> {
         // Verify authorization from the executor
         executor.require_auth();
 
-        // Ensure the proposal is in the 'Passed' status
-        if proposal.status != ProposalStatus::Passed {
+        // Ensure the proposal has been queued in the timelock
+        if proposal.status != ProposalStatus::Queued {
             return Err(Error::InvalidProposalStatus);
         }
 
-        // Check if the execution delay has passed
-        if !Self::check_execution_delay(env, proposal)? {
-            return Err(Error::ExecutionDelayNotMet);
-        }
+        // Check that the proposal is inside its execution window
+        Self::check_timelock(env, proposal)?;
+
+        Ok(Self::run_actions(env, executor, proposal))
+    }
 
+    /// Run every action of a proposal and store the results
+    /// A failing action is recorded without stopping the others
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `executor` - The address of the account executing the proposal
+    /// * `proposal` - The proposal whose actions are run
+    ///
+    /// # Returns
+    /// * `Vec<ActionResult>` - The outcome of each action
+    pub fn run_actions(env: &Env, executor: &Address, proposal: &Proposal) -> Vec<ActionResult> {
         let proposal_id = proposal.id;
         Self::log_execution_start(env, proposal_id, executor);
 
-        // Execute each action in the proposal and log results
+        // Execute each action in the proposal and record results
+        let mut results: Vec<ActionResult> = vec![env];
+        let mut all_succeeded = true;
         for (i, action) in proposal.actions.iter().enumerate() {
             Self::log_action_execution(env, proposal_id, i as u32);
-            match Self::execute_action(env, &action) {
-                Ok(_) => {
-                    Self::log_action_result(env, proposal_id, i as u32, true);
-                }
+            let error = match Self::execute_action(env, proposal_id, &action) {
+                Ok(_) => None,
                 Err(e) => {
-                    Self::log_action_result(env, proposal_id, i as u32, false);
-                    return Err(e);
+                    all_succeeded = false;
+                    Some(e as u32)
                 }
+            };
+            Self::log_action_result(env, proposal_id, i as u32, error.is_none());
+            results.push_back(ActionResult {
+                index: i as u32,
+                success: error.is_none(),
+                error,
+            });
+        }
+
+        // Store the results so they can be inspected after execution
+        let key = Self::get_results_key(env, proposal_id);
+        env.storage().instance().set(&key, &results);
+
+        Self::log_execution_complete(env, proposal_id, all_succeeded);
+        results
+    }
+
+    /// Get the recorded action results of an executed proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<ActionResult>` - The action results, empty if not executed
+    pub fn get_results(env: &Env, proposal_id: u32) -> Vec<ActionResult> {
+        let key = Self::get_results_key(env, proposal_id);
+        env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Add a (contract, function) pair to the call allowlist
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `contract` - The target contract address
+    /// * `function` - The function name to allow
+    pub fn allow_call(env: &Env, contract: &Address, function: &Symbol) {
+        if Self::is_call_allowed(env, contract, function) {
+            return;
+        }
+
+        let mut allowlist = Self::get_allowed_calls(env);
+        allowlist.push_back(AllowedCall {
+            contract: contract.clone(),
+            function: function.clone(),
+        });
+        env.storage().instance().set(&ALLOWLIST_KEY, &allowlist);
+
+        env.events().publish(
+            (symbol_short!("allow"), symbol_short!("added")),
+            (contract, function),
+        );
+    }
+
+    /// Remove a (contract, function) pair from the call allowlist
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `contract` - The target contract address
+    /// * `function` - The function name to disallow
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the pair was not allowed
+    pub fn disallow_call(env: &Env, contract: &Address, function: &Symbol) -> Result<(), Error> {
+        let allowlist = Self::get_allowed_calls(env);
+
+        // Create new list excluding the specified pair
+        let mut new_allowlist = vec![env];
+        for entry in allowlist.iter() {
+            if entry.contract != *contract || entry.function != *function {
+                new_allowlist.push_back(entry);
             }
         }
 
-        Self::log_execution_complete(env, proposal_id, true);
+        if new_allowlist.len() == allowlist.len() {
+            return Err(Error::CallNotAllowed);
+        }
+        env.storage().instance().set(&ALLOWLIST_KEY, &new_allowlist);
+
+        env.events().publish(
+            (symbol_short!("allow"), symbol_short!("removed")),
+            (contract, function),
+        );
+
+        Ok(())
+    }
+
+    /// Get all (contract, function) pairs governance is allowed to call
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<AllowedCall>` - The allowlist
+    pub fn get_allowed_calls(env: &Env) -> Vec<AllowedCall> {
+        env.storage()
+            .instance()
+            .get(&ALLOWLIST_KEY)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Check whether a (contract, function) pair is on the allowlist
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `contract` - The target contract address
+    /// * `function` - The function name
+    ///
+    /// # Returns
+    /// * `bool` - True if the call is allowed
+    pub fn is_call_allowed(env: &Env, contract: &Address, function: &Symbol) -> bool {
+        Self::get_allowed_calls(env)
+            .iter()
+            .any(|entry| entry.contract == *contract && entry.function == *function)
+    }
+
+    /// Ensure every generic call in a list of actions targets an allowlisted pair,
+    /// every treasury payout has a positive amount and duration, and every pause
+    /// lasts no longer than the emergency limit
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `actions` - The actions to validate
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if a call is not allowed
+    pub fn validate_actions(env: &Env, actions: &Vec<Action>) -> Result<(), Error> {
+        for action in actions.iter() {
+            match action {
+                Action::Invoke(call)
+                    if !Self::is_call_allowed(env, &call.contract, &call.function) =>
+                {
+                    return Err(Error::CallNotAllowed);
+                }
+                Action::TreasuryTransfer(_, _, amount) if amount <= 0 => {
+                    return Err(Error::InvalidAction);
+                }
+                Action::StreamGrant(terms) if terms.amount <= 0 || terms.duration == 0 => {
+                    return Err(Error::InvalidAction);
+                }
+                Action::Pause(_, _, duration) if !Self::is_valid_pause(env, duration) => {
+                    return Err(Error::InvalidAction);
+                }
+                _ => {}
+            }
+        }
         Ok(())
     }
 
@@ -73,11 +316,12 @@ impl ExecutionEngine {
     ///
     /// # Arguments
     /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal being executed
     /// * `action` - The action to execute
     ///
     /// # Returns
     /// * `Result<(), Error>` - Success or an error
-    fn execute_action(env: &Env, action: &Action) -> Result<(), Error> {
+    fn execute_action(env: &Env, proposal_id: u32, action: &Action) -> Result<(), Error> {
         match action {
             // Update proposal requirements
             Action::UpdateProposalRequirements(requirements) => {
@@ -143,13 +387,8 @@ impl ExecutionEngine {
                     .ok_or(Error::NotInitialized)?;
 
                 // Call the set_reward_rates function on the referral contract
-                env.invoke_contract::<()>(
-                    &referral,
-                    &Symbol::new(&env, "set_reward_rates"),
-                    Vec::from_array(&env, [rates.into_val(env)]),
-                );
-
-                Ok(())
+                let args = vec![env, rates.into_val(env)];
+                Self::call_target(env, &referral, "set_reward_rates", args)
             }
 
             // Update level requirements in the referral contract
@@ -162,17 +401,12 @@ impl ExecutionEngine {
                     .ok_or(Error::NotInitialized)?;
 
                 // Call the set_level_requirements function on the referral contract
-                env.invoke_contract::<()>(
-                    &referral,
-                    &Symbol::new(&env, "set_level_requirements"),
-                    Vec::from_array(&env, [requirements.into_val(env)]),
-                );
-
-                Ok(())
+                let args = vec![env, requirements.into_val(env)];
+                Self::call_target(env, &referral, "set_level_requirements", args)
             }
 
-            // Update auction conditions in the auction contract
-            Action::UpdateAuctionConditions(auction_id, conditions) => {
+            // Update the default platform fee in the auction contract
+            Action::UpdateAuctionPlatformFee(recipient, fee_bps) => {
                 // Get the auction contract address
                 let auction: Address = env
                     .storage()
@@ -180,18 +414,139 @@ impl ExecutionEngine {
                     .get(&AUCTION_KEY)
                     .ok_or(Error::NotInitialized)?;
 
-                // Call the update_conditions function on the auction contract
-                env.invoke_contract::<()>(
-                    &auction,
-                    &Symbol::new(&env, "update_conditions"),
-                    Vec::from_array(env, [auction_id.into_val(env), conditions.into_val(env)]),
-                );
+                // Call the set_platform_fee function on the auction contract
+                let args = vec![env, recipient.into_val(env), fee_bps.into_val(env)];
+                Self::call_target(env, &auction, "set_platform_fee", args)
+            }
+
+            // Call an allowlisted function on an arbitrary contract
+            Action::Invoke(call) => {
+                // Re-check the allowlist in case it changed after the proposal was created
+                if !Self::is_call_allowed(env, &call.contract, &call.function) {
+                    return Err(Error::CallNotAllowed);
+                }
+
+                // Failures in the target contract are caught and reported for this action only
+                match env.try_invoke_contract::<Val, soroban_sdk::Error>(
+                    &call.contract,
+                    &call.function,
+                    call.args.clone(),
+                ) {
+                    Ok(Ok(_)) => Ok(()),
+                    _ => Err(Error::ExecutionFailed),
+                }
+            }
+
+            // Pay tokens out of the treasury
+            Action::TreasuryTransfer(token, recipient, amount) => {
+                Treasury::transfer(env, proposal_id, token, recipient, *amount)
+            }
 
+            // Start a grant that streams treasury tokens to its recipient
+            Action::StreamGrant(terms) => {
+                Treasury::create_grant(env, proposal_id, terms)?;
                 Ok(())
             }
+
+            // Pause a contract or one of its functions; the pause lifts itself after `duration`
+            Action::Pause(contract, scope, duration) => {
+                // Re-check the limit in case it was lowered after the proposal was created
+                if !Self::is_valid_pause(env, *duration) {
+                    return Err(Error::InvalidAction);
+                }
+
+                let until = env.ledger().timestamp() + duration;
+                let args = vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    scope.into_val(env),
+                    until.into_val(env),
+                ];
+                Self::call_target(env, contract, "pause", args)
+            }
+
+            // Lift a pause before it expires
+            Action::Unpause(contract, scope) => {
+                let args = vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    scope.into_val(env),
+                ];
+                Self::call_target(env, contract, "unpause", args)
+            }
+
+            // Upgrade a contract that accepts governance as its upgrade authority
+            Action::UpgradeContract(target, wasm_hash) => {
+                let args = vec![env, wasm_hash.into_val(env)];
+                Self::call_target(env, target, "upgrade", args)
+            }
+        }
+    }
+
+    /// Check that a pause duration is positive and within the emergency limit
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `duration` - The requested pause duration in seconds
+    ///
+    /// # Returns
+    /// * `bool` - True if the duration is allowed
+    fn is_valid_pause(env: &Env, duration: u64) -> bool {
+        duration > 0 && duration <= EmergencyManager::get_config(env).max_pause_duration
+    }
+
+    /// Call a pause or upgrade function on a target contract, authorized by governance
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `contract` - The target contract
+    /// * `function` - The function name
+    /// * `args` - The arguments passed to the function
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the target rejected the call
+    fn call_target(
+        env: &Env,
+        contract: &Address,
+        function: &str,
+        args: Vec<Val>,
+    ) -> Result<(), Error> {
+        match env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            contract,
+            &Symbol::new(env, function),
+            args,
+        ) {
+            Ok(Ok(_)) => Ok(()),
+            _ => Err(Error::ExecutionFailed),
         }
     }
 
+    /// Generate a storage key for a queued proposal's ETA
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_eta_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"ETA_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
+    /// Generate a storage key for a proposal's action results
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_results_key(env: &Env, proposal_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"EXEC_");
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
     /// Internal helper to log the start of execution
     ///
     /// # Arguments
@@ -1,12 +1,23 @@
+use crate::checkpoints::VotingPowerLedger;
+use crate::choices::ChoiceVoting;
+use crate::drafts::DraftManager;
+use crate::emergency::EmergencyManager;
 use crate::execution::ExecutionEngine;
 use crate::proposals::ProposalManager;
+use crate::signatures::SignatureVoting;
+use crate::treasury::Treasury;
 use crate::types::{
-    Action, Error, Proposal, ProposalStatus, ProposalType, VotingConfig, ADMIN_KEY, AUCTION_KEY,
-    REFERRAL_KEY, TOKEN_KEY,
+    Action, ActionResult, AllowedCall, ChoiceConfig, ChoiceResult, Delegation, DelegationScope,
+    EmergencyConfig, Error, Grant, Outflow, Proposal, ProposalRevision, ProposalStatus,
+    ProposalType, SignedVote, SignedVoteResult, SpendingLimit, Sponsorship, SponsorshipConfig,
+    TreasuryBalance, Vote, VoteSupport, VotingConfig, WeightingMode, ADMIN_KEY, AUCTION_KEY,
+    GRACE_PERIOD_KEY, GUARDIAN_KEY, MAX_SIGNED_VOTES, REFERRAL_KEY, TOKEN_KEY,
 };
 use crate::voting::VotingSystem;
 use crate::weights::WeightCalculator;
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
 
 /// Main contract for the governance system
 /// Handles all governance-related operations including proposal management,
@@ -102,6 +113,122 @@ impl GovernanceContract {
         )
     }
 
+    /// Create a multi-choice proposal that picks one of several options
+    /// Multi-choice proposals carry no actions; the winning option is recorded as the outcome
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposer` - The address creating the proposal
+    /// * `title` - The title of the proposal
+    /// * `description` - The description of the proposal
+    /// * `metadata_hash` - A hash pointing to additional metadata
+    /// * `proposal_type` - The type of proposal
+    /// * `choice_config` - The options and how ballots are tallied
+    /// * `voting_config` - Configuration for the voting process
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The proposal ID or an error
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_choice_proposal(
+        env: Env,
+        proposer: Address,
+        title: Symbol,
+        description: Symbol,
+        metadata_hash: String,
+        proposal_type: ProposalType,
+        choice_config: ChoiceConfig,
+        voting_config: VotingConfig,
+    ) -> Result<u32, Error> {
+        ChoiceVoting::validate_config(&choice_config)?;
+
+        // Conviction is measured against a single yes/no outcome
+        if voting_config.weighting == WeightingMode::Conviction {
+            return Err(Error::InvalidVotingMode);
+        }
+
+        let proposal_id = ProposalManager::store_new_proposal(
+            &env,
+            &proposer,
+            title,
+            description,
+            metadata_hash,
+            proposal_type,
+            Vec::new(&env),
+            voting_config,
+        )?;
+        ChoiceVoting::init(&env, proposal_id, &choice_config)?;
+
+        Ok(proposal_id)
+    }
+
+    /// Co-sponsor a draft proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `sponsor` - The co-sponsoring address
+    /// * `proposal_id` - The ID of the draft
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The number of co-sponsors so far or an error
+    pub fn sponsor_proposal(env: Env, sponsor: Address, proposal_id: u32) -> Result<u32, Error> {
+        DraftManager::sponsor(&env, &sponsor, proposal_id)
+    }
+
+    /// Publish an amended version of a draft proposal
+    /// Existing sponsorships are cleared and the discussion period restarts
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposer` - The proposer of the draft
+    /// * `proposal_id` - The ID of the draft
+    /// * `description` - The amended description
+    /// * `metadata_hash` - The amended metadata hash
+    /// * `actions` - The amended actions, empty for multi-choice proposals
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The new revision number or an error
+    pub fn amend_proposal(
+        env: Env,
+        proposer: Address,
+        proposal_id: u32,
+        description: Symbol,
+        metadata_hash: String,
+        actions: Vec<Action>,
+    ) -> Result<u32, Error> {
+        DraftManager::amend(
+            &env,
+            &proposer,
+            proposal_id,
+            description,
+            metadata_hash,
+            actions,
+        )
+    }
+
+    /// Get the co-sponsors of the current revision of a draft
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<Sponsorship>` - The co-sponsors
+    pub fn get_sponsors(env: Env, proposal_id: u32) -> Vec<Sponsorship> {
+        DraftManager::get_sponsors(&env, proposal_id)
+    }
+
+    /// Get the published revisions of a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<ProposalRevision>` - The revisions, oldest first
+    pub fn get_proposal_revisions(env: Env, proposal_id: u32) -> Vec<ProposalRevision> {
+        DraftManager::get_revisions(&env, proposal_id)
+    }
+
     /// Activate a proposal to begin the voting period
     ///
     /// # Arguments
@@ -138,17 +265,26 @@ impl GovernanceContract {
         // Get proposal and check caller permissions
         let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
 
-        // Only the proposer, an admin, or a moderator can cancel a proposal
-        if proposal.proposer != caller
-            && !ProposalManager::is_admin(&env, &caller)
-            && !ProposalManager::is_moderator(&env, &caller)
-        {
-            return Err(Error::Unauthorized);
-        }
-
-        // Ensure proposal is in a cancellable state (Draft or Active)
-        if proposal.status != ProposalStatus::Draft && proposal.status != ProposalStatus::Active {
-            return Err(Error::InvalidProposalStatus);
+        match proposal.status {
+            // Only the proposer, an admin, or a moderator can cancel a proposal before it passes
+            ProposalStatus::Draft | ProposalStatus::Active => {
+                if proposal.proposer != caller
+                    && !ProposalManager::is_admin(&env, &caller)
+                    && !ProposalManager::is_moderator(&env, &caller)
+                {
+                    return Err(Error::Unauthorized);
+                }
+            }
+            // Only the guardian or a moderator can intercept a queued proposal
+            ProposalStatus::Queued => {
+                let guardian: Option<Address> = env.storage().instance().get(&GUARDIAN_KEY);
+                if guardian != Some(caller.clone())
+                    && !ProposalManager::is_moderator(&env, &caller)
+                {
+                    return Err(Error::Unauthorized);
+                }
+            }
+            _ => return Err(Error::InvalidProposalStatus),
         }
 
         // Cancel the proposal
@@ -223,6 +359,7 @@ impl GovernanceContract {
     }
 
     /// Cast a vote on a proposal
+    /// Voting again with the other position changes the vote while the proposal is active
     ///
     /// # Arguments
     /// * `env` - The environment object
@@ -238,142 +375,942 @@ impl GovernanceContract {
         proposal_id: u32,
         support: bool,
     ) -> Result<(), Error> {
-        // Get proposal and verify it's active
-        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
-        if proposal.status != ProposalStatus::Active {
-            return Err(Error::ProposalNotActive);
-        }
+        // Require authentication from the voter
+        voter.require_auth();
 
-        // Calculate voting weight based on configuration
-        let weight = if proposal.voting_config.one_address_one_vote {
-            // One address, one vote mode
-            1
+        let support = if support {
+            VoteSupport::For
         } else {
-            // Weighted voting based on token holdings and delegations
-            WeightCalculator::get_weight(&env, &voter, proposal_id)?
+            VoteSupport::Against
         };
+        Self::record_vote(&env, &voter, proposal_id, support)
+    }
 
-        // Ensure voter has voting power
-        if weight <= 0 {
-            return Err(Error::NoVotingPower);
+    /// Cast an abstaining vote on a proposal
+    /// Abstentions count toward quorum but not toward the approval threshold
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `proposal_id` - The ID of the proposal being voted on
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn cast_abstain_vote(env: Env, voter: Address, proposal_id: u32) -> Result<(), Error> {
+        voter.require_auth();
+        Self::record_vote(&env, &voter, proposal_id, VoteSupport::Abstain)
+    }
+
+    /// Register the ed25519 public key a voter signs off-chain votes with
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `public_key` - The ed25519 public key
+    pub fn register_signing_key(env: Env, voter: Address, public_key: BytesN<32>) {
+        SignatureVoting::register_key(&env, &voter, &public_key)
+    }
+
+    /// Get the ed25519 public key registered by a voter
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Option<BytesN<32>>` - The public key if one is registered
+    pub fn get_signing_key(env: Env, voter: Address) -> Option<BytesN<32>> {
+        SignatureVoting::get_key(&env, &voter)
+    }
+
+    /// Get the nonce a voter's next signed vote must use
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `u64` - The next nonce
+    pub fn get_signing_nonce(env: Env, voter: Address) -> u64 {
+        SignatureVoting::get_nonce(&env, &voter)
+    }
+
+    /// Get the message a voter signs to vote on a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `support` - The position taken by the voter
+    /// * `nonce` - The voter's next nonce
+    /// * `deadline` - The last timestamp at which the vote may be submitted
+    ///
+    /// # Returns
+    /// * `Bytes` - The message to sign
+    pub fn get_vote_message(
+        env: Env,
+        proposal_id: u32,
+        support: VoteSupport,
+        nonce: u64,
+        deadline: u64,
+    ) -> Bytes {
+        SignatureVoting::vote_message(&env, proposal_id, &support, nonce, deadline)
+    }
+
+    /// Record a batch of votes signed off-chain, submitted by any relayer
+    /// Signed votes follow the same weight, snapshot and eligibility rules as direct votes.
+    /// A rejected vote is reported without stopping the others and leaves the nonce
+    /// unused, but an invalid signature aborts the whole batch
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `votes` - The signed votes, at most `MAX_SIGNED_VOTES`
+    ///
+    /// # Returns
+    /// * `Result<Vec<SignedVoteResult>, Error>` - The outcome of each vote or an error
+    pub fn cast_votes_by_sig(
+        env: Env,
+        votes: Vec<SignedVote>,
+    ) -> Result<Vec<SignedVoteResult>, Error> {
+        if votes.is_empty() || votes.len() > MAX_SIGNED_VOTES {
+            return Err(Error::InvalidVoteBatch);
         }
 
-        // Check if voting has ended and finalize if needed
-        if VotingSystem::check_voting_ended(&env, proposal_id, &proposal.voting_config)? {
-            let passed = VotingSystem::tally_votes(&env, proposal_id, &proposal.voting_config)?;
-            if passed {
-                ProposalManager::mark_passed(&env, proposal_id)?;
-            } else {
-                ProposalManager::mark_rejected(&env, proposal_id)?;
-            }
+        let mut results = Vec::new(&env);
+        for vote in votes.iter() {
+            // The nonce is only consumed once the vote has been recorded
+            let outcome = SignatureVoting::verify(&env, &vote)
+                .and_then(|_| {
+                    Self::record_vote(&env, &vote.voter, vote.proposal_id, vote.support.clone())
+                })
+                .map(|_| SignatureVoting::consume_nonce(&env, &vote.voter));
+            let error = outcome.err().map(|e| e as u32);
+            results.push_back(SignedVoteResult {
+                voter: vote.voter,
+                success: error.is_none(),
+                error,
+            });
         }
+        Ok(results)
+    }
 
-        // Cast the vote
-        VotingSystem::cast_vote(&env, proposal_id, &voter, support, weight)?;
-        Ok(())
+    /// Get the vote an address has cast on a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Option<Vote>` - The current vote, if any
+    pub fn get_vote(env: Env, voter: Address, proposal_id: u32) -> Option<Vote> {
+        VotingSystem::get_vote(&env, proposal_id, &voter)
     }
 
-    /// Take a voting power snapshot for a proposal
+    /// Get the for, against and abstain totals of a proposal
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `proposal_id` - The ID of the proposal
     ///
     /// # Returns
-    /// * `Result<(), Error>` - Success or an error
-    pub fn take_snapshot(env: Env, proposal_id: u32) -> Result<(), Error> {
-        WeightCalculator::take_snapshot(&env, proposal_id)
+    /// * `(u128, u128, u128)` - The for, against and abstain vote totals
+    pub fn get_vote_totals(env: Env, proposal_id: u32) -> (u128, u128, u128) {
+        (
+            VotingSystem::get_for_votes(&env, proposal_id),
+            VotingSystem::get_against_votes(&env, proposal_id),
+            VotingSystem::get_abstain_votes(&env, proposal_id),
+        )
     }
 
-    /// Delegate voting power to another address
+    /// Cast a ballot on a multi-choice proposal
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `delegator` - The address delegating their voting power
-    /// * `delegatee` - The address receiving the delegated voting power
+    /// * `voter` - The address of the voter
+    /// * `proposal_id` - The ID of the proposal being voted on
+    /// * `choices` - The selected option indexes: one for plurality, any for approval,
+    ///   or a preference order for ranked choice
     ///
     /// # Returns
     /// * `Result<(), Error>` - Success or an error
-    pub fn delegate_vote(env: Env, delegator: Address, delegatee: Address) -> Result<(), Error> {
-        WeightCalculator::delegate(&env, &delegator, &delegatee)
+    pub fn cast_choice_vote(
+        env: Env,
+        voter: Address,
+        proposal_id: u32,
+        choices: Vec<u32>,
+    ) -> Result<(), Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+
+        // Calculate voting weight based on configuration
+        let weight = WeightCalculator::get_vote_weight(&env, &voter, &proposal)?;
+        if weight <= 0 {
+            return Err(Error::NoVotingPower);
+        }
+
+        ChoiceVoting::cast_vote(&env, proposal_id, &voter, choices, weight)?;
+
+        // Voting directly overrides the voter's delegations for this proposal
+        VotingSystem::refresh_delegate_votes(&env, &proposal, &voter)
     }
 
-    /// Get the voting weight of an address for a specific proposal
+    /// Get the options of a multi-choice proposal
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `voter` - The address to check
     /// * `proposal_id` - The ID of the proposal
     ///
     /// # Returns
-    /// * `Result<i128, Error>` - The voting weight or an error
-    pub fn get_vote_weight(env: Env, voter: Address, proposal_id: u32) -> Result<i128, Error> {
-        WeightCalculator::get_weight(&env, &voter, proposal_id)
+    /// * `Option<ChoiceConfig>` - The configuration, or None for yes/no proposals
+    pub fn get_choice_config(env: Env, proposal_id: u32) -> Option<ChoiceConfig> {
+        ChoiceVoting::get_config(&env, proposal_id)
     }
 
-    /// Execute a passed proposal's actions
+    /// Get the current tally of a multi-choice proposal
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `executor` - The address executing the proposal
-    /// * `proposal_id` - The ID of the proposal to execute
+    /// * `proposal_id` - The ID of the proposal
     ///
     /// # Returns
-    /// * `Result<(), Error>` - Success or an error
-    pub fn execute_proposal(env: Env, executor: Address, proposal_id: u32) -> Result<(), Error> {
-        // Check if executor is authorized (admin or moderator)
-        if !ProposalManager::is_admin(&env, &executor)
-            && !ProposalManager::is_moderator(&env, &executor)
-        {
-            return Err(Error::Unauthorized);
+    /// * `Result<ChoiceResult, Error>` - The tally result or an error
+    pub fn get_choice_result(env: Env, proposal_id: u32) -> Result<ChoiceResult, Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        let choice_config =
+            ChoiceVoting::get_config(&env, proposal_id).ok_or(Error::WrongVoteType)?;
+        ChoiceVoting::tally(&env, proposal_id, &choice_config, &proposal.voting_config)
+    }
+
+    /// Close voting on an active proposal whose voting period has ended
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Result<bool, Error>` - True if the proposal passed, or an error
+    pub fn finalize_proposal(env: Env, proposal_id: u32) -> Result<bool, Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+        if !VotingSystem::check_voting_ended(&env, proposal_id, &proposal.voting_config)? {
+            return Err(Error::InvalidVotingPeriod);
         }
 
-        // Get the proposal
+        let passed = VotingSystem::tally_votes(&env, proposal_id, &proposal.voting_config)?;
+        if passed {
+            ProposalManager::mark_passed(&env, proposal_id)?;
+        } else {
+            ProposalManager::mark_rejected(&env, proposal_id)?;
+        }
+        Ok(passed)
+    }
+
+    /// Take a voting power snapshot for a proposal
+    /// Only allowed while the proposal is a draft; activation takes the final snapshot
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn take_snapshot(env: Env, proposal_id: u32) -> Result<(), Error> {
         let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        if proposal.status != ProposalStatus::Draft {
+            return Err(Error::InvalidProposalStatus);
+        }
+        WeightCalculator::take_snapshot(&env, proposal_id)
+    }
 
-        // Execute all actions in the proposal
-        ExecutionEngine::execute(&env, &executor, &proposal)?;
+    /// Deposit governance tokens to gain checkpointed voting power
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address depositing tokens
+    /// * `amount` - The amount of tokens to deposit
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn deposit_voting_power(env: Env, holder: Address, amount: i128) -> Result<(), Error> {
+        VotingPowerLedger::deposit(&env, &holder, amount)
+    }
 
-        // Mark the proposal as executed
-        ProposalManager::mark_executed(&env, proposal_id)
+    /// Withdraw deposited governance tokens
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `holder` - The address withdrawing tokens
+    /// * `amount` - The amount of tokens to withdraw
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn withdraw_voting_power(env: Env, holder: Address, amount: i128) -> Result<(), Error> {
+        VotingPowerLedger::withdraw(&env, &holder, amount)
     }
 
-    /// Get a proposal by ID
+    /// Get the current deposited voting power of an address
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `proposal_id` - The ID of the proposal to retrieve
+    /// * `holder` - The address to check
     ///
     /// # Returns
-    /// * `Result<Proposal, Error>` - The proposal or an error if not found
-    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, Error> {
-        ProposalManager::get_proposal(&env, proposal_id)
+    /// * `i128` - The deposited voting power
+    pub fn get_voting_power(env: Env, holder: Address) -> i128 {
+        VotingPowerLedger::get_balance(&env, &holder)
     }
 
-    /// Get all active proposals
+    /// Get the deposited voting power of an address as of a ledger sequence
     ///
     /// # Arguments
     /// * `env` - The environment object
+    /// * `holder` - The address to check
+    /// * `ledger` - The ledger sequence to look up
     ///
     /// # Returns
-    /// * `Vec<u32>` - A list of active proposal IDs
-    pub fn get_active_proposals(env: Env) -> Vec<u32> {
-        ProposalManager::get_proposals_by_status(&env, ProposalStatus::Active)
+    /// * `i128` - The deposited voting power at that ledger
+    pub fn get_voting_power_at(env: Env, holder: Address, ledger: u32) -> i128 {
+        VotingPowerLedger::get_balance_at(&env, &holder, ledger)
     }
 
-    /// Get all proposals that are passed and ready for execution
+    /// Get the total deposited voting power as of a ledger sequence
     ///
     /// # Arguments
     /// * `env` - The environment object
+    /// * `ledger` - The ledger sequence to look up
     ///
     /// # Returns
-    /// * `Vec<u32>` - A list of executable proposal IDs
-    pub fn get_executable_proposals(env: Env) -> Vec<u32> {
-        ProposalManager::get_proposals_by_status(&env, ProposalStatus::Passed)
+    /// * `i128` - The total deposited voting power at that ledger
+    pub fn get_total_voting_power_at(env: Env, ledger: u32) -> i128 {
+        VotingPowerLedger::get_total_at(&env, ledger)
     }
 
-    /// Get total number of voters
-    pub fn get_proposal_voters_count(env: Env, proposal_id: u32) -> u128 {
-        VotingSystem::get_voter_count(&env, proposal_id)
+    /// Delegate voting power to another address
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address delegating their voting power
+    /// * `delegatee` - The address receiving the delegated voting power
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn delegate_vote(env: Env, delegator: Address, delegatee: Address) -> Result<(), Error> {
+        WeightCalculator::delegate(&env, &delegator, &delegatee)
+    }
+
+    /// Delegate a share of voting power, optionally for one proposal type only
+    /// Type-specific delegations take precedence over delegations for all types
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address delegating their voting power
+    /// * `delegatee` - The address receiving the delegated voting power
+    /// * `share` - The share of voting power in basis points (10000 = 100%)
+    /// * `scope` - All proposal types, or a single proposal type
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn delegate_vote_share(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        share: u32,
+        scope: DelegationScope,
+    ) -> Result<(), Error> {
+        WeightCalculator::delegate_share(&env, &delegator, &delegatee, share, scope)
+    }
+
+    /// Revoke a delegation
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address that delegated
+    /// * `delegatee` - The address that received the delegation
+    /// * `scope` - The scope the delegation was made for
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn revoke_delegation(
+        env: Env,
+        delegator: Address,
+        delegatee: Address,
+        scope: DelegationScope,
+    ) -> Result<(), Error> {
+        WeightCalculator::revoke(&env, &delegator, &delegatee, scope)
+    }
+
+    /// Get the current delegations of an address
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address to check
+    ///
+    /// # Returns
+    /// * `Vec<Delegation>` - The delegations currently in effect
+    pub fn get_delegations(env: Env, delegator: Address) -> Vec<Delegation> {
+        WeightCalculator::get_delegations(&env, &delegator)
+    }
+
+    /// Get the voting weight of an address for a specific proposal
+    /// Applies the proposal's weighting mode, so quadratic proposals report votes, not tokens
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address to check
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Result<i128, Error>` - The voting weight or an error
+    pub fn get_vote_weight(env: Env, voter: Address, proposal_id: u32) -> Result<i128, Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        WeightCalculator::get_vote_weight(&env, &voter, &proposal)
+    }
+
+    /// Get the conviction accrued for and against a conviction-weighted proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Result<(u128, u128), Error>` - The for and against conviction, or an error
+    pub fn get_conviction(env: Env, proposal_id: u32) -> Result<(u128, u128), Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        if proposal.voting_config.weighting != WeightingMode::Conviction {
+            return Err(Error::InvalidVotingMode);
+        }
+        Ok((
+            VotingSystem::get_conviction(&env, &proposal, &VoteSupport::For),
+            VotingSystem::get_conviction(&env, &proposal, &VoteSupport::Against),
+        ))
+    }
+
+    /// Execute a passed proposal's actions
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `executor` - The address executing the proposal
+    /// * `proposal_id` - The ID of the proposal to execute
+    ///
+    /// # Returns
+    /// * `Result<Vec<ActionResult>, Error>` - The outcome of each action or an error
+    pub fn execute_proposal(
+        env: Env,
+        executor: Address,
+        proposal_id: u32,
+    ) -> Result<Vec<ActionResult>, Error> {
+        // Check if executor is authorized (admin or moderator)
+        if !ProposalManager::is_admin(&env, &executor)
+            && !ProposalManager::is_moderator(&env, &executor)
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        // Get the proposal
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+
+        // Execute all actions in the proposal
+        let results = ExecutionEngine::execute(&env, &executor, &proposal)?;
+
+        // Mark the proposal as executed
+        ProposalManager::mark_executed(&env, proposal_id)?;
+        Ok(results)
+    }
+
+    /// Get the per-action results recorded when a proposal was executed
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<ActionResult>` - The action results, empty if not executed
+    pub fn get_execution_results(env: Env, proposal_id: u32) -> Vec<ActionResult> {
+        ExecutionEngine::get_results(&env, proposal_id)
+    }
+
+    /// Allow governance to call a function on a contract through `Action::Invoke`
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `contract` - The target contract address
+    /// * `function` - The function name to allow
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn allow_call(
+        env: Env,
+        caller: Address,
+        contract: Address,
+        function: Symbol,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        ExecutionEngine::allow_call(&env, &contract, &function);
+        Ok(())
+    }
+
+    /// Remove a function from the `Action::Invoke` allowlist
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `contract` - The target contract address
+    /// * `function` - The function name to disallow
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn disallow_call(
+        env: Env,
+        caller: Address,
+        contract: Address,
+        function: Symbol,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        ExecutionEngine::disallow_call(&env, &contract, &function)
+    }
+
+    /// Get all (contract, function) pairs governance is allowed to call
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<AllowedCall>` - The allowlist
+    pub fn get_allowed_calls(env: Env) -> Vec<AllowedCall> {
+        ExecutionEngine::get_allowed_calls(&env)
+    }
+
+    /// Queue a passed proposal in the timelock
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal to queue
+    ///
+    /// # Returns
+    /// * `Result<u64, Error>` - The timestamp from which the proposal can be executed
+    pub fn queue_proposal(env: Env, proposal_id: u32) -> Result<u64, Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        ExecutionEngine::queue(&env, &proposal)
+    }
+
+    /// Expire a queued proposal that was not executed within its grace period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal to expire
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn expire_proposal(env: Env, proposal_id: u32) -> Result<(), Error> {
+        let proposal = ProposalManager::get_proposal(&env, proposal_id)?;
+        ExecutionEngine::expire(&env, &proposal)
+    }
+
+    /// Get the ETA of a queued proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The ETA timestamp if the proposal was queued
+    pub fn get_proposal_eta(env: Env, proposal_id: u32) -> Option<u64> {
+        ExecutionEngine::get_eta(&env, proposal_id)
+    }
+
+    /// Set the guardian allowed to cancel queued proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `guardian` - The new guardian address
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_guardian(env: Env, caller: Address, guardian: Address) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&GUARDIAN_KEY, &guardian);
+        env.events().publish(
+            (symbol_short!("govern"), symbol_short!("guardian")),
+            guardian,
+        );
+        Ok(())
+    }
+
+    /// Get the guardian allowed to cancel queued proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The guardian if one is set
+    pub fn get_guardian(env: Env) -> Option<Address> {
+        env.storage().instance().get(&GUARDIAN_KEY)
+    }
+
+    /// Set how long queued proposals stay executable after their ETA
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `grace_period` - The grace period in seconds
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_grace_period(env: Env, caller: Address, grace_period: u64) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&GRACE_PERIOD_KEY, &grace_period);
+        Ok(())
+    }
+
+    /// Get how long queued proposals stay executable after their ETA
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `u64` - The grace period in seconds
+    pub fn get_grace_period(env: Env) -> u64 {
+        ExecutionEngine::get_grace_period(&env)
+    }
+
+    /// Set the fast-track settings for emergency proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `config` - The emergency voting period, approval threshold and pause limit
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_emergency_config(
+        env: Env,
+        caller: Address,
+        config: EmergencyConfig,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        EmergencyManager::set_config(&env, &config)
+    }
+
+    /// Get the fast-track settings for emergency proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `EmergencyConfig` - The emergency settings
+    pub fn get_emergency_config(env: Env) -> EmergencyConfig {
+        EmergencyManager::get_config(&env)
+    }
+
+    /// Set the sponsorship and discussion requirements for draft proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `config` - The co-sponsor count, co-sponsor weight and discussion period
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_sponsorship_config(
+        env: Env,
+        caller: Address,
+        config: SponsorshipConfig,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        DraftManager::set_config(&env, &config)
+    }
+
+    /// Get the sponsorship and discussion requirements for draft proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `SponsorshipConfig` - The sponsorship requirements
+    pub fn get_sponsorship_config(env: Env) -> SponsorshipConfig {
+        DraftManager::get_config(&env)
+    }
+
+    /// Approve an active emergency proposal as a moderator
+    /// The proposal is executed immediately once enough moderators approve it
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `moderator` - The approving moderator
+    /// * `proposal_id` - The ID of the emergency proposal
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The number of approvals so far or an error
+    pub fn approve_emergency(env: Env, moderator: Address, proposal_id: u32) -> Result<u32, Error> {
+        EmergencyManager::approve(&env, &moderator, proposal_id)
+    }
+
+    /// Get the moderators that approved an emergency proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The approving moderators
+    pub fn get_emergency_approvals(env: Env, proposal_id: u32) -> Vec<Address> {
+        EmergencyManager::get_approvals(&env, proposal_id)
+    }
+
+    /// Deposit tokens into the governance treasury
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `from` - The address funding the treasury
+    /// * `token` - The token being deposited
+    /// * `amount` - The amount to deposit
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn fund_treasury(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Treasury::deposit(&env, &from, &token, amount)
+    }
+
+    /// Claim the vested part of a streamed grant for its recipient
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant_id` - The ID of the grant
+    ///
+    /// # Returns
+    /// * `Result<i128, Error>` - The amount paid or an error
+    pub fn claim_grant(env: Env, grant_id: u32) -> Result<i128, Error> {
+        Treasury::claim_grant(&env, grant_id)
+    }
+
+    /// Get a streamed grant by ID
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant_id` - The ID of the grant
+    ///
+    /// # Returns
+    /// * `Result<Grant, Error>` - The grant or an error if not found
+    pub fn get_grant(env: Env, grant_id: u32) -> Result<Grant, Error> {
+        Treasury::get_grant(&env, grant_id)
+    }
+
+    /// Set how much of a token the treasury may commit per period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `caller` - The admin address
+    /// * `token` - The token to limit
+    /// * `limit` - The maximum amount committed per period
+    /// * `period` - The length of a period in seconds
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_spending_limit(
+        env: Env,
+        caller: Address,
+        token: Address,
+        limit: i128,
+        period: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        if !ProposalManager::is_admin(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        Treasury::set_spending_limit(&env, &token, limit, period)
+    }
+
+    /// Get the spending limit of a treasury token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `Option<SpendingLimit>` - The limit, or None if spending is unlimited
+    pub fn get_spending_limit(env: Env, token: Address) -> Option<SpendingLimit> {
+        Treasury::get_spending_limit(&env, &token)
+    }
+
+    /// Get the amount of a treasury token committed in the current period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `i128` - The amount spent in the current period
+    pub fn get_period_spent(env: Env, token: Address) -> i128 {
+        Treasury::get_period_spent(&env, &token)
+    }
+
+    /// Get the treasury balance of a token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `TreasuryBalance` - The available and reserved amounts
+    pub fn get_treasury_balance(env: Env, token: Address) -> TreasuryBalance {
+        Treasury::get_balance(&env, &token)
+    }
+
+    /// Get the treasury balances of every token it holds
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<TreasuryBalance>` - The balance of each token
+    pub fn get_treasury_balances(env: Env) -> Vec<TreasuryBalance> {
+        Treasury::get_balances(&env)
+    }
+
+    /// Get the history of treasury payouts
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<Outflow>` - The outflows, oldest first
+    pub fn get_treasury_outflows(env: Env) -> Vec<Outflow> {
+        Treasury::get_outflows(&env)
+    }
+
+    /// Get a proposal by ID
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal to retrieve
+    ///
+    /// # Returns
+    /// * `Result<Proposal, Error>` - The proposal or an error if not found
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, Error> {
+        ProposalManager::get_proposal(&env, proposal_id)
+    }
+
+    /// Get all active proposals
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<u32>` - A list of active proposal IDs
+    pub fn get_active_proposals(env: Env) -> Vec<u32> {
+        ProposalManager::get_proposals_by_status(&env, ProposalStatus::Active)
+    }
+
+    /// Get all queued proposals that are inside their execution window
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<u32>` - A list of executable proposal IDs
+    pub fn get_executable_proposals(env: Env) -> Vec<u32> {
+        let queued = ProposalManager::get_proposals_by_status(&env, ProposalStatus::Queued);
+        let mut executable = Vec::new(&env);
+        for id in queued.iter() {
+            let proposal = ProposalManager::get_proposal(&env, id).unwrap();
+            if ExecutionEngine::check_timelock(&env, &proposal).is_ok() {
+                executable.push_back(id);
+            }
+        }
+        executable
+    }
+
+    /// Get total number of voters
+    pub fn get_proposal_voters_count(env: Env, proposal_id: u32) -> u128 {
+        VotingSystem::get_voter_count(&env, proposal_id)
+    }
+}
+
+impl GovernanceContract {
+    /// Validate and record a for, against or abstain vote
+    /// A vote arriving after the voting period finalizes the proposal and is not counted
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `proposal_id` - The ID of the proposal being voted on
+    /// * `support` - The position taken by the voter
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    fn record_vote(
+        env: &Env,
+        voter: &Address,
+        proposal_id: u32,
+        support: VoteSupport,
+    ) -> Result<(), Error> {
+        // Get proposal and verify it's active
+        let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        if proposal.status != ProposalStatus::Active {
+            return Err(Error::ProposalNotActive);
+        }
+
+        // Multi-choice proposals take ballots through cast_choice_vote
+        if ChoiceVoting::get_config(env, proposal_id).is_some() {
+            return Err(Error::WrongVoteType);
+        }
+
+        // Calculate voting weight based on the proposal's weighting mode
+        let weight = WeightCalculator::get_vote_weight(env, voter, &proposal)?;
+
+        // Ensure voter has voting power
+        if weight <= 0 {
+            return Err(Error::NoVotingPower);
+        }
+
+        // Check if voting has ended and finalize if needed
+        if VotingSystem::check_voting_ended(env, proposal_id, &proposal.voting_config)? {
+            let passed = VotingSystem::tally_votes(env, proposal_id, &proposal.voting_config)?;
+            if passed {
+                ProposalManager::mark_passed(env, proposal_id)?;
+            } else {
+                ProposalManager::mark_rejected(env, proposal_id)?;
+            }
+
+            // A vote cast after the voting period is not counted
+            let ends_at = proposal.activated_at + proposal.voting_config.duration;
+            if env.ledger().timestamp() >= ends_at {
+                return Ok(());
+            }
+        }
+
+        // Cast the vote
+        VotingSystem::cast_vote(env, proposal_id, voter, support, weight)?;
+
+        // Voting directly overrides the voter's delegations for this proposal
+        VotingSystem::refresh_delegate_votes(env, &proposal, voter)
     }
 }
@@ -1,8 +1,14 @@
 #![no_std]
 
+pub mod checkpoints;
+pub mod choices;
+pub mod drafts;
+pub mod emergency;
 pub mod execution;
 pub mod governance;
 pub mod proposals;
+pub mod signatures;
+pub mod treasury;
 pub mod types;
 pub mod utils;
 pub mod voting;
@@ -1,8 +1,11 @@
 use crate::{
+    drafts::DraftManager,
+    emergency::EmergencyManager,
+    execution::ExecutionEngine,
     types::{
         Action, Error, Proposal, ProposalRequirements, ProposalStatus, ProposalType, UserLevel,
-        VotingConfig, ADMIN_KEY, DEFAULT_CONFIG_KEY, MODERATOR_KEY, PROPOSAL_COUNTER_KEY,
-        REFERRAL_KEY, REQUIREMENTS_KEY, TOKEN_KEY,
+        VotingConfig, WeightingMode, ADMIN_KEY, DEFAULT_CONFIG_KEY, MODERATOR_KEY,
+        PROPOSAL_COUNTER_KEY, REFERRAL_KEY, REQUIREMENTS_KEY, TOKEN_KEY,
     },
     utils::get_key_str,
 };
@@ -156,13 +159,86 @@ impl ProposalManager {
         actions: Vec<Action>,
         voting_config: VotingConfig,
     ) -> Result<u32, Error> {
-        // Require authentication from the proposer
-        proposer.require_auth();
+        Self::validate_actions(env, &proposal_type, &actions)?;
+
+        Self::store_new_proposal(
+            env,
+            proposer,
+            title,
+            description,
+            metadata_hash,
+            proposal_type,
+            actions,
+            voting_config,
+        )
+    }
 
+    /// Validate the actions of an action proposal
+    /// Used when a proposal is created and whenever its draft is amended
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_type` - The type of proposal
+    /// * `actions` - The actions to be executed if proposal passes
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if an action is not allowed
+    pub fn validate_actions(
+        env: &Env,
+        proposal_type: &ProposalType,
+        actions: &Vec<Action>,
+    ) -> Result<(), Error> {
         if actions.is_empty() || actions.len() > 5 {
             return Err(Error::InvalidAction);
         }
 
+        // Generic contract calls must target allowlisted functions
+        ExecutionEngine::validate_actions(env, actions)?;
+
+        // Only contract upgrade proposals may upgrade contracts
+        let upgrades = actions
+            .iter()
+            .any(|action| matches!(action, Action::UpgradeContract(..)));
+        if upgrades && *proposal_type != ProposalType::ContractUpgrade {
+            return Err(Error::InvalidAction);
+        }
+
+        // Emergency proposals may only pause or unpause
+        if *proposal_type == ProposalType::EmergencyAction {
+            EmergencyManager::validate_actions(actions)?;
+        }
+        Ok(())
+    }
+
+    /// Validate a proposer and store a new draft proposal
+    /// Shared by action proposals and multi-choice proposals, which carry no actions
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposer` - The address creating the proposal
+    /// * `title` - The title of the proposal
+    /// * `description` - The description of the proposal
+    /// * `metadata_hash` - A hash pointing to additional metadata
+    /// * `proposal_type` - The type of proposal
+    /// * `actions` - The actions to be executed if proposal passes
+    /// * `voting_config` - Configuration for the voting process
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The proposal ID or an error
+    #[allow(clippy::too_many_arguments)]
+    pub fn store_new_proposal(
+        env: &Env,
+        proposer: &Address,
+        title: Symbol,
+        description: Symbol,
+        metadata_hash: String,
+        proposal_type: ProposalType,
+        actions: Vec<Action>,
+        voting_config: VotingConfig,
+    ) -> Result<u32, Error> {
+        // Require authentication from the proposer
+        proposer.require_auth();
+
         // Check if proposer is eligible to create a proposal
         Self::check_proposer_eligibility(env, proposer, &proposal_type)?;
 
@@ -190,13 +266,25 @@ impl ProposalManager {
             .get(&DEFAULT_CONFIG_KEY)
             .ok_or(Error::NotInitialized)?;
 
-        // Validate voting duration
-        if voting_config.duration < default_config.duration / 2
+        // Emergency proposals may only pause or unpause and take the fast track:
+        // the short emergency voting period and no timelock
+        let mut voting_config = voting_config;
+        if proposal_type == ProposalType::EmergencyAction {
+            EmergencyManager::validate_actions(&actions)?;
+            voting_config.duration = EmergencyManager::get_config(env).voting_duration;
+            voting_config.execution_delay = 0;
+        } else if voting_config.duration < default_config.duration / 2
             || voting_config.duration > default_config.duration * 2
         {
+            // Validate voting duration
             return Err(Error::InvalidVotingPeriod);
         }
 
+        // Headcount voting has no token weight to apply a weighting mode to
+        if voting_config.one_address_one_vote && voting_config.weighting != WeightingMode::Linear {
+            return Err(Error::InvalidVotingMode);
+        }
+
         // Additional validations for weighted voting
         if !voting_config.one_address_one_vote {
             // Validate quorum
@@ -238,13 +326,15 @@ impl ProposalManager {
         };
 
         // Store the proposal and update the counter
-        let key = Self::get_proposal_key(env, proposal_counter);
-        env.storage().instance().set(&key, &proposal);
+        Self::save_proposal(env, &proposal);
         env.storage()
             .instance()
             .set(&PROPOSAL_COUNTER_KEY, &proposal_counter);
         Self::add_to_status_list(env, proposal_counter, ProposalStatus::Draft);
 
+        // The original text is the first revision of the draft
+        DraftManager::record_revision(env, &proposal);
+
         // Emit an event for the proposal creation
         env.events().publish(
             (symbol_short!("proposal"), symbol_short!("created")),
@@ -280,6 +370,9 @@ impl ProposalManager {
             return Err(Error::InvalidProposalStatus);
         }
 
+        // The draft must be sponsored and past its discussion period
+        DraftManager::check_ready(env, &proposal)?;
+
         // Verify there are moderators in the system
         let moderators: Vec<Address> = env
             .storage()
@@ -335,7 +428,10 @@ impl ProposalManager {
         let old_status = proposal.status.clone();
 
         // Check that the proposal is in a cancellable state
-        if old_status != ProposalStatus::Draft && old_status != ProposalStatus::Active {
+        if old_status != ProposalStatus::Draft
+            && old_status != ProposalStatus::Active
+            && old_status != ProposalStatus::Queued
+        {
             return Err(Error::InvalidProposalStatus);
         }
 
@@ -544,8 +640,11 @@ impl ProposalManager {
             .get(&key)
             .ok_or(Error::ProposalNotFound)?;
 
-        // Check that the proposal is in Passed status
-        if proposal.status != ProposalStatus::Passed {
+        // Save the old status for list management
+        let old_status = proposal.status.clone();
+
+        // Check that the proposal is Passed or Queued in the timelock
+        if old_status != ProposalStatus::Passed && old_status != ProposalStatus::Queued {
             return Err(Error::InvalidProposalStatus);
         }
 
@@ -568,7 +667,7 @@ impl ProposalManager {
         env.storage().instance().set(&key, &proposal);
 
         // Update status lists
-        Self::remove_from_status_list(env, proposal_id, ProposalStatus::Passed);
+        Self::remove_from_status_list(env, proposal_id, old_status);
         Self::add_to_status_list(env, proposal_id, ProposalStatus::Executed);
 
         // Emit an event for the executed proposal
@@ -580,6 +679,92 @@ impl ProposalManager {
         Ok(())
     }
 
+    /// Mark a passed proposal as queued in the timelock
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal to queue
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn mark_queued(env: &Env, proposal_id: u32) -> Result<(), Error> {
+        // Retrieve the proposal
+        let key = Self::get_proposal_key(env, proposal_id);
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(Error::ProposalNotFound)?;
+
+        // Check that the proposal is in Passed status
+        if proposal.status != ProposalStatus::Passed {
+            return Err(Error::InvalidProposalStatus);
+        }
+
+        // Update proposal status to Queued
+        proposal.status = ProposalStatus::Queued;
+        env.storage().instance().set(&key, &proposal);
+
+        // Update status lists
+        Self::remove_from_status_list(env, proposal_id, ProposalStatus::Passed);
+        Self::add_to_status_list(env, proposal_id, ProposalStatus::Queued);
+
+        Ok(())
+    }
+
+    /// Mark a queued proposal as expired once its execution window has passed
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal to expire
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn mark_expired(env: &Env, proposal_id: u32) -> Result<(), Error> {
+        // Retrieve the proposal
+        let key = Self::get_proposal_key(env, proposal_id);
+        let mut proposal: Proposal = env
+            .storage()
+            .instance()
+            .get(&key)
+            .ok_or(Error::ProposalNotFound)?;
+
+        // Check that the proposal is in Queued status
+        if proposal.status != ProposalStatus::Queued {
+            return Err(Error::InvalidProposalStatus);
+        }
+
+        // Retrieve requirements and token information for refunding
+        let requirements: ProposalRequirements =
+            env.storage().instance().get(&REQUIREMENTS_KEY).unwrap();
+        let token_address: Address = env.storage().instance().get(&TOKEN_KEY).unwrap();
+        let token_client = TokenClient::new(env, &token_address);
+        let contract_addr: Address = env.current_contract_address();
+
+        // Refund the stake to the proposer since the proposal did pass
+        token_client.transfer(
+            &contract_addr,
+            &proposal.proposer,
+            &requirements.required_stake,
+        );
+
+        // Update proposal status to Expired
+        proposal.status = ProposalStatus::Expired;
+        env.storage().instance().set(&key, &proposal);
+
+        // Update status lists
+        Self::remove_from_status_list(env, proposal_id, ProposalStatus::Queued);
+        Self::add_to_status_list(env, proposal_id, ProposalStatus::Expired);
+
+        // Emit an event for the expired proposal
+        env.events().publish(
+            (symbol_short!("proposal"), symbol_short!("expired")),
+            proposal_id,
+        );
+
+        Ok(())
+    }
+
     /// Add a proposal ID to a status-based list
     ///
     /// # Arguments
@@ -672,6 +857,16 @@ impl ProposalManager {
             .ok_or(Error::ProposalNotFound)
     }
 
+    /// Store a proposal under its ID
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal to store
+    pub fn save_proposal(env: &Env, proposal: &Proposal) {
+        let key = Self::get_proposal_key(env, proposal.id);
+        env.storage().instance().set(&key, proposal);
+    }
+
     /// Get all proposal IDs with a given status
     ///
     /// # Arguments
@@ -705,6 +900,8 @@ impl ProposalManager {
             ProposalStatus::Executed,
             ProposalStatus::Canceled,
             ProposalStatus::Vetoed,
+            ProposalStatus::Queued,
+            ProposalStatus::Expired,
         ];
 
         // Collect proposals from all status lists
@@ -794,6 +991,8 @@ impl ProposalStatus {
             4 => ProposalStatus::Executed,
             5 => ProposalStatus::Canceled,
             6 => ProposalStatus::Vetoed,
+            7 => ProposalStatus::Queued,
+            8 => ProposalStatus::Expired,
             _ => panic!("Invalid proposal status"),
         }
     }
@@ -0,0 +1,147 @@
+use crate::types::{Error, SignedVote, VoteSupport};
+use crate::utils::get_governance_op_key;
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+/// SignatureVoting lets voters authorize votes with an off-chain ed25519 signature
+/// A relayer submits the signed votes, so voters do not need to send a transaction per vote
+pub struct SignatureVoting;
+
+impl SignatureVoting {
+    /// Register the ed25519 public key a voter signs votes with
+    /// Registering again replaces the previous key
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `public_key` - The ed25519 public key
+    pub fn register_key(env: &Env, voter: &Address, public_key: &BytesN<32>) {
+        voter.require_auth();
+
+        let key = Self::get_signing_key_key(env, voter);
+        env.storage().instance().set(&key, public_key);
+
+        env.events().publish(
+            (symbol_short!("vote"), symbol_short!("sig_key")),
+            (voter, public_key),
+        );
+    }
+
+    /// Get the ed25519 public key registered by a voter
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Option<BytesN<32>>` - The public key if one is registered
+    pub fn get_key(env: &Env, voter: &Address) -> Option<BytesN<32>> {
+        let key = Self::get_signing_key_key(env, voter);
+        env.storage().instance().get(&key)
+    }
+
+    /// Get the nonce the voter's next signed vote must use
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `u64` - The next nonce
+    pub fn get_nonce(env: &Env, voter: &Address) -> u64 {
+        let key = Self::get_nonce_key(env, voter);
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+
+    /// Build the message a voter signs: the XDR encoding of
+    /// (governance contract, proposal ID, support, nonce, deadline)
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `support` - The position taken by the voter
+    /// * `nonce` - The voter's next nonce
+    /// * `deadline` - The last timestamp at which the vote may be submitted
+    ///
+    /// # Returns
+    /// * `Bytes` - The message to sign
+    pub fn vote_message(
+        env: &Env,
+        proposal_id: u32,
+        support: &VoteSupport,
+        nonce: u64,
+        deadline: u64,
+    ) -> Bytes {
+        (env.current_contract_address(), proposal_id, support.clone(), nonce, deadline).to_xdr(env)
+    }
+
+    /// Verify a signed vote without consuming its nonce
+    /// An invalid signature aborts the whole call, as ed25519 verification traps on failure
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `vote` - The signed vote
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the key, nonce or deadline is wrong
+    pub fn verify(env: &Env, vote: &SignedVote) -> Result<(), Error> {
+        let public_key = Self::get_key(env, &vote.voter).ok_or(Error::SigningKeyNotFound)?;
+
+        // A signed vote can only be relayed until its deadline
+        if env.ledger().timestamp() > vote.deadline {
+            return Err(Error::InvalidNonce);
+        }
+
+        // Each nonce is accepted once and in order, so a signed vote cannot be replayed
+        if vote.nonce != Self::get_nonce(env, &vote.voter) {
+            return Err(Error::InvalidNonce);
+        }
+
+        let message = Self::vote_message(
+            env,
+            vote.proposal_id,
+            &vote.support,
+            vote.nonce,
+            vote.deadline,
+        );
+        env.crypto()
+            .ed25519_verify(&public_key, &message, &vote.signature);
+        Ok(())
+    }
+
+    /// Consume a voter's nonce once their signed vote has been recorded
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    pub fn consume_nonce(env: &Env, voter: &Address) {
+        let key = Self::get_nonce_key(env, voter);
+        let nonce = Self::get_nonce(env, voter);
+        env.storage().instance().set(&key, &(nonce + 1));
+    }
+
+    /// Generate a storage key for a voter's signing key
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_signing_key_key(env: &Env, voter: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"SKEY_");
+        get_governance_op_key(env, key_bytes, 0, voter)
+    }
+
+    /// Generate a storage key for a voter's next nonce
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_nonce_key(env: &Env, voter: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"NONCE_");
+        get_governance_op_key(env, key_bytes, 0, voter)
+    }
+}
@@ -0,0 +1,577 @@
+use crate::types::{
+    Error, Grant, GrantTerms, Outflow, OutflowKind, SpendingLimit, SpendingWindow,
+    TreasuryBalance, GRANT_COUNTER_KEY, OUTFLOWS_KEY, TREASURY_TOKENS_KEY,
+};
+use crate::utils::{get_governance_op_key, get_key_str};
+use soroban_sdk::{symbol_short, token::TokenClient, vec, Address, Bytes, Env, Symbol, Vec};
+
+/// Treasury holds community funds in any number of tokens
+/// Funds only leave through executed proposals (one-off transfers and streamed grants),
+/// subject to per-token spending limits per period
+pub struct Treasury;
+
+impl Treasury {
+    /// Deposit tokens into the treasury
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `from` - The address funding the treasury
+    /// * `token` - The token being deposited
+    /// * `amount` - The amount to deposit
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn deposit(env: &Env, from: &Address, token: &Address, amount: i128) -> Result<(), Error> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let token_client = TokenClient::new(env, token);
+        token_client.transfer(from, &env.current_contract_address(), &amount);
+
+        // Treasury balances are tracked separately from staked and deposited voting tokens
+        let balance = Self::get_held(env, token) + amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_balance_key(env, token), &balance);
+        Self::track_token(env, token);
+
+        env.events().publish(
+            (symbol_short!("treasury"), symbol_short!("deposit")),
+            (from, token, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Pay tokens from the treasury for an executed proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal approving the transfer
+    /// * `token` - The token to pay
+    /// * `recipient` - The address receiving the tokens
+    /// * `amount` - The amount to pay
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn transfer(
+        env: &Env,
+        proposal_id: u32,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if Self::get_available(env, token) < amount {
+            return Err(Error::InsufficientTreasury);
+        }
+        let window = Self::check_spending_limit(env, token, amount)?;
+
+        // Pay out before updating balances so a failing token leaves the treasury untouched
+        let token_client = TokenClient::new(env, token);
+        if token_client
+            .try_transfer(&env.current_contract_address(), recipient, &amount)
+            .is_err()
+        {
+            return Err(Error::ExecutionFailed);
+        }
+
+        Self::record_spend(env, token, window);
+        let balance = Self::get_held(env, token) - amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_balance_key(env, token), &balance);
+        Self::record_outflow(
+            env,
+            token,
+            recipient,
+            amount,
+            OutflowKind::Transfer,
+            proposal_id,
+        );
+
+        Ok(())
+    }
+
+    /// Start a streamed grant for an executed proposal
+    /// The full amount counts against the spending limit and is reserved when the grant starts
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal approving the grant
+    /// * `terms` - The token, recipient, amount and duration of the stream
+    ///
+    /// # Returns
+    /// * `Result<u32, Error>` - The ID of the new grant or an error
+    pub fn create_grant(env: &Env, proposal_id: u32, terms: &GrantTerms) -> Result<u32, Error> {
+        if terms.amount <= 0 || terms.duration == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if Self::get_available(env, &terms.token) < terms.amount {
+            return Err(Error::InsufficientTreasury);
+        }
+        let window = Self::check_spending_limit(env, &terms.token, terms.amount)?;
+        Self::record_spend(env, &terms.token, window);
+
+        // Reserve the full amount so it cannot be spent elsewhere
+        let reserved = Self::get_reserved(env, &terms.token) + terms.amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_reserved_key(env, &terms.token), &reserved);
+
+        let grant_id: u32 = env
+            .storage()
+            .instance()
+            .get(&GRANT_COUNTER_KEY)
+            .unwrap_or(0)
+            + 1;
+        env.storage().instance().set(&GRANT_COUNTER_KEY, &grant_id);
+
+        let grant = Grant {
+            id: grant_id,
+            proposal_id,
+            token: terms.token.clone(),
+            recipient: terms.recipient.clone(),
+            amount: terms.amount,
+            claimed: 0,
+            start: env.ledger().timestamp(),
+            duration: terms.duration,
+        };
+        env.storage()
+            .instance()
+            .set(&Self::get_grant_key(env, grant_id), &grant);
+
+        env.events().publish(
+            (symbol_short!("treasury"), symbol_short!("grant")),
+            (grant_id, proposal_id, grant.recipient, grant.amount),
+        );
+
+        Ok(grant_id)
+    }
+
+    /// Pay out the vested, unclaimed part of a grant to its recipient
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant_id` - The ID of the grant
+    ///
+    /// # Returns
+    /// * `Result<i128, Error>` - The amount paid or an error
+    pub fn claim_grant(env: &Env, grant_id: u32) -> Result<i128, Error> {
+        let mut grant = Self::get_grant(env, grant_id)?;
+
+        let amount = Self::get_vested(env, &grant) - grant.claimed;
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        grant.claimed += amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_grant_key(env, grant_id), &grant);
+
+        // Release the reservation and the held balance together
+        let reserved = Self::get_reserved(env, &grant.token) - amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_reserved_key(env, &grant.token), &reserved);
+        let balance = Self::get_held(env, &grant.token) - amount;
+        env.storage()
+            .instance()
+            .set(&Self::get_balance_key(env, &grant.token), &balance);
+
+        let token_client = TokenClient::new(env, &grant.token);
+        token_client.transfer(&env.current_contract_address(), &grant.recipient, &amount);
+
+        Self::record_outflow(
+            env,
+            &grant.token,
+            &grant.recipient,
+            amount,
+            OutflowKind::GrantClaim,
+            grant_id,
+        );
+
+        Ok(amount)
+    }
+
+    /// Get a grant by its ID
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant_id` - The ID of the grant
+    ///
+    /// # Returns
+    /// * `Result<Grant, Error>` - The grant or an error if not found
+    pub fn get_grant(env: &Env, grant_id: u32) -> Result<Grant, Error> {
+        env.storage()
+            .instance()
+            .get(&Self::get_grant_key(env, grant_id))
+            .ok_or(Error::GrantNotFound)
+    }
+
+    /// Get the amount of a grant vested so far
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant` - The grant
+    ///
+    /// # Returns
+    /// * `i128` - The vested amount, including what has been claimed
+    pub fn get_vested(env: &Env, grant: &Grant) -> i128 {
+        let elapsed = env.ledger().timestamp().saturating_sub(grant.start);
+        if elapsed >= grant.duration {
+            return grant.amount;
+        }
+        grant.amount * elapsed as i128 / grant.duration as i128
+    }
+
+    /// Get the treasury balance of a token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `TreasuryBalance` - The available and reserved amounts
+    pub fn get_balance(env: &Env, token: &Address) -> TreasuryBalance {
+        TreasuryBalance {
+            token: token.clone(),
+            available: Self::get_available(env, token),
+            reserved: Self::get_reserved(env, token),
+        }
+    }
+
+    /// Get the treasury balances of every token it has received
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<TreasuryBalance>` - The balance of each token
+    pub fn get_balances(env: &Env) -> Vec<TreasuryBalance> {
+        let mut balances = vec![env];
+        for token in Self::get_tokens(env).iter() {
+            balances.push_back(Self::get_balance(env, &token));
+        }
+        balances
+    }
+
+    /// Get the history of tokens paid out of the treasury
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<Outflow>` - The outflows, oldest first
+    pub fn get_outflows(env: &Env) -> Vec<Outflow> {
+        env.storage()
+            .instance()
+            .get(&OUTFLOWS_KEY)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Set the spending limit of a token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to limit
+    /// * `limit` - The maximum amount committed per period
+    /// * `period` - The length of a period in seconds
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn set_spending_limit(
+        env: &Env,
+        token: &Address,
+        limit: i128,
+        period: u64,
+    ) -> Result<(), Error> {
+        if limit < 0 || period == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let spending_limit = SpendingLimit { limit, period };
+        env.storage()
+            .instance()
+            .set(&Self::get_limit_key(env, token), &spending_limit);
+
+        env.events().publish(
+            (symbol_short!("treasury"), symbol_short!("limit")),
+            (token, limit, period),
+        );
+
+        Ok(())
+    }
+
+    /// Get the spending limit of a token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `Option<SpendingLimit>` - The limit, or None if spending is unlimited
+    pub fn get_spending_limit(env: &Env, token: &Address) -> Option<SpendingLimit> {
+        env.storage()
+            .instance()
+            .get(&Self::get_limit_key(env, token))
+    }
+
+    /// Get the amount of a token committed in the current period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `i128` - The amount spent in the current period
+    pub fn get_period_spent(env: &Env, token: &Address) -> i128 {
+        match Self::get_spending_limit(env, token) {
+            Some(limit) => Self::current_window(env, token, &limit).spent,
+            None => 0,
+        }
+    }
+
+    /// Check that a spend fits in the token's limit for the current period
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token being spent
+    /// * `amount` - The amount to spend
+    ///
+    /// # Returns
+    /// * `Result<Option<SpendingWindow>, Error>` - The updated window to record, if limited
+    fn check_spending_limit(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+    ) -> Result<Option<SpendingWindow>, Error> {
+        let limit = match Self::get_spending_limit(env, token) {
+            Some(limit) => limit,
+            None => return Ok(None),
+        };
+
+        let mut window = Self::current_window(env, token, &limit);
+        if window.spent + amount > limit.limit {
+            return Err(Error::SpendingLimitExceeded);
+        }
+        window.spent += amount;
+
+        Ok(Some(window))
+    }
+
+    /// Get the spending window for the current period, starting a new one if it elapsed
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    /// * `limit` - The token's spending limit
+    ///
+    /// # Returns
+    /// * `SpendingWindow` - The window covering the current time
+    fn current_window(env: &Env, token: &Address, limit: &SpendingLimit) -> SpendingWindow {
+        let now = env.ledger().timestamp();
+        let window: Option<SpendingWindow> = env
+            .storage()
+            .instance()
+            .get(&Self::get_window_key(env, token));
+
+        match window {
+            Some(window) if now < window.start + limit.period => window,
+            _ => SpendingWindow {
+                start: now,
+                spent: 0,
+            },
+        }
+    }
+
+    /// Store the updated spending window of a token
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token being spent
+    /// * `window` - The window returned by `check_spending_limit`
+    fn record_spend(env: &Env, token: &Address, window: Option<SpendingWindow>) {
+        if let Some(window) = window {
+            env.storage()
+                .instance()
+                .set(&Self::get_window_key(env, token), &window);
+        }
+    }
+
+    /// Append a payout to the outflow history
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token paid out
+    /// * `recipient` - The address that received the tokens
+    /// * `amount` - The amount paid out
+    /// * `kind` - Transfer or grant claim
+    /// * `reference` - The proposal ID or grant ID
+    fn record_outflow(
+        env: &Env,
+        token: &Address,
+        recipient: &Address,
+        amount: i128,
+        kind: OutflowKind,
+        reference: u32,
+    ) {
+        let outflow = Outflow {
+            token: token.clone(),
+            recipient: recipient.clone(),
+            amount,
+            kind,
+            reference,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        let mut outflows = Self::get_outflows(env);
+        outflows.push_back(outflow.clone());
+        env.storage().instance().set(&OUTFLOWS_KEY, &outflows);
+
+        env.events().publish(
+            (symbol_short!("treasury"), symbol_short!("outflow")),
+            outflow,
+        );
+    }
+
+    /// Get the amount of a token that can still be spent
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `i128` - The held amount minus grant reservations
+    fn get_available(env: &Env, token: &Address) -> i128 {
+        Self::get_held(env, token) - Self::get_reserved(env, token)
+    }
+
+    /// Get the amount of a token held by the treasury, including reservations
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `i128` - The held amount
+    fn get_held(env: &Env, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Self::get_balance_key(env, token))
+            .unwrap_or(0)
+    }
+
+    /// Get the amount of a token reserved for grants
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to check
+    ///
+    /// # Returns
+    /// * `i128` - The reserved amount
+    fn get_reserved(env: &Env, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&Self::get_reserved_key(env, token))
+            .unwrap_or(0)
+    }
+
+    /// Get the tokens the treasury has received
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The token addresses
+    fn get_tokens(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&TREASURY_TOKENS_KEY)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Add a token to the list of treasury tokens if it is not already tracked
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token to track
+    fn track_token(env: &Env, token: &Address) {
+        let mut tokens = Self::get_tokens(env);
+        if !tokens.contains(token) {
+            tokens.push_back(token.clone());
+            env.storage().instance().set(&TREASURY_TOKENS_KEY, &tokens);
+        }
+    }
+
+    /// Generate a storage key for a token's treasury balance
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token address
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_balance_key(env: &Env, token: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"TBAL_");
+        get_governance_op_key(env, key_bytes, 0, token)
+    }
+
+    /// Generate a storage key for a token's grant reservations
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token address
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_reserved_key(env: &Env, token: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"TRSV_");
+        get_governance_op_key(env, key_bytes, 0, token)
+    }
+
+    /// Generate a storage key for a token's spending limit
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token address
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_limit_key(env: &Env, token: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"TLIM_");
+        get_governance_op_key(env, key_bytes, 0, token)
+    }
+
+    /// Generate a storage key for a token's current spending window
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `token` - The token address
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_window_key(env: &Env, token: &Address) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"TWIN_");
+        get_governance_op_key(env, key_bytes, 0, token)
+    }
+
+    /// Generate a storage key for a grant
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `grant_id` - The ID of the grant
+    ///
+    /// # Returns
+    /// * `Symbol` - The storage key
+    fn get_grant_key(env: &Env, grant_id: u32) -> Symbol {
+        let key_bytes = Bytes::from_slice(env, b"GRANT_");
+        get_key_str(env, key_bytes, grant_id)
+    }
+}
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracterror, contracttype, symbol_short, Address, String, Symbol, Vec};
+use soroban_sdk::{
+    contracterror, contracttype, symbol_short, Address, BytesN, String, Symbol, Val, Vec,
+};
 
 // Enum representing the status of a proposal
 #[contracttype]
@@ -11,6 +13,8 @@ pub enum ProposalStatus {
     Executed = 4, // Proposal has been executed
     Canceled = 5, // Proposal has been canceled
     Vetoed = 6,   // Proposal has been vetoed
+    Queued = 7,   // Proposal is queued in the timelock awaiting execution
+    Expired = 8,  // Proposal missed its execution window
 }
 
 // Enum representing the type of a proposal
@@ -42,6 +46,36 @@ pub struct Proposal {
     pub actions: Vec<Action>,        // Actions associated with the proposal
 }
 
+// Struct representing a published version of a proposal
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProposalRevision {
+    pub revision: u32,         // Revision number, starting at 1 for the original draft
+    pub description: Symbol,   // Description of this version
+    pub metadata_hash: String, // Metadata hash of this version
+    pub actions: Vec<Action>,  // Actions of this version
+    pub published_at: u64,     // Timestamp when this version was published
+}
+
+// Struct representing a co-sponsor backing a draft proposal
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sponsorship {
+    pub sponsor: Address, // Address of the co-sponsor
+    pub weight: i128,     // Deposited voting power of the co-sponsor when sponsoring
+    pub timestamp: u64,   // Timestamp of the sponsorship
+}
+
+// Struct representing what a draft needs before it can be activated
+// A zero threshold disables that path; when both are zero no sponsorship is needed
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SponsorshipConfig {
+    pub min_sponsors: u32,        // Co-sponsors that make a draft votable
+    pub min_sponsor_weight: i128, // Combined co-sponsor voting power that makes a draft votable
+    pub discussion_period: u64,   // Seconds between the latest revision and activation
+}
+
 // Struct representing the requirements for a proposal
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -52,16 +86,82 @@ pub struct ProposalRequirements {
     pub max_voting_power: i128, // Maximum voting power allowed
 }
 
+// Enum representing the position taken by a vote
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum VoteSupport {
+    Against = 0, // Vote against the proposal
+    For = 1,     // Vote in support of the proposal
+    Abstain = 2, // Counts toward quorum but not toward the threshold
+}
+
 // Struct representing a vote
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub struct Vote {
-    pub voter: Address, // Address of the voter
-    pub support: bool,  // Whether the vote is in support of the proposal
+    pub voter: Address,        // Address of the voter
+    pub support: VoteSupport,  // Position taken by the voter
     pub weight: i128,   // Weight of the vote
     pub timestamp: u64, // Timestamp when the vote was cast
 }
 
+// Struct representing a vote signed off-chain with the voter's ed25519 key
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedVote {
+    pub voter: Address,         // Address of the voter
+    pub proposal_id: u32,       // ID of the proposal being voted on
+    pub support: VoteSupport,   // Position taken by the voter
+    pub nonce: u64,             // Voter's next signing nonce, prevents replay
+    pub deadline: u64,          // Last timestamp at which the vote may be submitted
+    pub signature: BytesN<64>,  // Signature over the vote message
+}
+
+// Struct representing the outcome of a single relayed vote
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedVoteResult {
+    pub voter: Address,     // Address of the voter
+    pub success: bool,      // Whether the vote was recorded
+    pub error: Option<u32>, // Error code if the vote was rejected
+}
+
+// Enum representing how the options of a multi-choice proposal are tallied
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TallyMethod {
+    Plurality = 0,    // One choice per voter, most votes wins
+    Approval = 1,     // Any number of approved options, most approvals wins
+    RankedChoice = 2, // Ranked ballots resolved by instant runoff
+}
+
+// Struct representing the options of a multi-choice proposal
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChoiceConfig {
+    pub options: Vec<Symbol>,  // Labels of the available options
+    pub method: TallyMethod,   // How the ballots are tallied
+}
+
+// Struct representing a ballot on a multi-choice proposal
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChoiceVote {
+    pub voter: Address,    // Address of the voter
+    pub choices: Vec<u32>, // Selected option indexes, in order of preference for ranked ballots
+    pub weight: i128,      // Weight of the vote
+    pub timestamp: u64,    // Timestamp when the vote was cast
+}
+
+// Struct representing the outcome of a multi-choice tally
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChoiceResult {
+    pub winner: Option<u32>, // Winning option index, if quorum and the method's rule are met
+    pub tallies: Vec<u128>,  // Votes per option (final round for ranked choice)
+    pub rounds: u32,         // Number of counting rounds performed
+}
+
 // Struct representing the voting configuration
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -71,6 +171,16 @@ pub struct VotingConfig {
     pub threshold: u128,            // Minimum threshold required for the proposal to pass
     pub execution_delay: u64,       // Delay before the proposal can be executed
     pub one_address_one_vote: bool, // Whether each address gets one vote
+    pub weighting: WeightingMode,   // How token weight is turned into votes in token voting
+}
+
+// Enum representing how deposited tokens are weighted in token voting
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum WeightingMode {
+    Linear = 0,     // One vote per deposited token
+    Quadratic = 1,  // Votes are the square root of deposited tokens, one identity per voter
+    Conviction = 2, // Votes accrue for as long as they stay committed to a position
 }
 
 // Struct representing a snapshot of weights
@@ -79,6 +189,48 @@ pub struct VotingConfig {
 pub struct WeightSnapshot {
     pub proposal_id: u32, // ID of the proposal
     pub snapshot_at: u64, // Timestamp when the snapshot was taken
+    pub ledger: u32,      // Ledger sequence whose checkpointed balances are used
+}
+
+// Struct representing a deposited voting power balance as of a ledger
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,   // Ledger sequence at which the balance was recorded
+    pub balance: i128, // Deposited balance from this ledger onwards
+}
+
+// Enum representing which proposals a delegation applies to
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DelegationScope {
+    All,                // Every proposal type without a type-specific delegation
+    Type(ProposalType), // Only proposals of this type
+}
+
+// Struct representing a share of voting power delegated to another address
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delegation {
+    pub delegatee: Address,     // Address receiving the delegated power
+    pub share: u32,             // Share of the delegator's power in basis points
+    pub scope: DelegationScope, // Proposals the share applies to
+}
+
+// Struct representing a delegator's delegations as of a ledger
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegationCheckpoint {
+    pub ledger: u32,                  // Ledger sequence at which the delegations were recorded
+    pub delegations: Vec<Delegation>, // Delegations in effect from this ledger onwards
+}
+
+// Struct representing the addresses delegating to a delegatee as of a ledger
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DelegatorsCheckpoint {
+    pub ledger: u32,              // Ledger sequence at which the delegators were recorded
+    pub delegators: Vec<Address>, // Delegators in effect from this ledger onwards
 }
 
 // Struct representing a moderator
@@ -98,7 +250,125 @@ pub enum Action {
     RemoveModerator(Address),                         // Remove an existing moderator
     UpdateRewardRates(RewardRates),                   // Update reward rates
     UpdateLevelRequirements(LevelRequirements),       // Update level requirements
-    UpdateAuctionConditions(u32, AuctionConditions),  // Update auction conditions
+    UpdateAuctionPlatformFee(Address, u32),           // Update auction platform fee recipient and basis points
+    Invoke(ContractCall),                             // Call an allowlisted function on any contract
+    TreasuryTransfer(Address, Address, i128),         // Pay a treasury token (token, recipient, amount)
+    StreamGrant(GrantTerms),                          // Stream treasury tokens to a recipient over time
+    Pause(Address, PauseScope, u64),                  // Pause a target for a time (contract, scope, seconds)
+    Unpause(Address, PauseScope),                     // Lift a pause early (contract, scope)
+    UpgradeContract(Address, BytesN<32>),             // Upgrade a contract's code (target, wasm_hash)
+}
+
+// Enum representing what a pause applies to on a pausable contract
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PauseScope {
+    Contract,         // Every guarded function of the contract
+    Function(Symbol), // A single function, by name
+}
+
+// Struct representing the fast-track settings for emergency proposals
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmergencyConfig {
+    pub voting_duration: u64,    // Voting period applied to emergency proposals
+    pub approvals: u32,          // Moderator approvals that execute an emergency proposal at once
+    pub max_pause_duration: u64, // Longest pause an action may request, in seconds
+}
+
+// Struct representing the terms of a streamed treasury grant
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrantTerms {
+    pub token: Address,     // Token paid out by the grant
+    pub recipient: Address, // Address receiving the stream
+    pub amount: i128,       // Total amount streamed
+    pub duration: u64,      // Seconds over which the amount vests linearly
+}
+
+// Struct representing a streamed treasury grant
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grant {
+    pub id: u32,            // Unique identifier of the grant
+    pub proposal_id: u32,   // Proposal that approved the grant
+    pub token: Address,     // Token paid out by the grant
+    pub recipient: Address, // Address receiving the stream
+    pub amount: i128,       // Total amount streamed
+    pub claimed: i128,      // Amount already paid out
+    pub start: u64,         // Timestamp the stream started vesting
+    pub duration: u64,      // Seconds over which the amount vests linearly
+}
+
+// Struct representing a token held by the treasury
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreasuryBalance {
+    pub token: Address,  // Token held by the treasury
+    pub available: i128, // Amount that can still be spent
+    pub reserved: i128,  // Amount committed to grants but not yet claimed
+}
+
+// Struct representing a per-period spending limit for a treasury token
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpendingLimit {
+    pub limit: i128, // Maximum amount committed per period
+    pub period: u64, // Length of a period in seconds
+}
+
+// Struct representing the spending recorded in the current period
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpendingWindow {
+    pub start: u64,  // Timestamp the current period started
+    pub spent: i128, // Amount committed during the current period
+}
+
+// Enum representing why tokens left the treasury
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutflowKind {
+    Transfer = 0,   // One-off transfer approved by a proposal
+    GrantClaim = 1, // Vested amount claimed from a streamed grant
+}
+
+// Struct representing tokens paid out of the treasury
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outflow {
+    pub token: Address,     // Token paid out
+    pub recipient: Address, // Address that received the tokens
+    pub amount: i128,       // Amount paid out
+    pub kind: OutflowKind,  // Transfer or grant claim
+    pub reference: u32,     // Proposal ID for transfers, grant ID for claims
+    pub timestamp: u64,     // Timestamp of the payout
+}
+
+// Struct representing an arbitrary contract call made by governance
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContractCall {
+    pub contract: Address, // Address of the target contract
+    pub function: Symbol,  // Name of the function to invoke
+    pub args: Vec<Val>,    // Arguments passed to the function
+}
+
+// Struct representing a (contract, function) pair governance is allowed to call
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllowedCall {
+    pub contract: Address, // Address of the target contract
+    pub function: Symbol,  // Name of the allowed function
+}
+
+// Struct representing the outcome of a single executed action
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionResult {
+    pub index: u32,         // Position of the action in the proposal
+    pub success: bool,      // Whether the action succeeded
+    pub error: Option<u32>, // Error code if the action failed
 }
 
 // Custom Errors
@@ -117,22 +387,45 @@ pub enum Error {
     InvalidProposalType = 106,       // Invalid proposal type
     ProposalLimitReached = 107,      // Proposal limit reached
     InvalidProposalInput = 108,      // Invalid proposal input
+    AlreadySponsored = 109,          // Address has already sponsored the draft
+    InsufficientSponsorship = 110,   // Draft lacks the required co-sponsors or weight
+    DiscussionPeriodActive = 111,    // Draft is still in its discussion period
     ProposalNotActive = 201,         // Proposal is not active
     AlreadyVoted = 202,              // Voter has already voted
     NoVotingPower = 203,             // No voting power available
     InvalidVotingPeriod = 204,       // Invalid voting period
+    InvalidAmount = 205,             // Deposit or withdrawal amount must be positive
+    InsufficientDeposit = 206,       // Withdrawal exceeds deposited power, or nothing to delegate
+    InvalidChoice = 207,             // Invalid options or ballot for a multi-choice proposal
+    WrongVoteType = 208,             // Vote type does not match the proposal
+    InvalidVotingMode = 209,         // Weighting mode is not supported for the proposal
+    SigningKeyNotFound = 210,        // Voter has not registered a signing key
+    InvalidNonce = 211,              // Signed vote is past its deadline or not on the next nonce
+    InvalidVoteBatch = 212,          // Relayed batch is empty or too large
     InvalidDelegation = 301,         // Invalid delegation
     SelfDelegationNotAllowed = 302,  // Self-delegation is not allowed
+    InvalidDelegationShare = 303,    // Share is zero, above 100% or over-allocated
+    TooManyDelegates = 304,          // Delegator or delegatee has reached its delegation limit
     ProposalNotExecutable = 401,     // Proposal is not executable
     ExecutionFailed = 402,           // Execution of the proposal failed
     ExecutionDelayNotMet = 403,      // Execution delay not met
     InvalidAction = 404,             // Invalid action
+    CallNotAllowed = 405,            // Contract call is not on the allowlist
+    ProposalExpired = 406,           // Execution window has passed
+    ExecutionWindowOpen = 407,       // Proposal can still be executed
     NotVerified = 501,               // User is not verified
     UserLevelNotSet = 502,           // User level is not set
     InsufficientReferralLevel = 503, // Insufficient referral level
     ModeratorNotFound = 601,         // Moderator not found
     AlreadyModerator = 602,          // Address is already a moderator
     ContractCallFailed = 701,        // Contract call failed
+    InsufficientTreasury = 801,      // Treasury does not hold enough of the token
+    SpendingLimitExceeded = 802,     // Spend would exceed the token's limit for the period
+    GrantNotFound = 803,             // Grant not found
+    NothingToClaim = 804,            // No vested amount is left to claim
+    NotEmergencyProposal = 901,      // Proposal is not an emergency action
+    AlreadyApproved = 902,           // Moderator has already approved the proposal
+    InvalidEmergencyConfig = 903,    // Emergency duration, approvals or pause limit is zero
 }
 
 // Constants
@@ -150,6 +443,38 @@ pub const VOTE_PREFIX: Symbol = symbol_short!("VOTE");
 pub const WEIGHT_PREFIX: Symbol = symbol_short!("WGHT");
 pub const MODERATOR_KEY: Symbol = symbol_short!("MODS");
 pub const DEFAULT_CONFIG_KEY: Symbol = symbol_short!("DEFCFG");
+pub const GUARDIAN_KEY: Symbol = symbol_short!("GUARD");
+pub const GRACE_PERIOD_KEY: Symbol = symbol_short!("GRACE");
+pub const ALLOWLIST_KEY: Symbol = symbol_short!("ALLOW");
+pub const TOTAL_CHECKPOINTS_KEY: Symbol = symbol_short!("TCKPT");
+pub const TREASURY_TOKENS_KEY: Symbol = symbol_short!("TTOKENS");
+pub const GRANT_COUNTER_KEY: Symbol = symbol_short!("GCNT");
+pub const OUTFLOWS_KEY: Symbol = symbol_short!("OUTFLOW");
+pub const EMERGENCY_KEY: Symbol = symbol_short!("EMERG");
+pub const SPONSORSHIP_KEY: Symbol = symbol_short!("SPONSOR");
+
+// Bounds on the number of options in a multi-choice proposal
+pub const MIN_CHOICE_OPTIONS: u32 = 2;
+pub const MAX_CHOICE_OPTIONS: u32 = 10;
+
+// Maximum number of signed votes a relayer can submit in one call
+pub const MAX_SIGNED_VOTES: u32 = 20;
+
+// Delegation limits: shares are in basis points, and delegated power is
+// passed on through at most MAX_DELEGATION_DEPTH delegates
+pub const FULL_DELEGATION_SHARE: u32 = 10000;
+pub const MAX_DELEGATES: u32 = 5;
+pub const MAX_DELEGATORS: u32 = 50;
+pub const MAX_DELEGATION_DEPTH: u32 = 3;
+
+// Default time a queued proposal stays executable after its ETA (14 days)
+pub const DEFAULT_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60;
+
+// Default emergency fast track: one hour of voting, two moderator
+// approvals, and pauses of at most 7 days
+pub const DEFAULT_EMERGENCY_DURATION: u64 = 60 * 60;
+pub const DEFAULT_EMERGENCY_APPROVALS: u32 = 2;
+pub const DEFAULT_MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
 
 // Referral contract types
 #[contracttype]
@@ -184,32 +509,3 @@ pub enum UserLevel {
     Gold,
     Platinum,
 }
-
-// Auction contract types
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct AuctionConditions {
-    pub auction_type: AuctionType,
-    pub end_time: u64,
-    pub starting_price: i128,
-    pub on_bid_count: Option<u32>,
-    pub on_target_price: Option<i128>,
-    pub on_inactivity_seconds: Option<u64>,
-    pub on_fixed_sequence_number: Option<u32>,
-    pub on_minimum_participants: Option<u32>,
-    pub on_maximum_participants: Option<u32>,
-}
-
-#[contracttype]
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub enum AuctionType {
-    Regular,
-    Reverse,
-    Dutch(DutchAuctionData),
-}
-
-#[contracttype]
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub struct DutchAuctionData {
-    pub floor_price: i128,
-}
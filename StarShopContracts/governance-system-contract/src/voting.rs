@@ -1,6 +1,11 @@
+use crate::choices::ChoiceVoting;
 use crate::proposals::ProposalManager;
-use crate::types::{Error, ProposalType, UserLevel, Vote, VotingConfig, REFERRAL_KEY};
+use crate::types::{
+    Error, Proposal, ProposalStatus, ProposalType, UserLevel, Vote, VoteSupport, VotingConfig,
+    WeightingMode, REFERRAL_KEY,
+};
 use crate::utils::{get_key_str, get_governance_op_key};
+use crate::weights::WeightCalculator;
 use soroban_sdk::{symbol_short, vec, Address, Bytes, Env, IntoVal, Symbol, Vec};
 
 /// VotingSystem handles all vote-related operations for the governance system
@@ -9,12 +14,14 @@ pub struct VotingSystem;
 
 impl VotingSystem {
     /// Cast a vote for a proposal
+    /// A voter may change their vote while the proposal is active; the previous vote is replaced
+    /// The caller is responsible for authenticating the voter, directly or by signature
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `proposal_id` - The ID of the proposal being voted on
     /// * `voter` - The address of the voter
-    /// * `support` - Whether the vote is in support (true) or against (false)
+    /// * `support` - Whether the vote is for, against or abstaining
     /// * `weight` - The voting weight of the voter
     ///
     /// # Returns
@@ -23,15 +30,161 @@ impl VotingSystem {
         env: &Env,
         proposal_id: u32,
         voter: &Address,
-        support: bool,
+        support: VoteSupport,
         weight: i128,
     ) -> Result<(), Error> {
-        // Require authentication from the voter
-        voter.require_auth();
-
-        // Retrieve the proposal
+        // Check the voter is verified and has the required referral level
         let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        Self::check_voter_eligibility(env, &proposal, voter)?;
+
+        // Recasting the same position is rejected, a different position replaces the vote
+        let key = Self::get_vote_key(env, proposal_id, voter);
+        let previous: Option<Vote> = env.storage().instance().get(&key);
+        if let Some(previous) = &previous {
+            if previous.support == support {
+                return Err(Error::AlreadyVoted);
+            }
+            // Votes can only be changed until the proposal is closed
+            if proposal.status != ProposalStatus::Active {
+                return Err(Error::ProposalNotActive);
+            }
+        }
+
+        // Create a new vote and store it
+        let vote = Vote {
+            voter: voter.clone(),
+            support: support.clone(),
+            weight,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&key, &vote);
+
+        // Update vote totals
+        Self::update_vote_totals(env, proposal_id, previous.as_ref(), &support, vote.weight);
+        if proposal.voting_config.weighting == WeightingMode::Conviction {
+            Self::update_conviction(env, proposal_id, previous.as_ref(), &vote);
+        }
+
+        // Publish an event for the vote
+        let action = if previous.is_some() {
+            symbol_short!("changed")
+        } else {
+            symbol_short!("cast")
+        };
+        env.events().publish(
+            (symbol_short!("vote"), action),
+            (proposal_id, voter, support, vote.weight),
+        );
+
+        Ok(())
+    }
+
+    /// Get the vote an address has cast on a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Option<Vote>` - The current vote, if any
+    pub fn get_vote(env: &Env, proposal_id: u32, voter: &Address) -> Option<Vote> {
+        let key = Self::get_vote_key(env, proposal_id, voter);
+        env.storage().instance().get(&key)
+    }
+
+    /// Check whether an address has voted on a proposal, with either kind of ballot
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `bool` - True if the address has voted
+    pub fn has_voted(env: &Env, proposal_id: u32, voter: &Address) -> bool {
+        env.storage()
+            .instance()
+            .has(&Self::get_vote_key(env, proposal_id, voter))
+            || ChoiceVoting::get_vote(env, proposal_id, voter).is_some()
+    }
+
+    /// Reweight the votes of delegates that counted a voter's delegated power
+    /// Called after the voter votes directly, which overrides their delegations
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal being voted on
+    /// * `voter` - The address that just voted
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn refresh_delegate_votes(
+        env: &Env,
+        proposal: &Proposal,
+        voter: &Address,
+    ) -> Result<(), Error> {
+        for delegate in WeightCalculator::get_voted_delegates(env, voter, proposal).iter() {
+            let weight = WeightCalculator::get_vote_weight(env, &delegate, proposal)?;
+
+            // Multi-choice ballots keep their own weights
+            if ChoiceVoting::get_vote(env, proposal.id, &delegate).is_some() {
+                ChoiceVoting::reweight_ballot(env, proposal.id, &delegate, weight);
+                continue;
+            }
+
+            let key = Self::get_vote_key(env, proposal.id, &delegate);
+            let mut vote: Vote = env.storage().instance().get(&key).unwrap();
+            if vote.weight == weight {
+                continue;
+            }
+            let previous = vote.clone();
+            vote.weight = weight;
+            env.storage().instance().set(&key, &vote);
+
+            // Replace the old weight in the totals, keeping the vote's position and time
+            Self::update_vote_totals(env, proposal.id, Some(&previous), &vote.support, weight);
+            if proposal.voting_config.weighting == WeightingMode::Conviction {
+                Self::update_conviction(env, proposal.id, Some(&previous), &vote);
+            }
+
+            env.events().publish(
+                (symbol_short!("vote"), symbol_short!("reweight")),
+                (proposal.id, delegate, previous.weight, weight),
+            );
+        }
 
+        Ok(())
+    }
+
+    /// Replace a vote's weight in the participation total
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `previous_weight` - The weight being replaced
+    /// * `weight` - The new weight
+    pub fn replace_participation(env: &Env, proposal_id: u32, previous_weight: i128, weight: i128) {
+        let key_total = Self::get_vote_total_key(env, proposal_id);
+        let total_votes: u128 = env.storage().instance().get(&key_total).unwrap_or(0);
+        let total_votes = total_votes - previous_weight as u128 + weight as u128;
+        env.storage().instance().set(&key_total, &total_votes);
+    }
+
+    /// Check that a voter is verified and meets the referral level the proposal requires
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal being voted on
+    /// * `voter` - The address of the voter
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error if the voter is not eligible
+    pub fn check_voter_eligibility(
+        env: &Env,
+        proposal: &Proposal,
+        voter: &Address,
+    ) -> Result<(), Error> {
         // Retrieve the referral contract address
         let referral: Address = env
             .storage()
@@ -59,81 +212,118 @@ impl VotingSystem {
             }
         }
 
-        // Check if the voter has already voted
-        let key = Self::get_vote_key(env, proposal_id, voter);
-        if env.storage().instance().has(&key) {
-            return Err(Error::AlreadyVoted);
-        }
-        
-        // Create a new vote and store it
-        let vote = Vote {
-            voter: voter.clone(),
-            support,
-            weight,
-            timestamp: env.ledger().timestamp(),
-        };
-        env.storage().instance().set(&key, &vote);
-
-        // Update vote totals
-        Self::update_vote_totals(env, proposal_id, support, vote.weight);
-
-        // Publish an event for the vote
-        env.events().publish(
-            (symbol_short!("vote"), symbol_short!("cast")),
-            (proposal_id, voter, support, vote.weight),
-        );
-
         Ok(())
     }
 
     /// Update vote totals for a proposal
+    /// When a vote is changed, the previous vote's weight is removed before the new one is added
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `previous` - The vote being replaced, if any
+    /// * `support` - Whether the vote is for, against or abstaining
+    /// * `weight` - The weight of the vote
+    fn update_vote_totals(
+        env: &Env,
+        proposal_id: u32,
+        previous: Option<&Vote>,
+        support: &VoteSupport,
+        weight: i128,
+    ) {
+        // Add the weight to the total for the chosen position
+        let key = Self::get_vote_totals_key(env, proposal_id, support);
+        let votes: u128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(votes + weight as u128));
+
+        match previous {
+            Some(previous) => {
+                // Reverse the previous vote's weight from its position and the total
+                let key = Self::get_vote_totals_key(env, proposal_id, &previous.support);
+                let votes: u128 = env.storage().instance().get(&key).unwrap_or(0);
+                env.storage()
+                    .instance()
+                    .set(&key, &(votes - previous.weight as u128));
+
+                Self::replace_participation(env, proposal_id, previous.weight, weight);
+            }
+            None => Self::record_participation(env, proposal_id, weight),
+        }
+    }
+
+    /// Add a voter's weight to the participation totals used for quorum
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `proposal_id` - The ID of the proposal
-    /// * `support` - Whether the vote is in support or against
     /// * `weight` - The weight of the vote
-    fn update_vote_totals(env: &Env, proposal_id: u32, support: bool, weight: i128) {
-        // Retrieve keys for vote totals
-        let key_for = Self::get_vote_totals_key(env, proposal_id, true);
-        let key_against = Self::get_vote_totals_key(env, proposal_id, false);
+    pub fn record_participation(env: &Env, proposal_id: u32, weight: i128) {
         let key_total = Self::get_vote_total_key(env, proposal_id);
         let key_voter_count = Self::get_voter_count_key(env, proposal_id);
 
-        // Initialize vote totals if not present
-        if !env.storage().instance().has(&key_for) {
-            env.storage().instance().set(&key_for, &0u128);
-        }
-        if !env.storage().instance().has(&key_against) {
-            env.storage().instance().set(&key_against, &0u128);
-        }
-        if !env.storage().instance().has(&key_total) {
-            env.storage().instance().set(&key_total, &0u128);
-        }
-        if !env.storage().instance().has(&key_voter_count) {
-            env.storage().instance().set(&key_voter_count, &0u128);
-        }
+        // Retrieve current totals
+        let total_votes: u128 = env.storage().instance().get(&key_total).unwrap_or(0);
+        let voter_count: u128 = env.storage().instance().get(&key_voter_count).unwrap_or(0);
 
-        // Retrieve current vote totals
-        let mut for_votes: u128 = env.storage().instance().get(&key_for).unwrap_or(0);
-        let mut against_votes: u128 = env.storage().instance().get(&key_against).unwrap_or(0);
-        let mut total_votes: u128 = env.storage().instance().get(&key_total).unwrap_or(0);
-        let mut voter_count: u128 = env.storage().instance().get(&key_voter_count).unwrap_or(0);
+        // Store updated totals
+        env.storage()
+            .instance()
+            .set(&key_total, &(total_votes + weight as u128));
+        env.storage()
+            .instance()
+            .set(&key_voter_count, &(voter_count + 1));
+    }
 
-        // Update vote totals based on support
-        if support {
-            for_votes += weight as u128;
+    /// Check whether participation in a proposal has reached quorum
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `config` - The voting configuration
+    ///
+    /// # Returns
+    /// * `Result<bool, Error>` - True if quorum has been reached, or an error
+    pub fn has_quorum(env: &Env, proposal_id: u32, config: &VotingConfig) -> Result<bool, Error> {
+        let total_votes = Self::get_total_votes(env, proposal_id);
+        let voter_count = Self::get_voter_count(env, proposal_id);
+        Self::meets_quorum(env, proposal_id, config, total_votes, voter_count)
+    }
+
+    /// Check whether a share of the votes on a proposal is large enough for quorum
+    /// Multi-choice tallies use this to measure quorum on the votes their method counts
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `config` - The voting configuration
+    /// * `votes` - The vote weight counted toward quorum
+    /// * `voters` - The number of voters counted toward quorum
+    ///
+    /// # Returns
+    /// * `Result<bool, Error>` - True if the votes reach quorum, or an error
+    pub fn meets_quorum(
+        env: &Env,
+        proposal_id: u32,
+        config: &VotingConfig,
+        votes: u128,
+        voters: u128,
+    ) -> Result<bool, Error> {
+        // Quadratic votes are not comparable to deposited supply, so quorum counts verified voters
+        if config.one_address_one_vote || config.weighting == WeightingMode::Quadratic {
+            // Voter count as percentage * 10000
+            let total_voters = Self::get_total_voters(env)?;
+            if total_voters == 0 {
+                return Ok(false);
+            }
+            Ok(voters * 10000 / total_voters >= config.quorum)
         } else {
-            against_votes += weight as u128;
+            // Vote weight as percentage * 10000
+            let total_voting_power = Self::get_total_voting_power(env, proposal_id);
+            if total_voting_power <= 0 {
+                return Ok(false);
+            }
+            Ok(votes * 10000 / total_voting_power as u128 >= config.quorum)
         }
-        total_votes += weight as u128;
-        voter_count += 1;
-
-        // Store updated vote totals
-        env.storage().instance().set(&key_for, &for_votes);
-        env.storage().instance().set(&key_against, &against_votes);
-        env.storage().instance().set(&key_total, &total_votes);
-        env.storage().instance().set(&key_voter_count, &voter_count);
     }
 
     /// Check if voting has ended for a proposal
@@ -164,45 +354,34 @@ impl VotingSystem {
             return Ok(true);
         }
 
-        // Handle one-address-one-vote mode
-        if config.one_address_one_vote {
-            let voter_count = Self::get_voter_count(env, proposal_id);
-            let total_voters = Self::get_total_voters(env)?;
+        // Multi-choice proposals only close when the voting period ends
+        if ChoiceVoting::get_config(env, proposal_id).is_some() {
+            return Ok(false);
+        }
 
-            // Check if quorum has been reached (voter count as percentage * 10000)
-            if voter_count * 10000 / total_voters >= config.quorum {
-                let for_votes = Self::get_for_votes(env, proposal_id);
-                let total_votes = Self::get_total_votes(env, proposal_id);
+        // Conviction keeps accruing, so conviction proposals only close when the voting period ends
+        if config.weighting == WeightingMode::Conviction {
+            return Ok(false);
+        }
 
-                // Check if support threshold has been reached
-                if for_votes * 10000 / total_votes >= config.threshold {
-                    return Ok(true);
-                }
+        // Check if quorum has been reached
+        if Self::has_quorum(env, proposal_id, config)? {
+            let (for_votes, against_votes) = Self::get_decisive_votes(env, &proposal);
 
-                // Check if opposition threshold makes passing impossible
-                if (total_votes - for_votes) * 10000 / total_votes > (10000 - config.threshold) {
-                    return Ok(true);
-                }
+            // Abstentions count toward quorum but not toward the threshold
+            let decisive_votes = for_votes + against_votes;
+            if decisive_votes == 0 {
+                return Ok(false);
             }
-        } else {
-            // Handle weighted voting mode
-            let total_votes = Self::get_total_votes(env, proposal_id);
-            let total_voting_power = Self::get_total_voting_power(env, proposal_id);
 
-            // Check if quorum has been reached (vote weight as percentage * 10000)
-            if total_votes * 10000 / total_voting_power as u128 >= config.quorum {
-                let for_votes = Self::get_for_votes(env, proposal_id);
-                let against_votes = Self::get_against_votes(env, proposal_id);
-
-                // Check if support threshold has been reached
-                if for_votes * 10000 / total_votes >= config.threshold {
-                    return Ok(true);
-                }
+            // Check if support threshold has been reached
+            if for_votes * 10000 / decisive_votes >= config.threshold {
+                return Ok(true);
+            }
 
-                // Check if opposition threshold makes passing impossible
-                if against_votes * 10000 / total_votes > (10000 - config.threshold) {
-                    return Ok(true);
-                }
+            // Check if opposition threshold makes passing impossible
+            if against_votes * 10000 / decisive_votes > (10000 - config.threshold) {
+                return Ok(true);
             }
         }
 
@@ -219,35 +398,104 @@ impl VotingSystem {
     /// # Returns
     /// * `Result<bool, Error>` - True if the proposal passes, false otherwise, or an error
     pub fn tally_votes(env: &Env, proposal_id: u32, config: &VotingConfig) -> Result<bool, Error> {
-        if config.one_address_one_vote {
-            // Check quorum in one-address-one-vote mode
-            let voter_count = Self::get_voter_count(env, proposal_id);
-            let total_voters = Self::get_total_voters(env)?;
-            if voter_count * 10000 / total_voters < config.quorum {
-                return Ok(false);
-            }
+        // Multi-choice proposals pass when their tally method produces a winner
+        if let Some(choice_config) = ChoiceVoting::get_config(env, proposal_id) {
+            let result = ChoiceVoting::tally(env, proposal_id, &choice_config, config)?;
+            return Ok(result.winner.is_some());
+        }
 
-            // Check threshold
-            let for_votes = Self::get_for_votes(env, proposal_id);
-            let total_votes = Self::get_total_votes(env, proposal_id);
-            if for_votes * 10000 / total_votes >= config.threshold {
-                return Ok(true);
-            }
+        // Check quorum and threshold
+        if !Self::has_quorum(env, proposal_id, config)? {
+            return Ok(false);
+        }
+        let proposal = ProposalManager::get_proposal(env, proposal_id)?;
+        let (for_votes, against_votes) = Self::get_decisive_votes(env, &proposal);
+
+        // Abstentions count toward quorum but not toward the threshold
+        let decisive_votes = for_votes + against_votes;
+        if decisive_votes == 0 {
+            return Ok(false);
+        }
+        Ok(for_votes * 10000 / decisive_votes >= config.threshold)
+    }
+
+    /// Get the for and against totals the threshold is measured on
+    /// Conviction proposals use accrued conviction instead of raw weight
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal being counted
+    ///
+    /// # Returns
+    /// * `(u128, u128)` - The for and against totals
+    fn get_decisive_votes(env: &Env, proposal: &Proposal) -> (u128, u128) {
+        if proposal.voting_config.weighting == WeightingMode::Conviction {
+            (
+                Self::get_conviction(env, proposal, &VoteSupport::For),
+                Self::get_conviction(env, proposal, &VoteSupport::Against),
+            )
         } else {
-            // Check quorum and threshold in weighted voting mode
-            let for_votes = Self::get_for_votes(env, proposal_id);
-            let total_votes = Self::get_total_votes(env, proposal_id);
-            let total_voting_power = Self::get_total_voting_power(env, proposal_id);
+            (
+                Self::get_for_votes(env, proposal.id),
+                Self::get_against_votes(env, proposal.id),
+            )
+        }
+    }
 
-            if (total_votes * 10000 / total_voting_power as u128) < config.quorum {
-                return Ok(false);
-            }
-            if for_votes * 10000 / total_votes >= config.threshold {
-                return Ok(true);
-            }
+    /// Get the conviction accrued by a position on a conviction proposal
+    /// Each vote accrues its weight for every second it stays committed, and a vote
+    /// held for the whole voting period is worth its full weight
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal` - The proposal being counted
+    /// * `support` - The position to read
+    ///
+    /// # Returns
+    /// * `u128` - The accrued conviction in token units
+    pub fn get_conviction(env: &Env, proposal: &Proposal, support: &VoteSupport) -> u128 {
+        let duration = proposal.voting_config.duration.max(1);
+        let now = env.ledger().timestamp();
+        let at = now.min(proposal.activated_at + duration) as u128;
+
+        let weight: u128 = env
+            .storage()
+            .instance()
+            .get(&Self::get_vote_totals_key(env, proposal.id, support))
+            .unwrap_or(0);
+        let weighted_time: u128 = env
+            .storage()
+            .instance()
+            .get(&Self::get_conviction_key(env, proposal.id, support))
+            .unwrap_or(0);
+
+        // Late votes are not counted, so no vote should be cast after `at`; saturate regardless
+        (weight * at).saturating_sub(weighted_time) / duration as u128
+    }
+
+    /// Update the conviction accumulators for a vote
+    /// Stores the sum of weight * cast time per position so conviction can be read at any time
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `previous` - The vote being replaced, if any
+    /// * `vote` - The new vote
+    fn update_conviction(env: &Env, proposal_id: u32, previous: Option<&Vote>, vote: &Vote) {
+        // A changed vote starts accruing conviction again from zero
+        if let Some(previous) = previous {
+            let key = Self::get_conviction_key(env, proposal_id, &previous.support);
+            let weighted_time: u128 = env.storage().instance().get(&key).unwrap_or(0);
+            let previous_time = previous.weight as u128 * previous.timestamp as u128;
+            env.storage()
+                .instance()
+                .set(&key, &(weighted_time - previous_time));
         }
 
-        Ok(false)
+        let key = Self::get_conviction_key(env, proposal_id, &vote.support);
+        let weighted_time: u128 = env.storage().instance().get(&key).unwrap_or(0);
+        let vote_time = vote.weight as u128 * vote.timestamp as u128;
+        env.storage().instance().set(&key, &(weighted_time + vote_time));
     }
 
     /// Retrieve the total votes in favor of a proposal
@@ -259,7 +507,7 @@ impl VotingSystem {
     /// # Returns
     /// * `u128` - The total votes in favor
     pub fn get_for_votes(env: &Env, proposal_id: u32) -> u128 {
-        let key = Self::get_vote_totals_key(env, proposal_id, true);
+        let key = Self::get_vote_totals_key(env, proposal_id, &VoteSupport::For);
         env.storage().instance().get(&key).unwrap_or(0)
     }
 
@@ -272,7 +520,20 @@ impl VotingSystem {
     /// # Returns
     /// * `u128` - The total votes against
     pub fn get_against_votes(env: &Env, proposal_id: u32) -> u128 {
-        let key = Self::get_vote_totals_key(env, proposal_id, false);
+        let key = Self::get_vote_totals_key(env, proposal_id, &VoteSupport::Against);
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+
+    /// Retrieve the total abstaining votes on a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `u128` - The total abstaining votes
+    pub fn get_abstain_votes(env: &Env, proposal_id: u32) -> u128 {
+        let key = Self::get_vote_totals_key(env, proposal_id, &VoteSupport::Abstain);
         env.storage().instance().get(&key).unwrap_or(0)
     }
 
@@ -349,18 +610,22 @@ impl VotingSystem {
         get_governance_op_key(env, key_bytes, proposal_id, voter)
     }
 
-    /// Generate a key for vote totals (for or against)
+    /// Generate a key for vote totals (for, against or abstain)
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `proposal_id` - The ID of the proposal
-    /// * `support` - Whether the count is for support (true) or against (false)
+    /// * `support` - The position the count is for
     ///
     /// # Returns
     /// * `Symbol` - A storage key for the vote totals
-    fn get_vote_totals_key(env: &Env, proposal_id: u32, support: bool) -> Symbol {
-        // Generate a key based on the proposal ID and whether it's for or against
-        let suffix = if support { b"FOR" } else { b"AGT" };
+    fn get_vote_totals_key(env: &Env, proposal_id: u32, support: &VoteSupport) -> Symbol {
+        // Generate a key based on the proposal ID and the position counted
+        let suffix = match support {
+            VoteSupport::For => b"FOR",
+            VoteSupport::Against => b"AGT",
+            VoteSupport::Abstain => b"ABS",
+        };
         let mut key_bytes = Bytes::from_slice(env, b"VOTE_");
         key_bytes.extend_from_array(suffix);
         key_bytes.extend_from_array(b"_");
@@ -368,6 +633,28 @@ impl VotingSystem {
         get_key_str(env, key_bytes, proposal_id)
     }
 
+    /// Generate a key for the conviction accumulator of a position
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    /// * `support` - The position the accumulator is for
+    ///
+    /// # Returns
+    /// * `Symbol` - A storage key for the conviction accumulator
+    fn get_conviction_key(env: &Env, proposal_id: u32, support: &VoteSupport) -> Symbol {
+        let suffix = match support {
+            VoteSupport::For => b"FOR",
+            VoteSupport::Against => b"AGT",
+            VoteSupport::Abstain => b"ABS",
+        };
+        let mut key_bytes = Bytes::from_slice(env, b"CONV_");
+        key_bytes.extend_from_array(suffix);
+        key_bytes.extend_from_array(b"_");
+
+        get_key_str(env, key_bytes, proposal_id)
+    }
+
     /// Generate a key for the total number of votes
     ///
     /// # Arguments
@@ -1,7 +1,13 @@
-use crate::types::{Error, ProposalRequirements, WeightSnapshot, REFERRAL_KEY, REQUIREMENTS_KEY, TOKEN_KEY};
+use crate::checkpoints::VotingPowerLedger;
+use crate::types::{
+    Delegation, DelegationCheckpoint, DelegationScope, DelegatorsCheckpoint, Error, Proposal,
+    ProposalRequirements, ProposalType, WeightSnapshot, WeightingMode, FULL_DELEGATION_SHARE,
+    MAX_DELEGATES, MAX_DELEGATION_DEPTH, MAX_DELEGATORS, REQUIREMENTS_KEY,
+};
 use crate::utils::{get_governance_op_key, get_key_str};
+use crate::voting::VotingSystem;
 use soroban_sdk::{
-    symbol_short, token::TokenClient, vec, Address,
+    symbol_short, vec, Address,
     Bytes, Env, Symbol, Vec,
 };
 
@@ -11,7 +17,9 @@ pub struct WeightCalculator;
 
 impl WeightCalculator {
     /// Take a snapshot of voting power for a proposal
-    /// Captures the voting power distribution at a specific point in time
+    /// Pins the proposal to the ledger before activation so that weights and quorum
+    /// are read from checkpointed deposits rather than live balances, and deposits
+    /// made in the activation ledger itself carry no weight
     ///
     /// # Arguments
     /// * `env` - The environment object
@@ -20,33 +28,20 @@ impl WeightCalculator {
     /// # Returns
     /// * `Result<(), Error>` - Success or an error
     pub fn take_snapshot(env: &Env, proposal_id: u32) -> Result<(), Error> {
-        // Get the referral contract address
-        let referral: Address = env
-            .storage()
-            .instance()
-            .get(&REFERRAL_KEY)
-            .ok_or(Error::NotInitialized)?;
-
-        // Create a snapshot record with current timestamp
+        // Create a snapshot record at the last closed ledger
+        let ledger = env.ledger().sequence().saturating_sub(1);
         let snapshot = WeightSnapshot {
             proposal_id,
             snapshot_at: env.ledger().timestamp(),
+            ledger,
         };
 
         // Store the snapshot
         let key = Self::get_snapshot_key(env, proposal_id);
         env.storage().instance().set(&key, &snapshot);
 
-        // Get total users from referral contract
-        let total_users: u32 =
-            env.invoke_contract(&referral, &Symbol::new(&env, "get_total_users"), vec![&env]);
-
-        // Calculate total voting power (placeholder implementation)
-        let mut total_power = 0i128;
-        // Placeholder: Need user registry
-        for _ in 0..total_users {
-            total_power += 1000; // Simulate balance calculation
-        }
+        // Total voting power is the deposited supply at the snapshot ledger
+        let total_power = VotingPowerLedger::get_total_at(env, ledger);
 
         // Store the total voting power
         let key_bytes = Bytes::from_slice(env, b"VOTE_POW_");
@@ -62,32 +57,43 @@ impl WeightCalculator {
         Ok(())
     }
 
+    /// Get the snapshot recorded for a proposal
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `Option<WeightSnapshot>` - The snapshot if one was taken
+    pub fn get_snapshot(env: &Env, proposal_id: u32) -> Option<WeightSnapshot> {
+        let key = Self::get_snapshot_key(env, proposal_id);
+        env.storage().instance().get(&key)
+    }
+
     /// Get the voting weight of an address for a specific proposal
-    /// Considers token balance and delegated voting power
+    /// Considers deposited balances and delegations as of the proposal snapshot.
+    /// Voting directly overrides the voter's own delegations, so their full deposit
+    /// counts, and delegators who have already voted are left out of delegated power
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `voter` - The address of the voter
-    /// * `proposal_id` - The ID of the proposal
+    /// * `proposal` - The proposal being voted on
     ///
     /// # Returns
     /// * `Result<i128, Error>` - The voting weight or an error
-    pub fn get_weight(env: &Env, voter: &Address, proposal_id: u32) -> Result<i128, Error> {
-        // Check if this voter has delegated their voting power
-        if let Some(_delegatee) = Self::get_delegation(env, voter) {
-            // If they delegated, they have zero voting power themselves
-            return Ok(0);
-        }
-
+    pub fn get_weight(env: &Env, voter: &Address, proposal: &Proposal) -> Result<i128, Error> {
         // Get the requirements to enforce max voting power cap
         let requirements: ProposalRequirements =
             env.storage().instance().get(&REQUIREMENTS_KEY).unwrap();
 
-        // Calculate base weight from token balance
-        let base_weight = Self::get_base_weight(env, voter);
+        // Calculate base weight from the deposit checkpointed at the snapshot
+        let ledger = Self::get_snapshot_ledger(env, proposal.id);
+        let base_weight = Self::get_base_weight(env, voter, ledger);
 
         // Add any delegated voting power from other addresses
-        let delegated_weight = Self::get_delegated_weight(env, voter, proposal_id);
+        let delegated_weight =
+            Self::get_delegated_weight(env, voter, proposal, ledger, MAX_DELEGATION_DEPTH);
 
         // Total weight is base + delegated, capped by max_voting_power
         let total_weight = base_weight + delegated_weight;
@@ -95,44 +101,124 @@ impl WeightCalculator {
         Ok(total_weight.min(requirements.max_voting_power))
     }
 
-    /// Get the base voting weight from token balance
+    /// Get the weight a vote on a proposal carries under its voting configuration
+    /// One-address-one-vote proposals give every voter a weight of one. Quadratic
+    /// proposals charge the square of the votes cast, so a deposit buys its square
+    /// root in votes and delegated power is not pooled, keeping one verified identity
+    /// per voter. Linear and conviction proposals use the token weight
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `voter` - The address of the voter
+    /// * `proposal` - The proposal being voted on
     ///
     /// # Returns
-    /// * `i128` - The base voting weight
-    pub fn get_base_weight(env: &Env, voter: &Address) -> i128 {
-        // Get the governance token
-        let token: Address = env.storage().instance().get(&TOKEN_KEY).unwrap();
-        let token_client = TokenClient::new(env, &token);
+    /// * `Result<i128, Error>` - The vote weight or an error
+    pub fn get_vote_weight(env: &Env, voter: &Address, proposal: &Proposal) -> Result<i128, Error> {
+        let config = &proposal.voting_config;
+        if config.one_address_one_vote {
+            return Ok(1);
+        }
+
+        match config.weighting {
+            WeightingMode::Linear | WeightingMode::Conviction => {
+                Self::get_weight(env, voter, proposal)
+            }
+            WeightingMode::Quadratic => {
+                let requirements: ProposalRequirements =
+                    env.storage().instance().get(&REQUIREMENTS_KEY).unwrap();
+                let ledger = Self::get_snapshot_ledger(env, proposal.id);
+                let credits = Self::get_base_weight(env, voter, ledger)
+                    .min(requirements.max_voting_power);
+
+                Ok(Self::integer_sqrt(credits))
+            }
+        }
+    }
 
-        // Return token balance as voting power
-        token_client.balance(voter)
+    /// Get the base voting weight from deposited tokens
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `ledger` - The ledger sequence to read the deposit at
+    ///
+    /// # Returns
+    /// * `i128` - The base voting weight
+    pub fn get_base_weight(env: &Env, voter: &Address, ledger: u32) -> i128 {
+        VotingPowerLedger::get_balance_at(env, voter, ledger)
     }
 
     /// Get delegated voting weight from other addresses
+    /// Power is passed on through delegates who have not voted, up to `depth` hops
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `delegatee` - The address that received delegations
-    /// * `_proposal_id` - The ID of the proposal
+    /// * `proposal` - The proposal being voted on
+    /// * `ledger` - The snapshot ledger delegations and balances are read at
+    /// * `depth` - The number of delegation hops still allowed
     ///
     /// # Returns
     /// * `i128` - The delegated voting weight
-    pub fn get_delegated_weight(env: &Env, delegatee: &Address, _proposal_id: u32) -> i128 {
-        // Get all delegators for this delegatee
-        let delegators = Self::get_delegators(env, &delegatee);
+    pub fn get_delegated_weight(
+        env: &Env,
+        delegatee: &Address,
+        proposal: &Proposal,
+        ledger: u32,
+        depth: u32,
+    ) -> i128 {
+        if depth == 0 {
+            return 0;
+        }
 
-        // Sum up the base weights of all delegators
-        delegators
-            .iter()
-            .map(|delegator| Self::get_base_weight(env, &delegator))
-            .sum()
+        let mut total = 0;
+        for delegator in Self::get_delegators_at(env, delegatee, ledger).iter() {
+            // Delegators who vote directly keep their power for this proposal
+            if VotingSystem::has_voted(env, proposal.id, &delegator) {
+                continue;
+            }
+
+            let delegations =
+                Self::get_scoped_delegations(env, &delegator, &proposal.proposal_type, ledger);
+            let share = delegations
+                .iter()
+                .filter(|delegation| delegation.delegatee == *delegatee)
+                .map(|delegation| delegation.share)
+                .sum::<u32>();
+            if share == 0 {
+                continue;
+            }
+
+            // The delegator passes on their deposit and whatever was delegated to them
+            let power = Self::get_base_weight(env, &delegator, ledger)
+                + Self::get_delegated_weight(env, &delegator, proposal, ledger, depth - 1);
+            total += power * share as i128 / FULL_DELEGATION_SHARE as i128;
+        }
+
+        total
+    }
+
+    /// Get the delegates that have already voted and receive power from a voter
+    /// Their votes must be reweighted when the voter votes directly
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `voter` - The address of the voter
+    /// * `proposal` - The proposal being voted on
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The delegates whose vote weight included the voter's power
+    pub fn get_voted_delegates(env: &Env, voter: &Address, proposal: &Proposal) -> Vec<Address> {
+        let ledger = Self::get_snapshot_ledger(env, proposal.id);
+        let mut voted = vec![env];
+        let depth = MAX_DELEGATION_DEPTH;
+        Self::collect_voted_delegates(env, voter, proposal, ledger, depth, &mut voted);
+        voted
     }
 
-    /// Delegate voting power to another address
+    /// Delegate all voting power to another address for every proposal type
+    /// Replaces any existing delegations that apply to all proposal types
     ///
     /// # Arguments
     /// * `env` - The environment object
@@ -145,63 +231,451 @@ impl WeightCalculator {
         // Require authorization from the delegator
         delegator.require_auth();
 
+        let mut delegations = Self::get_delegations(env, delegator);
+        delegations = Self::without_scope(env, &delegations, &DelegationScope::All, None);
+
+        let scope = DelegationScope::All;
+        Self::store_delegation(env, delegator, delegatee, FULL_DELEGATION_SHARE, scope, delegations)
+    }
+
+    /// Delegate a share of voting power to another address
+    /// Shares apply to one proposal type or to every type that has no delegations of
+    /// its own. Setting a share for an existing delegatee and scope replaces it
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address delegating their voting power
+    /// * `delegatee` - The address receiving the delegated voting power
+    /// * `share` - The share of voting power in basis points
+    /// * `scope` - The proposals the share applies to
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn delegate_share(
+        env: &Env,
+        delegator: &Address,
+        delegatee: &Address,
+        share: u32,
+        scope: DelegationScope,
+    ) -> Result<(), Error> {
+        // Require authorization from the delegator
+        delegator.require_auth();
+
+        let delegations = Self::get_delegations(env, delegator);
+        let delegations = Self::without_scope(env, &delegations, &scope, Some(delegatee));
+
+        Self::store_delegation(env, delegator, delegatee, share, scope, delegations)
+    }
+
+    /// Revoke a delegation
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address that delegated
+    /// * `delegatee` - The address that received the delegation
+    /// * `scope` - The proposals the delegation applies to
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    pub fn revoke(
+        env: &Env,
+        delegator: &Address,
+        delegatee: &Address,
+        scope: DelegationScope,
+    ) -> Result<(), Error> {
+        // Require authorization from the delegator
+        delegator.require_auth();
+
+        let delegations = Self::get_delegations(env, delegator);
+        let remaining = Self::without_scope(env, &delegations, &scope, Some(delegatee));
+        if remaining.len() == delegations.len() {
+            return Err(Error::InvalidDelegation);
+        }
+        Self::write_delegations(env, delegator, remaining);
+
+        // Emit an event for the revocation
+        env.events().publish(
+            (symbol_short!("vote"), symbol_short!("revoked")),
+            (delegator, delegatee, scope),
+        );
+
+        Ok(())
+    }
+
+    /// Get the current delegations of an address
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address that might have delegated
+    ///
+    /// # Returns
+    /// * `Vec<Delegation>` - The delegations currently in effect
+    pub fn get_delegations(env: &Env, delegator: &Address) -> Vec<Delegation> {
+        let checkpoints = Self::get_delegation_checkpoints(env, delegator);
+        match checkpoints.last() {
+            Some(checkpoint) => checkpoint.delegations,
+            None => vec![env],
+        }
+    }
+
+    /// Get the delegations of an address as of a ledger sequence
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address that might have delegated
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `Vec<Delegation>` - The delegations recorded at or before the ledger
+    pub fn get_delegations_at(env: &Env, delegator: &Address, ledger: u32) -> Vec<Delegation> {
+        let checkpoints = Self::get_delegation_checkpoints(env, delegator);
+        let mut low = 0u32;
+        let mut high = checkpoints.len();
+
+        // Find the first checkpoint recorded after the requested ledger
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get_unchecked(mid).ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        if low == 0 {
+            vec![env]
+        } else {
+            checkpoints.get_unchecked(low - 1).delegations
+        }
+    }
+
+    /// Get the addresses currently delegating to a specific delegatee
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegatee` - The address that received delegations
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - List of delegators
+    pub fn get_delegators(env: &Env, delegatee: &Address) -> Vec<Address> {
+        let checkpoints = Self::get_delegators_checkpoints(env, delegatee);
+        match checkpoints.last() {
+            Some(checkpoint) => checkpoint.delegators,
+            None => vec![env],
+        }
+    }
+
+    /// Get the addresses delegating to a specific delegatee as of a ledger sequence
+    /// Revoked delegators drop out of later lists but still resolve earlier snapshots
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegatee` - The address that received delegations
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The delegators recorded at or before the ledger
+    pub fn get_delegators_at(env: &Env, delegatee: &Address, ledger: u32) -> Vec<Address> {
+        let checkpoints = Self::get_delegators_checkpoints(env, delegatee);
+        let mut low = 0u32;
+        let mut high = checkpoints.len();
+
+        // Find the first checkpoint recorded after the requested ledger
+        while low < high {
+            let mid = (low + high) / 2;
+            if checkpoints.get_unchecked(mid).ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        if low == 0 {
+            vec![env]
+        } else {
+            checkpoints.get_unchecked(low - 1).delegators
+        }
+    }
+
+    /// Get the delegations of an address that apply to a proposal type
+    /// Delegations for the specific type take precedence over delegations for all types
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address that might have delegated
+    /// * `proposal_type` - The type of the proposal
+    /// * `ledger` - The ledger sequence to look up
+    ///
+    /// # Returns
+    /// * `Vec<Delegation>` - The applicable delegations
+    fn get_scoped_delegations(
+        env: &Env,
+        delegator: &Address,
+        proposal_type: &ProposalType,
+        ledger: u32,
+    ) -> Vec<Delegation> {
+        let mut typed = vec![env];
+        let mut general = vec![env];
+        for delegation in Self::get_delegations_at(env, delegator, ledger).iter() {
+            match &delegation.scope {
+                DelegationScope::Type(scope) if scope == proposal_type => {
+                    typed.push_back(delegation)
+                }
+                DelegationScope::All => general.push_back(delegation),
+                _ => {}
+            }
+        }
+
+        if typed.is_empty() {
+            general
+        } else {
+            typed
+        }
+    }
+
+    /// Walk the delegation chain of a voter and collect delegates that have voted
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address whose delegations are followed
+    /// * `proposal` - The proposal being voted on
+    /// * `ledger` - The snapshot ledger delegations are read at
+    /// * `depth` - The number of delegation hops still allowed
+    /// * `voted` - The delegates collected so far
+    fn collect_voted_delegates(
+        env: &Env,
+        delegator: &Address,
+        proposal: &Proposal,
+        ledger: u32,
+        depth: u32,
+        voted: &mut Vec<Address>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+
+        for delegation in
+            Self::get_scoped_delegations(env, delegator, &proposal.proposal_type, ledger).iter()
+        {
+            let delegatee = delegation.delegatee;
+            if VotingSystem::has_voted(env, proposal.id, &delegatee) {
+                if !voted.contains(&delegatee) {
+                    voted.push_back(delegatee);
+                }
+            } else {
+                Self::collect_voted_delegates(env, &delegatee, proposal, ledger, depth - 1, voted);
+            }
+        }
+    }
+
+    /// Validate and store a new delegation alongside the delegator's other delegations
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address delegating their voting power
+    /// * `delegatee` - The address receiving the delegated voting power
+    /// * `share` - The share of voting power in basis points
+    /// * `scope` - The proposals the share applies to
+    /// * `delegations` - The delegator's other delegations to keep
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success or an error
+    fn store_delegation(
+        env: &Env,
+        delegator: &Address,
+        delegatee: &Address,
+        share: u32,
+        scope: DelegationScope,
+        mut delegations: Vec<Delegation>,
+    ) -> Result<(), Error> {
         // Prevent self-delegation
         if delegator == delegatee {
             return Err(Error::SelfDelegationNotAllowed);
         }
 
-        // Prevent circular delegations
-        if let Some(current_delegatee) = Self::get_delegation(env, delegatee) {
-            if current_delegatee == *delegator {
-                return Err(Error::InvalidDelegation);
-            }
+        if share == 0 || share > FULL_DELEGATION_SHARE {
+            return Err(Error::InvalidDelegationShare);
         }
 
-        // Store the delegation relationship
-        let key = Self::get_delegator_key(env, delegator);
-        env.storage().instance().set(&key, delegatee);
+        // Only deposited power can be delegated
+        if VotingPowerLedger::get_balance(env, delegator) <= 0 {
+            return Err(Error::InsufficientDeposit);
+        }
+
+        // Prevent circular delegations within the reach of delegated power
+        if Self::reaches(env, delegatee, delegator, MAX_DELEGATION_DEPTH) {
+            return Err(Error::InvalidDelegation);
+        }
+
+        // Shares within one scope cannot add up to more than the delegator's power
+        let allocated: u32 = delegations
+            .iter()
+            .filter(|delegation| delegation.scope == scope)
+            .map(|delegation| delegation.share)
+            .sum();
+        if allocated + share > FULL_DELEGATION_SHARE {
+            return Err(Error::InvalidDelegationShare);
+        }
+
+        delegations.push_back(Delegation {
+            delegatee: delegatee.clone(),
+            share,
+            scope: scope.clone(),
+        });
+        if delegations.len() > MAX_DELEGATES {
+            return Err(Error::TooManyDelegates);
+        }
 
         // Update the delegatee's list of delegators
-        Self::add_to_delegators_list(env, delegatee, delegator);
+        Self::add_to_delegators_list(env, delegatee, delegator)?;
+
+        // Store the delegation relationship
+        Self::write_delegations(env, delegator, delegations);
 
         // Emit an event for the delegation
         env.events().publish(
             (symbol_short!("vote"), symbol_short!("delegated")),
-            (delegator, delegatee),
+            (delegator, delegatee, share, scope),
         );
 
         Ok(())
     }
 
-    /// Get the address a voter has delegated to
+    /// Check whether delegations starting at an address reach a target
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `delegator` - The address that might have delegated
+    /// * `from` - The address to start from
+    /// * `target` - The address to look for
+    /// * `depth` - The number of delegation hops to follow
     ///
     /// # Returns
-    /// * `Option<Address>` - The delegatee if delegation exists
-    pub fn get_delegation(env: &Env, delegator: &Address) -> Option<Address> {
+    /// * `bool` - True if the target is reachable
+    fn reaches(env: &Env, from: &Address, target: &Address, depth: u32) -> bool {
+        if depth == 0 {
+            return false;
+        }
+
+        Self::get_delegations(env, from).iter().any(|delegation| {
+            delegation.delegatee == *target
+                || Self::reaches(env, &delegation.delegatee, target, depth - 1)
+        })
+    }
+
+    /// Remove the delegations of one scope, optionally only those to a single delegatee
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegations` - The delegations to filter
+    /// * `scope` - The scope to remove
+    /// * `delegatee` - The delegatee to remove, or None for every delegatee in the scope
+    ///
+    /// # Returns
+    /// * `Vec<Delegation>` - The remaining delegations
+    fn without_scope(
+        env: &Env,
+        delegations: &Vec<Delegation>,
+        scope: &DelegationScope,
+        delegatee: Option<&Address>,
+    ) -> Vec<Delegation> {
+        let mut remaining = vec![env];
+        for delegation in delegations.iter() {
+            let same_scope = delegation.scope == *scope;
+            let same_delegatee = delegatee.is_none_or(|d| delegation.delegatee == *d);
+            if !(same_scope && same_delegatee) {
+                remaining.push_back(delegation);
+            }
+        }
+        remaining
+    }
+
+    /// Record a delegator's delegations for the current ledger
+    /// Multiple changes within one ledger collapse into a single checkpoint, and
+    /// delegatees the delegator no longer delegates to drop it from their list
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegator` - The address of the delegator
+    /// * `delegations` - The delegations now in effect
+    fn write_delegations(env: &Env, delegator: &Address, delegations: Vec<Delegation>) {
+        for previous in Self::get_delegations(env, delegator).iter() {
+            let kept = delegations
+                .iter()
+                .any(|delegation| delegation.delegatee == previous.delegatee);
+            if !kept {
+                Self::remove_from_delegators_list(env, &previous.delegatee, delegator);
+            }
+        }
+
+        let ledger = env.ledger().sequence();
+        let mut checkpoints = Self::get_delegation_checkpoints(env, delegator);
+
+        let checkpoint = DelegationCheckpoint { ledger, delegations };
+        match checkpoints.last() {
+            Some(last) if last.ledger == ledger => {
+                checkpoints.set(checkpoints.len() - 1, checkpoint);
+            }
+            _ => checkpoints.push_back(checkpoint),
+        }
+
         let key = Self::get_delegator_key(env, delegator);
-        env.storage().instance().get(&key)
+        env.storage().persistent().set(&key, &checkpoints);
     }
 
-    /// Get all addresses that delegated to a specific delegatee
+    /// Get the delegation history of a delegator
     ///
     /// # Arguments
     /// * `env` - The environment object
-    /// * `delegatee` - The address that received delegations
+    /// * `delegator` - The address of the delegator
     ///
     /// # Returns
-    /// * `Vec<Address>` - List of delegators
-    pub fn get_delegators(env: &Env, delegatee: &Address) -> Vec<Address> {
-        let key = Self::get_delegatees_key(env, delegatee);
+    /// * `Vec<DelegationCheckpoint>` - The checkpoints ordered by ledger sequence
+    fn get_delegation_checkpoints(env: &Env, delegator: &Address) -> Vec<DelegationCheckpoint> {
+        let key = Self::get_delegator_key(env, delegator);
         env.storage()
-            .instance()
+            .persistent()
             .get(&key)
             .unwrap_or_else(|| vec![env])
     }
 
+    /// Get the ledger sequence weights are read at for a proposal
+    /// Falls back to the last closed ledger when no snapshot has been taken yet
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `proposal_id` - The ID of the proposal
+    ///
+    /// # Returns
+    /// * `u32` - The ledger sequence
+    fn get_snapshot_ledger(env: &Env, proposal_id: u32) -> u32 {
+        match Self::get_snapshot(env, proposal_id) {
+            Some(snapshot) => snapshot.ledger,
+            None => env.ledger().sequence().saturating_sub(1),
+        }
+    }
+
+    /// Compute the integer square root of a non-negative value
+    ///
+    /// # Arguments
+    /// * `value` - The value to take the square root of
+    ///
+    /// # Returns
+    /// * `i128` - The largest integer whose square does not exceed the value
+    fn integer_sqrt(value: i128) -> i128 {
+        if value <= 0 {
+            return 0;
+        }
+
+        // Newton's method converges from above on the floor of the root
+        let mut root = value;
+        let mut next = (root + 1) / 2;
+        while next < root {
+            root = next;
+            next = (root + value / root) / 2;
+        }
+        root
+    }
+
     /// Generate a storage key for a proposal snapshot
     ///
     /// # Arguments
@@ -215,7 +689,7 @@ impl WeightCalculator {
         get_key_str(env, key_bytes, proposal_id)
     }
 
-    /// Generate a storage key for a delegator's delegation
+    /// Generate a storage key for a delegator's delegation history
     ///
     /// # Arguments
     /// * `env` - The environment object
@@ -224,7 +698,7 @@ impl WeightCalculator {
     /// # Returns
     /// * `Symbol` - The storage key
     fn get_delegator_key(env: &Env, delegator: &Address) -> Symbol {
-        // Generate a unique key for the delegator's delegation
+        // Generate a unique key for the delegator's delegations
         let key_bytes = Bytes::from_slice(env, b"DELGOR_");
         get_governance_op_key(env, key_bytes.clone(), 0, delegator)
     }
@@ -243,52 +717,86 @@ impl WeightCalculator {
         get_governance_op_key(env, key_bytes.clone(), 0, delegatee)
     }
 
+    /// Get the history of a delegatee's list of delegators
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegatee` - The address of the delegatee
+    ///
+    /// # Returns
+    /// * `Vec<DelegatorsCheckpoint>` - The checkpoints ordered by ledger sequence
+    fn get_delegators_checkpoints(env: &Env, delegatee: &Address) -> Vec<DelegatorsCheckpoint> {
+        let key = Self::get_delegatees_key(env, delegatee);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| vec![env])
+    }
+
+    /// Record a delegatee's list of delegators for the current ledger
+    /// Multiple changes within one ledger collapse into a single checkpoint
+    ///
+    /// # Arguments
+    /// * `env` - The environment object
+    /// * `delegatee` - The address of the delegatee
+    /// * `delegators` - The delegators now in effect
+    fn write_delegators(env: &Env, delegatee: &Address, delegators: Vec<Address>) {
+        let ledger = env.ledger().sequence();
+        let mut checkpoints = Self::get_delegators_checkpoints(env, delegatee);
+
+        let checkpoint = DelegatorsCheckpoint { ledger, delegators };
+        match checkpoints.last() {
+            Some(last) if last.ledger == ledger => {
+                checkpoints.set(checkpoints.len() - 1, checkpoint);
+            }
+            _ => checkpoints.push_back(checkpoint),
+        }
+
+        let key = Self::get_delegatees_key(env, delegatee);
+        env.storage().persistent().set(&key, &checkpoints);
+    }
+
     /// Add a delegator to a delegatee's list of delegators
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `delegatee` - The address receiving delegation
     /// * `delegator` - The address delegating voting power
-    fn add_to_delegators_list(env: &Env, delegatee: &Address, delegator: &Address) {
-        let key = Self::get_delegatees_key(env, delegatee);
-        let mut list: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&key)
-            .unwrap_or_else(|| vec![env]);
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - Success, or an error if the delegatee has too many delegators
+    fn add_to_delegators_list(
+        env: &Env,
+        delegatee: &Address,
+        delegator: &Address,
+    ) -> Result<(), Error> {
+        let mut list = Self::get_delegators(env, delegatee);
 
         // Add the delegator to the list if not already present
         if !list.iter().any(|a| a == *delegator) {
+            if list.len() >= MAX_DELEGATORS {
+                return Err(Error::TooManyDelegates);
+            }
+
             list.push_back(delegator.clone());
-            env.storage().instance().set(&key, &list);
+            Self::write_delegators(env, delegatee, list);
         }
+
+        Ok(())
     }
 
     /// Remove a delegator from a delegatee's list of delegators
-    /// Useful when revoking delegation (currently not used)
     ///
     /// # Arguments
     /// * `env` - The environment object
     /// * `delegatee` - The address that received delegation
-    /// * `delegator` - The address that was delegating
-    #[allow(dead_code)]
+    /// * `delegator` - The address that no longer delegates to it
     fn remove_from_delegators_list(env: &Env, delegatee: &Address, delegator: &Address) {
-        let key = Self::get_delegatees_key(env, delegatee);
-        let list: Vec<Address> = env
-            .storage()
-            .instance()
-            .get(&key)
-            .unwrap_or_else(|| vec![env]);
+        let mut list = Self::get_delegators(env, delegatee);
 
-        // Create a new list excluding the specified delegator
-        let mut new_list = Vec::new(&env);
-        list.iter().for_each(|a| {
-            if a != *delegator {
-                new_list.push_back(a.clone());
-            }
-        });
-
-        // Update the storage with the new list
-        env.storage().instance().set(&key, &new_list);
+        if let Some(index) = list.first_index_of(delegator) {
+            list.remove(index);
+            Self::write_delegators(env, delegatee, list);
+        }
     }
 }
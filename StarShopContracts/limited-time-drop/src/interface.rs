@@ -2,8 +2,8 @@
 //!
 //! This contract manages limited-time drops of products with access control and purchase tracking.
 
-use crate::types::{Drop, DropStatus, Error, PurchaseRecord, UserLevel};
-use soroban_sdk::{Address, String, Vec};
+use crate::types::{Drop, DropStatus, Error, PauseScope, PurchaseRecord, UserLevel};
+use soroban_sdk::{Address, String, Symbol, Vec};
 
 /// Contract Interface
 pub trait LimitedTimeDropContract {
@@ -145,4 +145,44 @@ pub trait LimitedTimeDropContract {
     /// * `Unauthorized` - If the caller is not the admin
     /// * `InvalidUserLevel` - If the level is invalid
     fn set_user_level(admin: Address, user: Address, level: UserLevel) -> Result<(), Error>;
+
+    /// Set the address allowed to pause the contract besides the admin
+    ///
+    /// # Arguments
+    /// * `caller` - The admin, or the current pause authority once one is set
+    /// * `authority` - The pause authority, usually the governance contract
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is not the admin, or not the current pause authority
+    fn set_pause_authority(caller: Address, authority: Address) -> Result<(), Error>;
+
+    /// Pause the contract or a single function until a timestamp
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or pause authority
+    /// * `scope` - The whole contract or a single function
+    /// * `until` - Unix timestamp when the pause lifts itself
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is neither the admin nor the pause authority,
+    ///   or the scope is under a pause set by the pause authority
+    /// * `InvalidTime` - If `until` is not in the future or beyond `MAX_PAUSE_DURATION`
+    fn pause(caller: Address, scope: PauseScope, until: u64) -> Result<(), Error>;
+
+    /// Lift a pause before it expires
+    ///
+    /// # Arguments
+    /// * `caller` - The admin or pause authority
+    /// * `scope` - The scope that was paused
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the caller is neither the admin nor the pause authority,
+    ///   or the pause was set by the pause authority
+    fn unpause(caller: Address, scope: PauseScope) -> Result<(), Error>;
+
+    /// Check if a function is paused on its own or by a contract-wide pause
+    ///
+    /// # Arguments
+    /// * `function` - The function name
+    fn is_paused(function: Symbol) -> bool;
 }
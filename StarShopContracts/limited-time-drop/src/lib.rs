@@ -7,13 +7,15 @@ use soroban_sdk::{
 mod access;
 mod drop;
 mod interface;
+mod pause;
 mod tracking;
 mod types;
 
 use crate::access::AccessManager;
 use crate::drop::DropManager;
+use crate::pause::PauseManager;
 use crate::tracking::TrackingManager;
-use crate::types::{DataKey, Drop, DropStatus, Error, PurchaseRecord, UserLevel};
+use crate::types::{DataKey, Drop, DropStatus, Error, PauseScope, PurchaseRecord, UserLevel};
 
 #[contract]
 pub struct LimitedTimeDropContract;
@@ -55,6 +57,7 @@ impl LimitedTimeDropContract {
     ) -> Result<u32, Error> {
         #[cfg(not(test))]
         creator.require_auth();
+        PauseManager::verify_not_paused(&env, "create_drop")?;
         DropManager::create_drop(
             &env,
             creator,
@@ -73,6 +76,7 @@ impl LimitedTimeDropContract {
     pub fn purchase(env: Env, buyer: Address, drop_id: u32, quantity: u32) -> Result<(), Error> {
         #[cfg(not(test))]
         buyer.require_auth();
+        PauseManager::verify_not_paused(&env, "purchase")?;
         DropManager::purchase(&env, buyer, drop_id, quantity)
     }
 
@@ -137,6 +141,35 @@ impl LimitedTimeDropContract {
         admin.require_auth();
         DropManager::update_status(&env, &admin, drop_id, status)
     }
+
+    /// Set the address (e.g. governance) allowed to pause the contract
+    /// (Admin first, then only the current pause authority)
+    pub fn set_pause_authority(env: Env, caller: Address, authority: Address) -> Result<(), Error> {
+        #[cfg(not(test))]
+        caller.require_auth();
+        PauseManager::set_pause_authority(&env, &caller, &authority)
+    }
+
+    /// Pause the contract or a single function until `until`, for at most
+    /// `MAX_PAUSE_DURATION` (Admin or pause authority)
+    pub fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) -> Result<(), Error> {
+        #[cfg(not(test))]
+        caller.require_auth();
+        PauseManager::pause(&env, &caller, scope, until)
+    }
+
+    /// Lift a pause before it expires (Admin or pause authority; pauses set
+    /// by the pause authority can only be lifted by it)
+    pub fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), Error> {
+        #[cfg(not(test))]
+        caller.require_auth();
+        PauseManager::unpause(&env, &caller, scope)
+    }
+
+    /// Check if a function is currently paused
+    pub fn is_paused(env: Env, function: Symbol) -> bool {
+        PauseManager::is_paused(&env, &function)
+    }
 }
 
 #[cfg(test)]
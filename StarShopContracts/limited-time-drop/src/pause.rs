@@ -0,0 +1,132 @@
+use crate::access::AccessManager;
+use crate::types::{DataKey, Error, PauseScope};
+use soroban_sdk::{Address, Env, Symbol};
+
+/// Longest pause that may be requested, in seconds
+pub const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
+
+pub struct PauseManager;
+
+impl PauseManager {
+    /// Appoint a second pauser next to the admin, usually the governance contract.
+    /// Only the admin may make the first appointment, and from then on only the
+    /// sitting authority may name its successor.
+    pub fn set_pause_authority(
+        env: &Env,
+        caller: &Address,
+        authority: &Address,
+    ) -> Result<(), Error> {
+        match Self::get_pause_authority(env) {
+            Some(current) if current != *caller => return Err(Error::Unauthorized),
+            Some(_) => {}
+            None => AccessManager::verify_admin(env, caller)?,
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PauseAuthority, authority);
+
+        env.events().publish(
+            (Symbol::new(env, "pause_authority_set"),),
+            (authority.clone(),),
+        );
+        Ok(())
+    }
+
+    /// Pause the contract or a single function until the given timestamp
+    pub fn pause(env: &Env, caller: &Address, scope: PauseScope, until: u64) -> Result<(), Error> {
+        Self::verify_pauser(env, caller)?;
+
+        // Drops reopen automatically at `until`; a longer halt needs a fresh pause
+        let now = env.ledger().timestamp();
+        if until <= now || until - now > MAX_PAUSE_DURATION {
+            return Err(Error::InvalidTime);
+        }
+
+        // A pause placed by the authority cannot be re-timed by the admin
+        let by_authority = Self::get_pause_authority(env).as_ref() == Some(caller);
+        if !by_authority && Self::is_authority_pause(env, &scope) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Paused(scope.clone()), &until);
+        env.storage()
+            .instance()
+            .set(&DataKey::PausedByAuthority(scope.clone()), &by_authority);
+
+        env.events()
+            .publish((Symbol::new(env, "paused"),), (scope, until));
+        Ok(())
+    }
+
+    /// Lift a pause before it expires
+    pub fn unpause(env: &Env, caller: &Address, scope: PauseScope) -> Result<(), Error> {
+        Self::verify_pauser(env, caller)?;
+
+        // nor lifted by the admin before it runs out
+        if Self::get_pause_authority(env).as_ref() != Some(caller)
+            && Self::is_authority_pause(env, &scope)
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::Paused(scope.clone()));
+        env.storage()
+            .instance()
+            .remove(&DataKey::PausedByAuthority(scope.clone()));
+
+        env.events()
+            .publish((Symbol::new(env, "unpaused"),), (scope,));
+        Ok(())
+    }
+
+    /// Check if a function is paused on its own or by a contract-wide pause
+    pub fn is_paused(env: &Env, function: &Symbol) -> bool {
+        Self::is_scope_paused(env, PauseScope::Contract)
+            || Self::is_scope_paused(env, PauseScope::Function(function.clone()))
+    }
+
+    /// Verify a function is not paused
+    pub fn verify_not_paused(env: &Env, function: &str) -> Result<(), Error> {
+        if Self::is_paused(env, &Symbol::new(env, function)) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Check if a pause scope is still in effect
+    fn is_scope_paused(env: &Env, scope: PauseScope) -> bool {
+        let until: Option<u64> = env.storage().instance().get(&DataKey::Paused(scope));
+        match until {
+            Some(until) => env.ledger().timestamp() < until,
+            None => false,
+        }
+    }
+
+    /// Check if a pause scope is in effect and was set by the pause authority
+    fn is_authority_pause(env: &Env, scope: &PauseScope) -> bool {
+        let by_authority: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::PausedByAuthority(scope.clone()))
+            .unwrap_or(false);
+        by_authority && Self::is_scope_paused(env, scope.clone())
+    }
+
+    /// Get the current pause authority, if one has been set
+    fn get_pause_authority(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PauseAuthority)
+    }
+
+    /// Verify the caller is the admin or the pause authority
+    fn verify_pauser(env: &Env, caller: &Address) -> Result<(), Error> {
+        if Self::get_pause_authority(env).as_ref() == Some(caller) {
+            return Ok(());
+        }
+        AccessManager::verify_admin(env, caller)
+    }
+}
@@ -1470,3 +1470,157 @@ fn test_admin_verification_consistency() {
         );
     });
 }
+
+// Test for emergency pauses by the pause authority
+#[test]
+fn test_emergency_pause_by_authority() {
+    let env = test_env();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let contract_id = deploy_contract(&env);
+
+    env.as_contract(&contract_id, || {
+        LimitedTimeDropContract::initialize(env.clone(), admin.clone()).unwrap();
+        LimitedTimeDropContract::set_pause_authority(
+            env.clone(),
+            admin.clone(),
+            governance.clone(),
+        )
+        .unwrap();
+
+        // Only the admin or the pause authority can pause
+        let purchase = Symbol::new(&env, "purchase");
+        let scope = PauseScope::Function(purchase.clone());
+        let result = LimitedTimeDropContract::pause(
+            env.clone(),
+            creator.clone(),
+            scope.clone(),
+            1_725_000_100,
+        );
+        assert_eq!(result, Err(Error::Unauthorized));
+
+        // Pausing purchases blocks them but leaves drop creation available
+        LimitedTimeDropContract::pause(env.clone(), governance.clone(), scope, 1_725_000_100)
+            .unwrap();
+        assert!(LimitedTimeDropContract::is_paused(
+            env.clone(),
+            purchase.clone()
+        ));
+        let result = LimitedTimeDropContract::purchase(env.clone(), creator.clone(), 1, 1);
+        assert_eq!(result, Err(Error::ContractPaused));
+
+        // A contract-wide pause covers drop creation too
+        LimitedTimeDropContract::pause(
+            env.clone(),
+            governance.clone(),
+            PauseScope::Contract,
+            1_725_000_050,
+        )
+        .unwrap();
+        let result = LimitedTimeDropContract::create_drop(
+            env.clone(),
+            creator.clone(),
+            String::from_str(&env, "Test Drop"),
+            42,
+            100,
+            1_725_000_100,
+            1_725_001_000,
+            500,
+            2,
+            String::from_str(&env, "ipfs://image"),
+        );
+        assert_eq!(result, Err(Error::ContractPaused));
+
+        // The contract-wide pause can be lifted early by the authority that set it
+        LimitedTimeDropContract::unpause(env.clone(), governance.clone(), PauseScope::Contract)
+            .unwrap();
+        assert!(!LimitedTimeDropContract::is_paused(
+            env.clone(),
+            Symbol::new(&env, "create_drop")
+        ));
+    });
+
+    // The function pause lifts itself once it expires
+    env.ledger().with_mut(|ledger| {
+        ledger.timestamp = 1_725_000_100;
+    });
+    env.as_contract(&contract_id, || {
+        let purchase = Symbol::new(&env, "purchase");
+        assert!(!LimitedTimeDropContract::is_paused(env.clone(), purchase));
+    });
+}
+
+#[test]
+fn test_pause_authority_keeps_control_of_its_pauses() {
+    let env = test_env();
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let new_governance = Address::generate(&env);
+    let contract_id = deploy_contract(&env);
+
+    env.as_contract(&contract_id, || {
+        LimitedTimeDropContract::initialize(env.clone(), admin.clone()).unwrap();
+        LimitedTimeDropContract::set_pause_authority(
+            env.clone(),
+            admin.clone(),
+            governance.clone(),
+        )
+        .unwrap();
+
+        // Once set, the admin can no longer replace the pause authority
+        let result =
+            LimitedTimeDropContract::set_pause_authority(env.clone(), admin.clone(), admin.clone());
+        assert_eq!(result, Err(Error::Unauthorized));
+
+        // Pauses are capped at the maximum duration
+        let now = env.ledger().timestamp();
+        let result = LimitedTimeDropContract::pause(
+            env.clone(),
+            governance.clone(),
+            PauseScope::Contract,
+            now + pause::MAX_PAUSE_DURATION + 1,
+        );
+        assert_eq!(result, Err(Error::InvalidTime));
+
+        // The admin cannot lift or shorten a pause set by the authority
+        LimitedTimeDropContract::pause(
+            env.clone(),
+            governance.clone(),
+            PauseScope::Contract,
+            now + pause::MAX_PAUSE_DURATION,
+        )
+        .unwrap();
+        let result =
+            LimitedTimeDropContract::unpause(env.clone(), admin.clone(), PauseScope::Contract);
+        assert_eq!(result, Err(Error::Unauthorized));
+        let result = LimitedTimeDropContract::pause(
+            env.clone(),
+            admin.clone(),
+            PauseScope::Contract,
+            now + 1,
+        );
+        assert_eq!(result, Err(Error::Unauthorized));
+
+        // The authority can lift its own pause and hand over to a successor
+        LimitedTimeDropContract::unpause(env.clone(), governance.clone(), PauseScope::Contract)
+            .unwrap();
+        assert!(!LimitedTimeDropContract::is_paused(
+            env.clone(),
+            Symbol::new(&env, "purchase")
+        ));
+        LimitedTimeDropContract::set_pause_authority(
+            env.clone(),
+            governance.clone(),
+            new_governance.clone(),
+        )
+        .unwrap();
+        let result = LimitedTimeDropContract::pause(
+            env.clone(),
+            governance.clone(),
+            PauseScope::Contract,
+            now + 100,
+        );
+        assert_eq!(result, Err(Error::Unauthorized));
+    });
+}
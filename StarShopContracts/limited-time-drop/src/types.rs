@@ -1,4 +1,4 @@
-use soroban_sdk::{contracterror, contracttype, Address, String};
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol};
 
 /// Storage keys for contract data
 #[contracttype]
@@ -11,6 +11,17 @@ pub enum DataKey {
     DropBuyers(u32),        // Drop ID -> Vec<Buyer>
     Whitelist,              // Whitelisted addresses
     UserLevels(Address),    // User -> Level
+    PauseAuthority,         // Address allowed to pause besides the admin
+    Paused(PauseScope),     // Pause scope -> Timestamp the pause lasts until
+    PausedByAuthority(PauseScope), // Pause scope -> Whether the pause authority set it
+}
+
+/// What an emergency pause applies to
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PauseScope {
+    Contract,         // Every guarded function
+    Function(Symbol), // A single guarded function, by name
 }
 
 /// Represents a limited-time drop
@@ -83,4 +94,5 @@ pub enum Error {
     PurchaseFailed = 16,          // Purchase transaction failed
     DuplicateWhitelistEntry = 17, // Duplicate whitelisted address
     InvalidStatusTransition = 18, // Invalid drop status transition
+    ContractPaused = 19,          // Function is paused
 }
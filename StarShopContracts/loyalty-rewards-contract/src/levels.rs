@@ -1,6 +1,15 @@
 use crate::points::PointsManager;
-use crate::types::{DataKey, Error, LevelCriteria, LevelRequirements, LoyaltyLevel, UserData, TransactionType};
-use soroban_sdk::{Address, Env, Symbol};
+use crate::types::{
+    DataKey, Error, LevelCriteria, LevelRequirements, LoyaltyLevel, PointsTransaction,
+    QualifyingActivity, TierPolicy, TierStatus, TransactionType, UserData,
+};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+const DAY_IN_SECONDS: u64 = 24 * 60 * 60;
+/// Length of a qualification month; activity is bucketed per month
+const MONTH_IN_SECONDS: u64 = 30 * DAY_IN_SECONDS;
+/// Longest rolling qualification window, bounding the months read per check
+const MAX_QUALIFICATION_MONTHS: u32 = 24;
 
 pub struct LevelManager;
 
@@ -41,44 +50,250 @@ impl LevelManager {
         let mut user_data = PointsManager::get_user_data(env, user)?;
         let requirements = Self::get_level_requirements(env)?;
 
-        let new_level = Self::calculate_eligible_level(env, &user_data, &requirements);
+        let new_level = Self::calculate_eligible_level(env, user, &user_data, &requirements);
 
-        // Only update if new level is higher
+        // Only update if new level is higher; downgrades go through requalification
         if new_level > user_data.level {
-            let previous_level = user_data.level.clone();
-            user_data.level = new_level.clone();
-            user_data.level_updated_at = env.ledger().timestamp();
-
-            // Save updated user data
-            env.storage()
-                .persistent()
-                .set(&DataKey::User(user.clone()), &user_data);
-            
-            // Publish event for level change
-            env.events().publish(
-                (Symbol::new(env, "level_changed"), user.clone()),
-                ((previous_level, new_level),),
-            );
+            let mut status = Self::get_tier_status(env, user)?;
+            status.qualified_at = env.ledger().timestamp();
+            status.grace_ends_at = 0;
+            Self::save_tier_status(env, user, &status);
 
+            Self::change_level(env, user, &mut user_data, new_level);
             return Ok(true);
         }
 
         Ok(false)
     }
 
+    /// Set the rules for keeping a level
+    pub fn set_tier_policy(env: &Env, policy: TierPolicy) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if policy.qualification_months > MAX_QUALIFICATION_MONTHS {
+            return Err(Error::InvalidLevelRequirements);
+        }
+
+        env.storage().instance().set(&DataKey::TierPolicy, &policy);
+
+        Ok(())
+    }
+
+    /// Get the rules for keeping a level; by default levels use lifetime totals and never expire
+    pub fn get_tier_policy(env: &Env) -> TierPolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::TierPolicy)
+            .unwrap_or(TierPolicy {
+                qualification_months: 0,
+                requalification_days: 0,
+                grace_days: 0,
+            })
+    }
+
+    /// Run the periodic requalification check, returning whether the user was downgraded
+    /// A user who no longer qualifies gets a grace period to requalify before dropping to
+    /// the level they currently earn; an expired status match drops right away
+    pub fn requalify_level(env: &Env, user: &Address) -> Result<bool, Error> {
+        let mut user_data = PointsManager::get_user_data(env, user)?;
+        let requirements = Self::get_level_requirements(env)?;
+        let policy = Self::get_tier_policy(env);
+        let mut status = Self::get_tier_status(env, user)?;
+        let current_time = env.ledger().timestamp();
+
+        let mut match_expired = false;
+        if status.match_expires_at != 0 && current_time >= status.match_expires_at {
+            status.matched_level = LoyaltyLevel::Bronze;
+            status.match_expires_at = 0;
+            match_expired = true;
+        }
+
+        let earned_level = Self::calculate_eligible_level(env, user, &user_data, &requirements);
+        let held_level = earned_level.max(status.matched_level.clone());
+
+        let due = policy.requalification_days > 0
+            && current_time >= status.qualified_at + policy.requalification_days * DAY_IN_SECONDS;
+
+        if held_level >= user_data.level {
+            // Requalified: start a new period and leave any grace period
+            if due || status.grace_ends_at != 0 {
+                status.qualified_at = current_time;
+                status.grace_ends_at = 0;
+            }
+            Self::save_tier_status(env, user, &status);
+            return Ok(false);
+        }
+
+        if !match_expired {
+            if !due {
+                return Ok(false);
+            }
+            if status.grace_ends_at == 0 && policy.grace_days > 0 {
+                status.grace_ends_at = current_time + policy.grace_days * DAY_IN_SECONDS;
+                Self::save_tier_status(env, user, &status);
+
+                env.events().publish(
+                    (Symbol::new(env, "level_grace_started"), user.clone()),
+                    ((user_data.level.clone(), status.grace_ends_at),),
+                );
+                return Ok(false);
+            }
+            if current_time < status.grace_ends_at {
+                return Ok(false);
+            }
+        }
+
+        status.qualified_at = current_time;
+        status.grace_ends_at = 0;
+        Self::save_tier_status(env, user, &status);
+        Self::change_level(env, user, &mut user_data, held_level);
+
+        Ok(true)
+    }
+
+    /// Grant a level for a limited time, for example to match a competitor's status
+    /// The user keeps the higher of the matched level and the level they earn
+    pub fn grant_status_match(
+        env: &Env,
+        user: &Address,
+        level: LoyaltyLevel,
+        duration_days: u64,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if duration_days == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut user_data = PointsManager::get_user_data(env, user)?;
+        let mut status = Self::get_tier_status(env, user)?;
+        let current_time = env.ledger().timestamp();
+        status.matched_level = level.clone();
+        status.match_expires_at = current_time + duration_days * DAY_IN_SECONDS;
+        if level >= user_data.level {
+            status.qualified_at = current_time;
+            status.grace_ends_at = 0;
+        }
+        Self::save_tier_status(env, user, &status);
+
+        env.events().publish(
+            (Symbol::new(env, "status_matched"), user.clone()),
+            ((level.clone(), status.match_expires_at),),
+        );
+
+        if level > user_data.level {
+            Self::change_level(env, user, &mut user_data, level);
+        }
+
+        Ok(())
+    }
+
+    /// Get a user's level qualification state
+    pub fn get_tier_status(env: &Env, user: &Address) -> Result<TierStatus, Error> {
+        if let Some(status) = env.storage().persistent().get(&DataKey::TierStatus(user.clone())) {
+            return Ok(status);
+        }
+
+        // Users who never went through requalification qualified when they reached their level
+        let user_data = PointsManager::get_user_data(env, user)?;
+        Ok(TierStatus {
+            qualified_at: user_data.level_updated_at,
+            grace_ends_at: 0,
+            matched_level: LoyaltyLevel::Bronze,
+            match_expires_at: 0,
+        })
+    }
+
+    /// Get the points and purchases that count towards a user's level
+    pub fn get_qualifying_activity(env: &Env, user: &Address) -> Result<QualifyingActivity, Error> {
+        let user_data = PointsManager::get_user_data(env, user)?;
+        Ok(Self::qualifying_activity(env, user, &user_data))
+    }
+
+    /// Add a transaction to the user's qualifying activity for the month
+    pub fn record_qualifying_activity(env: &Env, user: &Address, transaction: &PointsTransaction) {
+        let is_purchase = transaction.transaction_type == TransactionType::Earned
+            && transaction.description == symbol_short!("purchase");
+        let earns_points = transaction.transaction_type == TransactionType::Earned
+            || transaction.transaction_type == TransactionType::Bonus;
+        if !earns_points {
+            return;
+        }
+
+        let key = DataKey::QualifyingActivity(user.clone(), transaction.timestamp / MONTH_IN_SECONDS);
+        let mut activity: QualifyingActivity =
+            env.storage().persistent().get(&key).unwrap_or_default();
+        activity.points += transaction.amount;
+        if is_purchase {
+            activity.purchases += 1;
+        }
+        env.storage().persistent().set(&key, &activity);
+    }
+
+    /// Sum qualifying activity over the rolling window, or use lifetime totals without one
+    fn qualifying_activity(env: &Env, user: &Address, user_data: &UserData) -> QualifyingActivity {
+        let months = Self::get_tier_policy(env).qualification_months as u64;
+        if months == 0 {
+            return QualifyingActivity {
+                points: user_data.lifetime_points,
+                purchases: crate::milestones::MilestoneManager::count_purchases(user_data),
+            };
+        }
+
+        let current_month = env.ledger().timestamp() / MONTH_IN_SECONDS;
+        let first_month = (current_month + 1).saturating_sub(months);
+        let mut total = QualifyingActivity::default();
+        for month in first_month..=current_month {
+            let activity: QualifyingActivity = env
+                .storage()
+                .persistent()
+                .get(&DataKey::QualifyingActivity(user.clone(), month))
+                .unwrap_or_default();
+            total.points += activity.points;
+            total.purchases += activity.purchases;
+        }
+        total
+    }
+
+    fn change_level(env: &Env, user: &Address, user_data: &mut UserData, new_level: LoyaltyLevel) {
+        let previous_level = user_data.level.clone();
+        user_data.level = new_level.clone();
+        user_data.level_updated_at = env.ledger().timestamp();
+
+        // Save updated user data
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(user.clone()), user_data);
+
+        // Publish event for level change
+        env.events().publish(
+            (Symbol::new(env, "level_changed"), user.clone()),
+            ((previous_level, new_level),),
+        );
+    }
+
+    fn save_tier_status(env: &Env, user: &Address, status: &TierStatus) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::TierStatus(user.clone()), status);
+    }
+
     /// Calculate the highest eligible level for a user
     fn calculate_eligible_level(
         env: &Env,
+        user: &Address,
         user_data: &UserData,
         requirements: &LevelRequirements,
     ) -> LoyaltyLevel {
+        let activity = Self::qualifying_activity(env, user, user_data);
+
         // Check Gold requirements first
-        if Self::meets_criteria(env, user_data, &requirements.gold) {
+        if Self::meets_criteria(env, user_data, &activity, &requirements.gold) {
             return LoyaltyLevel::Gold;
         }
 
         // Check Silver requirements
-        if Self::meets_criteria(env, user_data, &requirements.silver) {
+        if Self::meets_criteria(env, user_data, &activity, &requirements.silver) {
             return LoyaltyLevel::Silver;
         }
 
@@ -87,14 +302,16 @@ impl LevelManager {
     }
 
     /// Check if user meets criteria for a specific level
-    fn meets_criteria(env: &Env, user_data: &UserData, criteria: &LevelCriteria) -> bool {
-        let days_active = (env.ledger().timestamp() - user_data.join_date) / (24 * 60 * 60);
-
-        // Count purchases from transaction history
-        let purchase_count = crate::milestones::MilestoneManager::count_purchases(user_data);
+    fn meets_criteria(
+        env: &Env,
+        user_data: &UserData,
+        activity: &QualifyingActivity,
+        criteria: &LevelCriteria,
+    ) -> bool {
+        let days_active = (env.ledger().timestamp() - user_data.join_date) / DAY_IN_SECONDS;
 
-        user_data.lifetime_points >= criteria.min_points
-            && purchase_count >= criteria.min_purchases
+        activity.points >= criteria.min_points
+            && activity.purchases >= criteria.min_purchases
             && days_active >= criteria.min_days_active
     }
 
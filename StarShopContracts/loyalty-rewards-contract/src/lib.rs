@@ -1,20 +1,33 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, Env, Symbol, Vec};
+use soroban_sdk::token::TokenInterface;
+use soroban_sdk::{contract, contractimpl, Address, Env, String, Symbol, Vec};
 
 pub mod admin;
 pub mod levels;
 pub mod milestones;
+pub mod partners;
 pub mod points;
+pub mod pool;
 pub mod rewards;
 mod test;
+pub mod token;
+pub mod transfers;
 pub mod types;
 
 use admin::AdminModule;
 use levels::LevelManager;
 use milestones::MilestoneManager;
+use partners::PartnerManager;
 use points::PointsManager;
+use pool::RewardPoolManager;
 use rewards::RewardManager;
-use types::{Error, LevelRequirements, LoyaltyLevel, Milestone, Reward};
+use token::PointsToken;
+use transfers::TransferManager;
+use types::{
+    Error, LevelRequirements, LoyaltyLevel, Milestone, Partner, PartnerAccount, PointsLot,
+    PointsTransaction, QualifyingActivity, Redemption, Reward, RewardAvailability, RewardPool,
+    TierPolicy, TierStatus, TokenConfig, TransferRules,
+};
 
 /// Main contract trait defining all available functions
 pub trait LoyaltyRewardsTrait {
@@ -32,6 +45,14 @@ pub trait LoyaltyRewardsTrait {
     fn register_user(env: Env, user: Address) -> Result<(), Error>;
     fn get_points_balance(env: Env, user: Address) -> Result<i128, Error>;
     fn get_lifetime_points(env: Env, user: Address) -> Result<i128, Error>;
+    fn expire_points(env: Env, user: Address) -> Result<i128, Error>;
+    fn get_expiring_points(env: Env, user: Address, days: u64) -> Result<Vec<PointsLot>, Error>;
+    fn get_transactions(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<Vec<PointsTransaction>, Error>;
     fn record_purchase_points(
         env: Env,
         user: Address,
@@ -40,11 +61,66 @@ pub trait LoyaltyRewardsTrait {
         category: Option<Symbol>,
     ) -> Result<i128, Error>;
 
+    // Point transfers
+    fn set_transfer_rules(env: Env, rules: TransferRules) -> Result<(), Error>;
+    fn get_transfer_rules(env: Env) -> TransferRules;
+    fn transfer_points(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error>;
+    fn set_transfer_exempt(env: Env, holder: Address, exempt: bool) -> Result<(), Error>;
+    fn is_transfer_exempt(env: Env, holder: Address) -> bool;
+
+    // Token mode
+    fn set_token_config(env: Env, config: TokenConfig) -> Result<(), Error>;
+    fn get_token_config(env: Env) -> Result<TokenConfig, Error>;
+
+    // Partner merchants
+    fn register_partner(
+        env: Env,
+        partner: Address,
+        name: Symbol,
+        points_ratio: u32,
+        settlement_token: Option<Address>,
+        point_value: i128,
+    ) -> Result<(), Error>;
+    fn fund_partner_budget(env: Env, partner: Address, points: i128) -> Result<i128, Error>;
+    fn set_partner_credit_limit(
+        env: Env,
+        partner: Address,
+        credit_limit: i128,
+    ) -> Result<(), Error>;
+    fn set_partner_active(env: Env, partner: Address, active: bool) -> Result<(), Error>;
+    fn partner_award_points(
+        env: Env,
+        partner: Address,
+        user: Address,
+        purchase_amount: i128,
+    ) -> Result<i128, Error>;
+    fn partner_redeem_points(
+        env: Env,
+        partner: Address,
+        user: Address,
+        points: i128,
+    ) -> Result<(), Error>;
+    fn settle_partner(env: Env, partner: Address) -> Result<i128, Error>;
+    fn force_settle_partner(env: Env, partner: Address) -> Result<i128, Error>;
+    fn get_partner(env: Env, partner: Address) -> Result<Partner, Error>;
+    fn get_partner_account(env: Env, partner: Address) -> PartnerAccount;
+
     // Levels management
     fn init_level_requirements(env: Env, requirements: LevelRequirements) -> Result<(), Error>;
     fn check_and_update_level(env: Env, user: Address) -> Result<bool, Error>;
     fn get_user_level(env: Env, user: Address) -> Result<LoyaltyLevel, Error>;
     fn award_anniversary_bonus(env: Env, user: Address) -> Result<i128, Error>;
+    fn set_tier_policy(env: Env, policy: TierPolicy) -> Result<(), Error>;
+    fn get_tier_policy(env: Env) -> TierPolicy;
+    fn requalify_level(env: Env, user: Address) -> Result<bool, Error>;
+    fn grant_status_match(
+        env: Env,
+        user: Address,
+        level: LoyaltyLevel,
+        duration_days: u64,
+    ) -> Result<(), Error>;
+    fn get_tier_status(env: Env, user: Address) -> Result<TierStatus, Error>;
+    fn get_qualifying_activity(env: Env, user: Address) -> Result<QualifyingActivity, Error>;
 
     // Milestones management
     fn create_milestone(env: Env, milestone: Milestone) -> Result<(), Error>;
@@ -65,6 +141,40 @@ pub trait LoyaltyRewardsTrait {
         reward_id: u32,
         purchase_amount: i128,
     ) -> Result<i128, Error>;
+    fn set_reward_availability(
+        env: Env,
+        reward_id: u32,
+        availability: RewardAvailability,
+    ) -> Result<(), Error>;
+    fn get_reward_availability(env: Env, reward_id: u32) -> RewardAvailability;
+    fn set_reward_level_price(
+        env: Env,
+        reward_id: u32,
+        level: LoyaltyLevel,
+        points_cost: i128,
+    ) -> Result<(), Error>;
+    fn set_reward_merchant(env: Env, reward_id: u32, merchant: Address) -> Result<(), Error>;
+    fn fulfill_redemption(env: Env, caller: Address, redemption_id: u32) -> Result<(), Error>;
+    fn cancel_redemption(env: Env, caller: Address, redemption_id: u32) -> Result<(), Error>;
+    fn get_redemption(env: Env, redemption_id: u32) -> Result<Redemption, Error>;
+    fn get_user_redemptions(env: Env, user: Address, cursor: u32, limit: u32) -> Vec<Redemption>;
+
+    // Reward payout pools
+    fn set_native_token(env: Env, token: Address) -> Result<(), Error>;
+    fn get_native_token(env: Env) -> Result<Address, Error>;
+    fn fund_reward_pool(env: Env, token: Address, amount: i128) -> Result<i128, Error>;
+    fn withdraw_reward_pool(
+        env: Env,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<i128, Error>;
+    fn set_pool_low_balance_threshold(
+        env: Env,
+        token: Address,
+        threshold: i128,
+    ) -> Result<(), Error>;
+    fn get_reward_pool(env: Env, token: Address) -> RewardPool;
 }
 
 #[contract]
@@ -120,6 +230,23 @@ impl LoyaltyRewardsTrait for LoyaltyRewards {
         PointsManager::get_lifetime_points(&env, &user)
     }
 
+    fn expire_points(env: Env, user: Address) -> Result<i128, Error> {
+        PointsManager::expire_points(&env, &user)
+    }
+
+    fn get_expiring_points(env: Env, user: Address, days: u64) -> Result<Vec<PointsLot>, Error> {
+        PointsManager::get_expiring_points(&env, &user, days)
+    }
+
+    fn get_transactions(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<Vec<PointsTransaction>, Error> {
+        PointsManager::get_transactions(&env, &user, cursor, limit)
+    }
+
     fn record_purchase_points(
         env: Env,
         user: Address,
@@ -130,6 +257,105 @@ impl LoyaltyRewardsTrait for LoyaltyRewards {
         PointsManager::record_purchase_points(&env, &user, purchase_amount, product_id, category)
     }
 
+    // Point transfers
+    fn set_transfer_rules(env: Env, rules: TransferRules) -> Result<(), Error> {
+        TransferManager::set_transfer_rules(&env, rules)
+    }
+
+    fn get_transfer_rules(env: Env) -> TransferRules {
+        TransferManager::get_transfer_rules(&env)
+    }
+
+    fn transfer_points(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        TransferManager::transfer_points(&env, &from, &to, amount)
+    }
+
+    fn set_transfer_exempt(env: Env, holder: Address, exempt: bool) -> Result<(), Error> {
+        TransferManager::set_transfer_exempt(&env, &holder, exempt)
+    }
+
+    fn is_transfer_exempt(env: Env, holder: Address) -> bool {
+        TransferManager::is_transfer_exempt(&env, &holder)
+    }
+
+    // Token mode
+    fn set_token_config(env: Env, config: TokenConfig) -> Result<(), Error> {
+        PointsToken::set_token_config(&env, config)
+    }
+
+    fn get_token_config(env: Env) -> Result<TokenConfig, Error> {
+        PointsToken::get_token_config(&env)
+    }
+
+    // Partner merchants
+    fn register_partner(
+        env: Env,
+        partner: Address,
+        name: Symbol,
+        points_ratio: u32,
+        settlement_token: Option<Address>,
+        point_value: i128,
+    ) -> Result<(), Error> {
+        PartnerManager::register_partner(
+            &env,
+            &partner,
+            name,
+            points_ratio,
+            settlement_token,
+            point_value,
+        )
+    }
+
+    fn fund_partner_budget(env: Env, partner: Address, points: i128) -> Result<i128, Error> {
+        PartnerManager::fund_partner_budget(&env, &partner, points)
+    }
+
+    fn set_partner_credit_limit(
+        env: Env,
+        partner: Address,
+        credit_limit: i128,
+    ) -> Result<(), Error> {
+        PartnerManager::set_partner_credit_limit(&env, &partner, credit_limit)
+    }
+
+    fn set_partner_active(env: Env, partner: Address, active: bool) -> Result<(), Error> {
+        PartnerManager::set_partner_active(&env, &partner, active)
+    }
+
+    fn partner_award_points(
+        env: Env,
+        partner: Address,
+        user: Address,
+        purchase_amount: i128,
+    ) -> Result<i128, Error> {
+        PartnerManager::award_points(&env, &partner, &user, purchase_amount)
+    }
+
+    fn partner_redeem_points(
+        env: Env,
+        partner: Address,
+        user: Address,
+        points: i128,
+    ) -> Result<(), Error> {
+        PartnerManager::redeem_points(&env, &partner, &user, points)
+    }
+
+    fn settle_partner(env: Env, partner: Address) -> Result<i128, Error> {
+        PartnerManager::settle_partner(&env, &partner)
+    }
+
+    fn force_settle_partner(env: Env, partner: Address) -> Result<i128, Error> {
+        PartnerManager::force_settle_partner(&env, &partner)
+    }
+
+    fn get_partner(env: Env, partner: Address) -> Result<Partner, Error> {
+        PartnerManager::get_partner(&env, &partner)
+    }
+
+    fn get_partner_account(env: Env, partner: Address) -> PartnerAccount {
+        PartnerManager::get_partner_account(&env, &partner)
+    }
+
     // Levels management
     fn init_level_requirements(env: Env, requirements: LevelRequirements) -> Result<(), Error> {
         LevelManager::init_level_requirements(&env, requirements)
@@ -147,6 +373,35 @@ impl LoyaltyRewardsTrait for LoyaltyRewards {
         LevelManager::award_anniversary_bonus(&env, &user)
     }
 
+    fn set_tier_policy(env: Env, policy: TierPolicy) -> Result<(), Error> {
+        LevelManager::set_tier_policy(&env, policy)
+    }
+
+    fn get_tier_policy(env: Env) -> TierPolicy {
+        LevelManager::get_tier_policy(&env)
+    }
+
+    fn requalify_level(env: Env, user: Address) -> Result<bool, Error> {
+        LevelManager::requalify_level(&env, &user)
+    }
+
+    fn grant_status_match(
+        env: Env,
+        user: Address,
+        level: LoyaltyLevel,
+        duration_days: u64,
+    ) -> Result<(), Error> {
+        LevelManager::grant_status_match(&env, &user, level, duration_days)
+    }
+
+    fn get_tier_status(env: Env, user: Address) -> Result<TierStatus, Error> {
+        LevelManager::get_tier_status(&env, &user)
+    }
+
+    fn get_qualifying_activity(env: Env, user: Address) -> Result<QualifyingActivity, Error> {
+        LevelManager::get_qualifying_activity(&env, &user)
+    }
+
     // Milestones management
     fn create_milestone(env: Env, milestone: Milestone) -> Result<(), Error> {
         MilestoneManager::create_milestone(&env, milestone)
@@ -185,4 +440,123 @@ impl LoyaltyRewardsTrait for LoyaltyRewards {
     ) -> Result<i128, Error> {
         RewardManager::calculate_discount(&env, reward_id, purchase_amount)
     }
+
+    fn set_reward_availability(
+        env: Env,
+        reward_id: u32,
+        availability: RewardAvailability,
+    ) -> Result<(), Error> {
+        RewardManager::set_reward_availability(&env, reward_id, availability)
+    }
+
+    fn get_reward_availability(env: Env, reward_id: u32) -> RewardAvailability {
+        RewardManager::get_reward_availability(&env, reward_id)
+    }
+
+    fn set_reward_level_price(
+        env: Env,
+        reward_id: u32,
+        level: LoyaltyLevel,
+        points_cost: i128,
+    ) -> Result<(), Error> {
+        RewardManager::set_reward_level_price(&env, reward_id, level, points_cost)
+    }
+
+    fn set_reward_merchant(env: Env, reward_id: u32, merchant: Address) -> Result<(), Error> {
+        RewardManager::set_reward_merchant(&env, reward_id, &merchant)
+    }
+
+    fn fulfill_redemption(env: Env, caller: Address, redemption_id: u32) -> Result<(), Error> {
+        RewardManager::fulfill_redemption(&env, &caller, redemption_id)
+    }
+
+    fn cancel_redemption(env: Env, caller: Address, redemption_id: u32) -> Result<(), Error> {
+        RewardManager::cancel_redemption(&env, &caller, redemption_id)
+    }
+
+    fn get_redemption(env: Env, redemption_id: u32) -> Result<Redemption, Error> {
+        RewardManager::get_redemption(&env, redemption_id)
+    }
+
+    fn get_user_redemptions(env: Env, user: Address, cursor: u32, limit: u32) -> Vec<Redemption> {
+        RewardManager::get_user_redemptions(&env, &user, cursor, limit)
+    }
+
+    // Reward payout pools
+    fn set_native_token(env: Env, token: Address) -> Result<(), Error> {
+        RewardPoolManager::set_native_token(&env, &token)
+    }
+
+    fn get_native_token(env: Env) -> Result<Address, Error> {
+        RewardPoolManager::get_native_token(&env)
+    }
+
+    fn fund_reward_pool(env: Env, token: Address, amount: i128) -> Result<i128, Error> {
+        RewardPoolManager::fund_pool(&env, &token, amount)
+    }
+
+    fn withdraw_reward_pool(
+        env: Env,
+        token: Address,
+        amount: i128,
+        to: Address,
+    ) -> Result<i128, Error> {
+        RewardPoolManager::withdraw_pool(&env, &token, amount, &to)
+    }
+
+    fn set_pool_low_balance_threshold(
+        env: Env,
+        token: Address,
+        threshold: i128,
+    ) -> Result<(), Error> {
+        RewardPoolManager::set_low_balance_threshold(&env, &token, threshold)
+    }
+
+    fn get_reward_pool(env: Env, token: Address) -> RewardPool {
+        RewardPoolManager::get_pool(&env, &token)
+    }
+}
+
+/// SEP-41 view of the spendable points balance, active once token mode is enabled
+#[contractimpl]
+impl TokenInterface for LoyaltyRewards {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        PointsToken::allowance(&env, &from, &spender)
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        PointsToken::approve(&env, &from, &spender, amount, expiration_ledger)
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        PointsToken::balance(&env, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        PointsToken::transfer(&env, &from, &to, amount)
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        PointsToken::transfer_from(&env, &spender, &from, &to, amount)
+    }
+
+    fn burn(env: Env, from: Address, amount: i128) {
+        PointsToken::burn(&env, &from, amount)
+    }
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        PointsToken::burn_from(&env, &spender, &from, amount)
+    }
+
+    fn decimals(env: Env) -> u32 {
+        PointsToken::decimals(&env)
+    }
+
+    fn name(env: Env) -> String {
+        PointsToken::name(&env)
+    }
+
+    fn symbol(env: Env) -> String {
+        PointsToken::symbol(&env)
+    }
 }
@@ -1,6 +1,6 @@
 use crate::points::PointsManager;
 use crate::types::{DataKey, Error, Milestone, MilestoneRequirement, TransactionType, UserData};
-use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 pub struct MilestoneManager;
 
@@ -62,10 +62,10 @@ impl MilestoneManager {
             }
             MilestoneRequirement::PointsEarned(required) => user_data.lifetime_points >= *required,
             MilestoneRequirement::SpecificProduct(product_id) => {
-                Self::has_purchased_product(user_data, product_id)
+                Self::has_purchased_product(env, user_data, product_id)
             }
             MilestoneRequirement::SpecificCategory(category) => {
-                Self::has_purchased_category(user_data, category)
+                Self::has_purchased_category(env, user_data, category)
             }
             MilestoneRequirement::DaysActive(required) => {
                 let days_active = (env.ledger().timestamp() - user_data.join_date) / (24 * 60 * 60);
@@ -137,31 +137,23 @@ impl MilestoneManager {
     // Helper Functions
 
     pub fn count_purchases(user_data: &UserData) -> u32 {
-        user_data.transactions.iter().filter(|tx| 
-            tx.transaction_type == TransactionType::Earned && tx.description == symbol_short!("purchase")
-        ).count() as u32
+        user_data.summary.purchase_count
     }
 
     fn calculate_total_spend(user_data: &UserData, points_ratio: u32) -> i128 {
-        let mut total_points_from_purchase = 0;
-        for tx in user_data.transactions.iter() {
-            if tx.transaction_type == TransactionType::Earned && tx.description == symbol_short!("purchase") {
-                total_points_from_purchase += tx.amount;
-            }
-        }
         // Approximate spend based on points earned and the base ratio
-        total_points_from_purchase * (points_ratio as i128)
+        user_data.summary.purchase_points * (points_ratio as i128)
     }
 
-    fn has_purchased_product(user_data: &UserData, product_id: &Symbol) -> bool {
-        user_data.transactions.iter().any(|tx| {
-            tx.transaction_type == TransactionType::Earned && tx.product_id.as_ref() == Some(product_id)
-        })
+    fn has_purchased_product(env: &Env, user_data: &UserData, product_id: &Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::PurchasedProduct(user_data.address.clone(), product_id.clone()))
     }
 
-    fn has_purchased_category(user_data: &UserData, category: &Symbol) -> bool {
-        user_data.transactions.iter().any(|tx| {
-            tx.transaction_type == TransactionType::Earned && tx.category.as_ref() == Some(category)
-        })
+    fn has_purchased_category(env: &Env, user_data: &UserData, category: &Symbol) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::PurchasedCategory(user_data.address.clone(), category.clone()))
     }
 }
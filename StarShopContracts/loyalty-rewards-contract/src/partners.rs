@@ -0,0 +1,259 @@
+use crate::points::PointsManager;
+use crate::types::{DataKey, Error, Partner, PartnerAccount, TransactionType};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+pub struct PartnerManager;
+
+impl PartnerManager {
+    /// Register a partner merchant, either prepaid (no settlement token) or settled in a token
+    /// Settled partners can only award points once the admin gives them a credit limit
+    pub fn register_partner(
+        env: &Env,
+        partner: &Address,
+        name: Symbol,
+        points_ratio: u32,
+        settlement_token: Option<Address>,
+        point_value: i128,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if env.storage().instance().has(&DataKey::Partner(partner.clone())) {
+            return Err(Error::AlreadyInitialized); // Partner already exists
+        }
+        if points_ratio == 0 || point_value < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let new_partner = Partner {
+            address: partner.clone(),
+            name: name.clone(),
+            points_ratio,
+            budget: 0,
+            settlement_token,
+            point_value,
+            credit_limit: 0,
+            active: true,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Partner(partner.clone()), &new_partner);
+
+        env.events().publish(
+            (Symbol::new(env, "partner_registered"), partner.clone()),
+            ((name, points_ratio),),
+        );
+
+        Ok(())
+    }
+
+    /// Add prepaid points to a partner's award budget
+    pub fn fund_partner_budget(env: &Env, partner: &Address, points: i128) -> Result<i128, Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if points <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut partner_data = Self::get_partner(env, partner)?;
+        partner_data.budget += points;
+        Self::save_partner(env, &partner_data);
+
+        Ok(partner_data.budget)
+    }
+
+    /// Set how many unsettled points a settled partner may have awarded
+    pub fn set_partner_credit_limit(
+        env: &Env,
+        partner: &Address,
+        credit_limit: i128,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if credit_limit < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut partner_data = Self::get_partner(env, partner)?;
+        partner_data.credit_limit = credit_limit;
+        Self::save_partner(env, &partner_data);
+
+        Ok(())
+    }
+
+    /// Activate or deactivate a partner
+    pub fn set_partner_active(env: &Env, partner: &Address, active: bool) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        let mut partner_data = Self::get_partner(env, partner)?;
+        partner_data.active = active;
+        Self::save_partner(env, &partner_data);
+
+        Ok(())
+    }
+
+    /// Award points for a purchase made at a partner, using the partner's own ratio
+    pub fn award_points(
+        env: &Env,
+        partner: &Address,
+        user: &Address,
+        purchase_amount: i128,
+    ) -> Result<i128, Error> {
+        partner.require_auth();
+
+        let mut partner_data = Self::get_active_partner(env, partner)?;
+        if purchase_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let points = purchase_amount / (partner_data.points_ratio as i128);
+        if points == 0 {
+            return Ok(0);
+        }
+
+        // Prepaid partners draw down their budget; settled partners accrue a liability
+        // up to their credit limit, after which awards stop until they settle
+        let mut account = Self::get_partner_account(env, partner);
+        if partner_data.settlement_token.is_none() {
+            if partner_data.budget < points {
+                return Err(Error::PartnerBudgetExceeded);
+            }
+            partner_data.budget -= points;
+            Self::save_partner(env, &partner_data);
+        } else if account.net_points + points > partner_data.credit_limit {
+            return Err(Error::PartnerCreditExceeded);
+        }
+
+        if !PointsManager::user_exists(env, user) {
+            PointsManager::register_user(env, user)?;
+        }
+        PointsManager::add_points(
+            env,
+            user,
+            points,
+            symbol_short!("partner"),
+            TransactionType::Earned,
+            None,
+            None,
+        )?;
+
+        account.awarded += points;
+        account.net_points += points;
+        Self::save_partner_account(env, partner, &account);
+
+        env.events().publish(
+            (Symbol::new(env, "partner_points_awarded"), partner.clone(), user.clone()),
+            ((points, purchase_amount),),
+        );
+
+        Ok(points)
+    }
+
+    /// Redeem a customer's points at a partner; requires both the partner and the customer
+    pub fn redeem_points(
+        env: &Env,
+        partner: &Address,
+        user: &Address,
+        points: i128,
+    ) -> Result<(), Error> {
+        partner.require_auth();
+        user.require_auth();
+
+        Self::get_active_partner(env, partner)?;
+        PointsManager::spend_points(env, user, points, symbol_short!("partner"))?;
+
+        let mut account = Self::get_partner_account(env, partner);
+        account.redeemed += points;
+        account.net_points -= points;
+        Self::save_partner_account(env, partner, &account);
+
+        env.events().publish(
+            (Symbol::new(env, "partner_points_redeemed"), partner.clone(), user.clone()),
+            (points,),
+        );
+
+        Ok(())
+    }
+
+    /// Settle a partner's net points balance in its settlement token
+    /// A partner that awarded more than it redeemed pays the program, otherwise the program pays it
+    pub fn settle_partner(env: &Env, partner: &Address) -> Result<i128, Error> {
+        Self::settle(env, partner, false)
+    }
+
+    /// Settle a partner's net points balance on the admin's initiative
+    /// A partner that owes the program is charged through the allowance it gave this contract
+    pub fn force_settle_partner(env: &Env, partner: &Address) -> Result<i128, Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+        Self::settle(env, partner, true)
+    }
+
+    fn settle(env: &Env, partner: &Address, forced: bool) -> Result<i128, Error> {
+        let partner_data = Self::get_partner(env, partner)?;
+        let token_address = partner_data
+            .settlement_token
+            .clone()
+            .ok_or(Error::SettlementNotConfigured)?;
+
+        let mut account = Self::get_partner_account(env, partner);
+        let amount = account.net_points * partner_data.point_value;
+        let token = TokenClient::new(env, &token_address);
+        if amount > 0 && forced {
+            let contract = env.current_contract_address();
+            token.transfer_from(&contract, partner, &contract, &amount);
+        } else if amount > 0 {
+            partner.require_auth();
+            token.transfer(partner, &env.current_contract_address(), &amount);
+        } else if amount < 0 {
+            crate::admin::AdminModule::verify_admin(env)?;
+            token.transfer(&env.current_contract_address(), partner, &-amount);
+        }
+
+        account.settled += account.net_points;
+        account.net_points = 0;
+        Self::save_partner_account(env, partner, &account);
+
+        env.events().publish(
+            (Symbol::new(env, "partner_settled"), partner.clone()),
+            ((amount, env.ledger().timestamp()),),
+        );
+
+        Ok(amount)
+    }
+
+    /// Get a partner by address
+    pub fn get_partner(env: &Env, partner: &Address) -> Result<Partner, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Partner(partner.clone()))
+            .ok_or(Error::PartnerNotFound)
+    }
+
+    /// Get a partner's points accounting
+    pub fn get_partner_account(env: &Env, partner: &Address) -> PartnerAccount {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PartnerAccount(partner.clone()))
+            .unwrap_or_default()
+    }
+
+    fn get_active_partner(env: &Env, partner: &Address) -> Result<Partner, Error> {
+        let partner_data = Self::get_partner(env, partner)?;
+        if !partner_data.active {
+            return Err(Error::PartnerInactive);
+        }
+        Ok(partner_data)
+    }
+
+    fn save_partner(env: &Env, partner: &Partner) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Partner(partner.address.clone()), partner);
+    }
+
+    fn save_partner_account(env: &Env, partner: &Address, account: &PartnerAccount) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::PartnerAccount(partner.clone()), account);
+    }
+}
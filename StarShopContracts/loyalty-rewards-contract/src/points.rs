@@ -1,5 +1,13 @@
-use crate::types::{DataKey, Error, PointsTransaction, TransactionType, UserData};
-use soroban_sdk::{symbol_short, Address, Env, Symbol};
+use crate::types::{
+    ActivitySummary, DataKey, Error, PointsLot, PointsTransaction, TransactionType, UserData,
+};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+/// Maximum number of entries returned by one history page
+pub(crate) const MAX_PAGE_SIZE: u32 = 50;
+
+/// Length of the expiry buckets points are grouped into, in seconds
+const EXPIRY_BUCKET: u64 = 24 * 60 * 60;
 
 pub struct PointsManager;
 
@@ -14,7 +22,7 @@ impl PointsManager {
             address: user.clone(),
             level: crate::types::LoyaltyLevel::Bronze,
             level_updated_at: env.ledger().timestamp(),
-            transactions: soroban_sdk::vec![env],
+            summary: ActivitySummary::default(),
             completed_milestones: soroban_sdk::vec![env],
             join_date: env.ledger().timestamp(),
             last_anniversary_awarded: env.ledger().timestamp(),
@@ -43,9 +51,11 @@ impl PointsManager {
 
         let mut user_data = Self::get_user_data(env, user)?;
 
+        // Round the expiry up to the end of its day so credits on the same day share a lot
         let expiry_days = crate::admin::AdminModule::get_points_expiry(env);
         let current_time = env.ledger().timestamp();
-        let expiration = current_time + (expiry_days * 24 * 60 * 60);
+        let expiration =
+            (current_time + (expiry_days * 24 * 60 * 60)).div_ceil(EXPIRY_BUCKET) * EXPIRY_BUCKET;
 
         let transaction = PointsTransaction {
             amount,
@@ -57,7 +67,7 @@ impl PointsManager {
             category,
         };
 
-        user_data.transactions.push_back(transaction.clone());
+        Self::record_transaction(env, user, &mut user_data, &transaction);
         
         if transaction_type == TransactionType::Earned || transaction_type == TransactionType::Bonus {
             user_data.lifetime_points += amount;
@@ -67,6 +77,18 @@ impl PointsManager {
             .persistent()
             .set(&DataKey::User(user.clone()), &user_data);
 
+        let mut lots = Self::get_lots(env, user);
+        Self::insert_lot(
+            &mut lots,
+            PointsLot {
+                amount,
+                transaction_type,
+                earned_at: current_time,
+                expiration,
+            },
+        );
+        Self::set_lots(env, user, &lots);
+
         env.events().publish(
             (Symbol::new(env, "points_added"), user.clone()),
             (transaction,),
@@ -75,23 +97,24 @@ impl PointsManager {
         Ok(())
     }
 
-    /// Spend points from a user's account
+    /// Spend points from a user's account, returning the portions taken from each lot
     pub fn spend_points(
         env: &Env,
         user: &Address,
         amount: i128,
         description: Symbol,
-    ) -> Result<(), Error> {
+    ) -> Result<Vec<PointsLot>, Error> {
         if amount <= 0 {
             return Err(Error::InvalidAmount);
         }
 
+        // Burn expired lots first so only live points can be spent
+        Self::expire_points(env, user)?;
+
         let mut user_data = Self::get_user_data(env, user)?;
-        let balance = Self::get_points_balance_internal(env, &user_data)?;
-        
-        if balance < amount {
-            return Err(Error::InsufficientPoints);
-        }
+        let mut lots = Self::get_lots(env, user);
+        let spent = Self::take_from_lots(env, &mut lots, amount, true)?;
+        Self::set_lots(env, user, &lots);
 
         let transaction = PointsTransaction {
             amount: -amount, // Negative for spending
@@ -103,7 +126,7 @@ impl PointsManager {
             category: None,
         };
 
-        user_data.transactions.push_back(transaction.clone());
+        Self::record_transaction(env, user, &mut user_data, &transaction);
 
         env.storage()
             .persistent()
@@ -114,38 +137,297 @@ impl PointsManager {
             (transaction,),
         );
 
+        Ok(spent)
+    }
+
+    /// Return spent points to a user, restoring each portion with its original expiry
+    pub fn refund_points(
+        env: &Env,
+        user: &Address,
+        spent: &Vec<PointsLot>,
+        description: Symbol,
+    ) -> Result<(), Error> {
+        let mut user_data = Self::get_user_data(env, user)?;
+
+        let mut lots = Self::get_lots(env, user);
+        let mut amount = 0i128;
+        for lot in spent.iter() {
+            amount += lot.amount;
+            Self::insert_lot(&mut lots, lot);
+        }
+        Self::set_lots(env, user, &lots);
+
+        let transaction = PointsTransaction {
+            amount,
+            transaction_type: TransactionType::Refunded,
+            timestamp: env.ledger().timestamp(),
+            description,
+            expiration: 0,
+            product_id: None,
+            category: None,
+        };
+        Self::record_transaction(env, user, &mut user_data, &transaction);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(user.clone()), &user_data);
+
+        env.events().publish(
+            (Symbol::new(env, "points_added"), user.clone()),
+            (transaction,),
+        );
+
         Ok(())
     }
     
-    /// Internal function to calculate balance from transaction history
-    fn get_points_balance_internal(env: &Env, user_data: &UserData) -> Result<i128, Error> {
+    /// Burn a user's expired lots, recording an Expired transaction for the total
+    pub fn expire_points(env: &Env, user: &Address) -> Result<i128, Error> {
+        let mut user_data = Self::get_user_data(env, user)?;
+        let current_time = env.ledger().timestamp();
+
+        // Lots are ordered by expiration, so expired ones sit at the front
+        let mut lots = Self::get_lots(env, user);
+        let mut burned = 0i128;
+        while let Some(lot) = lots.get(0) {
+            if lot.expiration > current_time {
+                break;
+            }
+            burned += lot.amount;
+            lots.remove(0);
+        }
+
+        if burned == 0 {
+            return Ok(0);
+        }
+        Self::set_lots(env, user, &lots);
+
+        let transaction = PointsTransaction {
+            amount: -burned, // Negative for burned points
+            transaction_type: TransactionType::Expired,
+            timestamp: current_time,
+            description: symbol_short!("expired"),
+            expiration: 0,
+            product_id: None,
+            category: None,
+        };
+        Self::record_transaction(env, user, &mut user_data, &transaction);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(user.clone()), &user_data);
+
+        env.events().publish(
+            (Symbol::new(env, "points_expired"), user.clone()),
+            (transaction,),
+        );
+
+        Ok(burned)
+    }
+
+    /// Get user's current, valid points balance
+    pub fn get_points_balance(env: &Env, user: &Address) -> Result<i128, Error> {
+        if !Self::user_exists(env, user) {
+            return Err(Error::UserNotFound);
+        }
+        Ok(Self::sum_lots(env, &Self::get_lots(env, user)))
+    }
+
+    /// Get the unexpired points a user can transfer, or zero for unknown users
+    /// Promotional points only count when `include_bonus` is set
+    pub fn get_transferable_balance(env: &Env, user: &Address, include_bonus: bool) -> i128 {
+        let current_time = env.ledger().timestamp();
+        Self::get_lots(env, user)
+            .iter()
+            .filter(|lot| lot.expiration > current_time)
+            .filter(|lot| include_bonus || lot.transaction_type != TransactionType::Bonus)
+            .map(|lot| lot.amount)
+            .sum()
+    }
+
+    /// Get the unexpired lots that expire within the given number of days, soonest first
+    pub fn get_expiring_points(env: &Env, user: &Address, days: u64) -> Result<Vec<PointsLot>, Error> {
+        if !Self::user_exists(env, user) {
+            return Err(Error::UserNotFound);
+        }
+
         let current_time = env.ledger().timestamp();
-        let mut balance = 0i128;
-
-        for transaction in user_data.transactions.iter() {
-            match transaction.transaction_type {
-                TransactionType::Earned | TransactionType::Bonus => {
-                    // Add points only if they have not expired
-                    if transaction.expiration > current_time {
-                        balance += transaction.amount;
-                    }
+        let horizon = current_time + (days * 24 * 60 * 60);
+        let mut expiring = Vec::new(env);
+        for lot in Self::get_lots(env, user).iter() {
+            if lot.expiration > horizon {
+                break;
+            }
+            if lot.expiration > current_time {
+                expiring.push_back(lot);
+            }
+        }
+        Ok(expiring)
+    }
+
+    /// Get a user's unspent point lots, including expired ones not yet burned
+    pub fn get_lots(env: &Env, user: &Address) -> Vec<PointsLot> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PointsLots(user.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Insert a lot, keeping lots ordered by expiration so the soonest-to-expire are used first
+    /// A lot of the same type and expiration is merged into rather than added alongside
+    pub fn insert_lot(lots: &mut Vec<PointsLot>, lot: PointsLot) {
+        let position = lots
+            .iter()
+            .position(|existing| existing.expiration > lot.expiration)
+            .unwrap_or(lots.len() as usize) as u32;
+
+        // Equal expirations sit just before the insert position
+        let mut index = position;
+        while index > 0 {
+            index -= 1;
+            let mut existing = lots.get(index).unwrap();
+            if existing.expiration != lot.expiration {
+                break;
+            }
+            if existing.transaction_type == lot.transaction_type {
+                existing.amount += lot.amount;
+                existing.earned_at = existing.earned_at.min(lot.earned_at);
+                lots.set(index, existing);
+                return;
+            }
+        }
+
+        lots.insert(position, lot);
+    }
+
+    /// Remove points from lots in expiration order, returning the portions taken
+    /// Bonus lots are skipped unless `include_bonus` is set; expired lots must be burned first
+    pub fn take_from_lots(
+        env: &Env,
+        lots: &mut Vec<PointsLot>,
+        amount: i128,
+        include_bonus: bool,
+    ) -> Result<Vec<PointsLot>, Error> {
+        let is_eligible =
+            |lot: &PointsLot| include_bonus || lot.transaction_type != TransactionType::Bonus;
+        let available: i128 = lots
+            .iter()
+            .filter(|lot| is_eligible(lot))
+            .map(|lot| lot.amount)
+            .sum();
+        if available < amount {
+            return Err(Error::InsufficientPoints);
+        }
+
+        let mut taken = Vec::new(env);
+        let mut remaining = amount;
+        let mut index = 0;
+        while remaining > 0 {
+            let mut lot = lots.get(index).ok_or(Error::InsufficientPoints)?;
+            if !is_eligible(&lot) {
+                index += 1;
+                continue;
+            }
+
+            let portion = lot.amount.min(remaining);
+            taken.push_back(PointsLot {
+                amount: portion,
+                ..lot.clone()
+            });
+            remaining -= portion;
+
+            // Drop lots that are used up
+            if portion == lot.amount {
+                lots.remove(index);
+            } else {
+                lot.amount -= portion;
+                lots.set(index, lot);
+            }
+        }
+        Ok(taken)
+    }
+
+    pub fn set_lots(env: &Env, user: &Address, lots: &Vec<PointsLot>) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::PointsLots(user.clone()), lots);
+    }
+
+    /// Sum the unexpired points across lots
+    fn sum_lots(env: &Env, lots: &Vec<PointsLot>) -> i128 {
+        let current_time = env.ledger().timestamp();
+        lots.iter()
+            .filter(|lot| lot.expiration > current_time)
+            .map(|lot| lot.amount)
+            .sum()
+    }
+
+    /// Append a transaction to the user's history and fold it into their summary
+    /// The caller saves the updated user data
+    pub fn record_transaction(
+        env: &Env,
+        user: &Address,
+        user_data: &mut UserData,
+        transaction: &PointsTransaction,
+    ) {
+        let summary = &mut user_data.summary;
+        env.storage().persistent().set(
+            &DataKey::Transaction(user.clone(), summary.transaction_count),
+            transaction,
+        );
+        summary.transaction_count += 1;
+        summary.last_activity = transaction.timestamp;
+        crate::levels::LevelManager::record_qualifying_activity(env, user, transaction);
+
+        match transaction.transaction_type {
+            TransactionType::Earned => {
+                if transaction.description == symbol_short!("purchase") {
+                    summary.purchase_count += 1;
+                    summary.purchase_points += transaction.amount;
                 }
-                TransactionType::Spent => {
-                    // Spent points are represented as negative amounts
-                    balance += transaction.amount; 
+                if let Some(product_id) = &transaction.product_id {
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::PurchasedProduct(user.clone(), product_id.clone()), &true);
                 }
-                TransactionType::Expired => {
-                    // Expired transactions are markers and don't affect live calculation
+                if let Some(category) = &transaction.category {
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::PurchasedCategory(user.clone(), category.clone()), &true);
                 }
             }
+            TransactionType::Spent => summary.total_spent -= transaction.amount,
+            TransactionType::Expired => summary.total_expired -= transaction.amount,
+            TransactionType::TransferSent => summary.total_sent -= transaction.amount,
+            TransactionType::TransferReceived => summary.total_received += transaction.amount,
+            TransactionType::Refunded => summary.total_spent -= transaction.amount,
+            TransactionType::Bonus => {}
         }
-        Ok(balance)
     }
 
-    /// Get user's current, valid points balance
-    pub fn get_points_balance(env: &Env, user: &Address) -> Result<i128, Error> {
+    /// Get a page of a user's transaction history, oldest first
+    /// `cursor` is the index of the first transaction; pass `cursor + len` to fetch the next page
+    pub fn get_transactions(
+        env: &Env,
+        user: &Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<Vec<PointsTransaction>, Error> {
         let user_data = Self::get_user_data(env, user)?;
-        Self::get_points_balance_internal(env, &user_data)
+        let end = cursor
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(user_data.summary.transaction_count);
+
+        let mut transactions = Vec::new(env);
+        for index in cursor..end {
+            if let Some(transaction) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Transaction(user.clone(), index))
+            {
+                transactions.push_back(transaction);
+            }
+        }
+        Ok(transactions)
     }
 
     /// Get user's lifetime points (total ever earned)
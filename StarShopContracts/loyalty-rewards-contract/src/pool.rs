@@ -0,0 +1,136 @@
+use crate::types::{DataKey, Error, RewardPool};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::{Address, Env, Symbol};
+
+pub struct RewardPoolManager;
+
+impl RewardPoolManager {
+    /// Set the Stellar Asset Contract address used to pay XLM rewards
+    pub fn set_native_token(env: &Env, token: &Address) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        env.storage().instance().set(&DataKey::NativeToken, token);
+
+        Ok(())
+    }
+
+    /// Get the Stellar Asset Contract address of native XLM
+    pub fn get_native_token(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NativeToken)
+            .ok_or(Error::NativeTokenNotConfigured)
+    }
+
+    /// Move tokens from the admin into a reward pool
+    pub fn fund_pool(env: &Env, token: &Address, amount: i128) -> Result<i128, Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let admin = crate::admin::AdminModule::get_admin(env)?;
+        TokenClient::new(env, token).transfer(&admin, &env.current_contract_address(), &amount);
+
+        let mut pool = Self::get_pool(env, token);
+        pool.balance += amount;
+        Self::save_pool(env, token, &pool);
+
+        env.events().publish(
+            (Symbol::new(env, "reward_pool_funded"), token.clone()),
+            ((amount, pool.balance),),
+        );
+
+        Ok(pool.balance)
+    }
+
+    /// Withdraw unused funds from a reward pool
+    pub fn withdraw_pool(
+        env: &Env,
+        token: &Address,
+        amount: i128,
+        to: &Address,
+    ) -> Result<i128, Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let pool = Self::pay_out(env, token, to, amount)?;
+
+        env.events().publish(
+            (Symbol::new(env, "reward_pool_withdrawn"), token.clone()),
+            ((amount, to.clone(), pool.balance),),
+        );
+
+        Ok(pool.balance)
+    }
+
+    /// Set the balance below which the pool emits a low-balance event
+    pub fn set_low_balance_threshold(
+        env: &Env,
+        token: &Address,
+        threshold: i128,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if threshold < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut pool = Self::get_pool(env, token);
+        pool.low_balance_threshold = threshold;
+        Self::save_pool(env, token, &pool);
+
+        Ok(())
+    }
+
+    /// Get the reward pool of a token, empty if never funded
+    pub fn get_pool(env: &Env, token: &Address) -> RewardPool {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardPool(token.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Ensure a pool can cover a payout
+    pub fn check_balance(env: &Env, token: &Address, amount: i128) -> Result<(), Error> {
+        if Self::get_pool(env, token).balance < amount {
+            return Err(Error::InsufficientPoolBalance);
+        }
+        Ok(())
+    }
+
+    /// Pay an amount out of a pool, warning when the pool runs low
+    pub fn pay_out(
+        env: &Env,
+        token: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<RewardPool, Error> {
+        Self::check_balance(env, token, amount)?;
+
+        let mut pool = Self::get_pool(env, token);
+        pool.balance -= amount;
+        Self::save_pool(env, token, &pool);
+
+        TokenClient::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+
+        if pool.balance < pool.low_balance_threshold {
+            env.events().publish(
+                (Symbol::new(env, "reward_pool_low"), token.clone()),
+                ((pool.balance, pool.low_balance_threshold),),
+            );
+        }
+
+        Ok(pool)
+    }
+
+    fn save_pool(env: &Env, token: &Address, pool: &RewardPool) {
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardPool(token.clone()), pool);
+    }
+}
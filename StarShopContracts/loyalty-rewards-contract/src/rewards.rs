@@ -1,7 +1,11 @@
-use crate::points::PointsManager;
-use crate::types::{DataKey, Error, Reward, RewardType, UserRedemption};
-use soroban_sdk::token::Client as TokenClient;
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use crate::partners::PartnerManager;
+use crate::points::{PointsManager, MAX_PAGE_SIZE};
+use crate::pool::RewardPoolManager;
+use crate::types::{
+    DataKey, Error, LoyaltyLevel, PointsLot, Redemption, RedemptionStatus, Reward,
+    RewardAvailability, RewardType, UserRedemption,
+};
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
 
 pub struct RewardManager;
 
@@ -41,7 +45,7 @@ impl RewardManager {
         let reward = Self::get_reward(env, reward_id)?;
 
         let balance = PointsManager::get_points_balance(env, user)?;
-        if balance < reward.points_cost {
+        if balance < Self::get_reward_cost(env, &reward, &user_data.level) {
             return Ok(false);
         }
 
@@ -74,7 +78,11 @@ impl RewardManager {
         }
 
         let reward = Self::get_reward(env, reward_id)?;
+        let mut availability = Self::check_availability(env, reward_id)?;
+        let level = PointsManager::get_user_data(env, user)?.level;
+        let points_cost = Self::get_reward_cost(env, &reward, &level);
         let mut reward_value = 0i128;
+        let mut payout: Option<(Address, i128)> = None;
 
         // Process the reward based on its type
         match reward.reward_type.clone() {
@@ -86,13 +94,29 @@ impl RewardManager {
             RewardType::Product(_) => {
                 reward_value = 1; // Represents 1 product
             }
-            RewardType::XLM(amount) | RewardType::Token(_, amount) => {
+            RewardType::XLM(amount) => {
+                let token_address = RewardPoolManager::get_native_token(env)?;
+                RewardPoolManager::check_balance(env, &token_address, amount)?;
+                payout = Some((token_address, amount));
+                reward_value = amount;
+            }
+            RewardType::Token(token_address, amount) => {
+                RewardPoolManager::check_balance(env, &token_address, amount)?;
+                payout = Some((token_address, amount));
                 reward_value = amount;
             }
         }
         
-        // Deduct points
-        PointsManager::spend_points(env, user, reward.points_cost, reward.name.clone())?;
+        // Deduct points, keeping the lots they came from for a possible refund
+        let spent_lots = PointsManager::spend_points(env, user, points_cost, reward.name.clone())?;
+
+        // Take one unit out of limited stock
+        if let Some(stock) = availability.stock {
+            availability.stock = Some(stock - 1);
+            env.storage()
+                .instance()
+                .set(&DataKey::RewardAvailability(reward_id), &availability);
+        }
 
         // Update redemption count for the user
         let redemption_key = UserRedemption(user.clone(), reward_id);
@@ -101,28 +125,274 @@ impl RewardManager {
         env.storage().persistent().set(&redemption_key, &user_redemptions);
 
 
-        // Handle token/XLM transfers
-        match reward.reward_type {
-            RewardType::Token(token_address, amount) => {
-                let token = TokenClient::new(env, &token_address);
-                token.transfer(&env.current_contract_address(), user, &amount);
-            }
-            RewardType::XLM(_) => {
-                // NOTE: Native XLM transfers are not directly supported in vanilla Soroban contracts.
-                // This requires a separate contract call to an XLM wrapper or a host function invocation.
-                // This part of the logic is a placeholder for that integration.
+        // Pay token/XLM rewards from their pool; Discount and Product rewards are handled off-chain
+        let status = match payout {
+            Some((token_address, amount)) => {
+                RewardPoolManager::pay_out(env, &token_address, user, amount)?;
+                RedemptionStatus::Fulfilled
             }
-            _ => {} // Discount and Product rewards are handled off-chain
-        }
+            None => RedemptionStatus::Issued,
+        };
         
+        let redemption_id =
+            Self::issue_redemption(env, user, reward_id, points_cost, spent_lots, status);
+
         env.events().publish(
             (Symbol::new(env, "reward_claimed"), user.clone()),
-            ((reward.id, reward.name, reward.points_cost, env.ledger().timestamp()),),
+            ((reward.id, reward.name, points_cost, env.ledger().timestamp(), redemption_id),),
         );
         
         Ok(reward_value)
     }
 
+    /// Set a reward's inventory and availability window
+    pub fn set_reward_availability(
+        env: &Env,
+        reward_id: u32,
+        availability: RewardAvailability,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+        Self::get_reward(env, reward_id)?;
+
+        if availability.ends_at != 0 && availability.ends_at <= availability.starts_at {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardAvailability(reward_id), &availability);
+
+        Ok(())
+    }
+
+    /// Get a reward's inventory and availability window, unlimited and always active by default
+    pub fn get_reward_availability(env: &Env, reward_id: u32) -> RewardAvailability {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardAvailability(reward_id))
+            .unwrap_or(RewardAvailability {
+                active: true,
+                stock: None,
+                starts_at: 0,
+                ends_at: 0,
+            })
+    }
+
+    /// Set the points a reward costs for users at a given level
+    pub fn set_reward_level_price(
+        env: &Env,
+        reward_id: u32,
+        level: LoyaltyLevel,
+        points_cost: i128,
+    ) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+        Self::get_reward(env, reward_id)?;
+
+        if points_cost <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardLevelPrice(reward_id, level), &points_cost);
+
+        Ok(())
+    }
+
+    /// Assign the partner merchant that fulfils and cancels a reward's vouchers
+    pub fn set_reward_merchant(env: &Env, reward_id: u32, merchant: &Address) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+        Self::get_reward(env, reward_id)?;
+        PartnerManager::get_partner(env, merchant)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardMerchant(reward_id), merchant);
+
+        Ok(())
+    }
+
+    /// Get the points a reward costs at a level, falling back to its base cost
+    pub fn get_reward_cost(env: &Env, reward: &Reward, level: &LoyaltyLevel) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardLevelPrice(reward.id, level.clone()))
+            .unwrap_or(reward.points_cost)
+    }
+
+    /// Ensure a reward is active, inside its window and in stock
+    fn check_availability(env: &Env, reward_id: u32) -> Result<RewardAvailability, Error> {
+        let availability = Self::get_reward_availability(env, reward_id);
+        let current_time = env.ledger().timestamp();
+
+        if !availability.active
+            || current_time < availability.starts_at
+            || (availability.ends_at != 0 && current_time >= availability.ends_at)
+        {
+            return Err(Error::RewardUnavailable);
+        }
+        if availability.stock == Some(0) {
+            return Err(Error::RewardOutOfStock);
+        }
+
+        Ok(availability)
+    }
+
+    /// Create a voucher for a redemption; rewards paid on-chain are fulfilled straight away
+    fn issue_redemption(
+        env: &Env,
+        user: &Address,
+        reward_id: u32,
+        points_cost: i128,
+        spent_lots: Vec<PointsLot>,
+        status: RedemptionStatus,
+    ) -> u32 {
+        let id: u32 = env.storage().instance().get(&DataKey::TotalRedemptions).unwrap_or(0);
+        let redemption = Redemption {
+            id,
+            user: user.clone(),
+            reward_id,
+            points_cost,
+            status,
+            issued_at: env.ledger().timestamp(),
+            updated_at: env.ledger().timestamp(),
+            merchant: env.storage().instance().get(&DataKey::RewardMerchant(reward_id)),
+            spent_lots,
+        };
+        env.storage().persistent().set(&DataKey::Redemption(id), &redemption);
+        env.storage().instance().set(&DataKey::TotalRedemptions, &(id + 1));
+
+        let count = Self::get_user_redemption_count(env, user);
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserVoucher(user.clone(), count), &id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::UserVoucherCount(user.clone()), &(count + 1));
+
+        id
+    }
+
+    /// Mark an issued voucher as fulfilled; callable by the admin or the voucher's partner merchant
+    pub fn fulfill_redemption(env: &Env, caller: &Address, redemption_id: u32) -> Result<(), Error> {
+        let mut redemption = Self::get_issued_redemption(env, redemption_id)?;
+        Self::verify_merchant(env, caller, &redemption)?;
+
+        redemption.status = RedemptionStatus::Fulfilled;
+        redemption.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Redemption(redemption_id), &redemption);
+
+        env.events().publish(
+            (Symbol::new(env, "redemption_fulfilled"), redemption.user.clone()),
+            ((redemption_id, caller.clone()),),
+        );
+
+        Ok(())
+    }
+
+    /// Cancel an issued voucher, refunding the points with their original expiry and
+    /// returning the unit to stock; callable by the admin or the voucher's partner merchant
+    pub fn cancel_redemption(env: &Env, caller: &Address, redemption_id: u32) -> Result<(), Error> {
+        let mut redemption = Self::get_issued_redemption(env, redemption_id)?;
+        Self::verify_merchant(env, caller, &redemption)?;
+
+        redemption.status = RedemptionStatus::Cancelled;
+        redemption.updated_at = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Redemption(redemption_id), &redemption);
+
+        PointsManager::refund_points(
+            env,
+            &redemption.user,
+            &redemption.spent_lots,
+            symbol_short!("refund"),
+        )?;
+
+        let mut availability = Self::get_reward_availability(env, redemption.reward_id);
+        if let Some(stock) = availability.stock {
+            availability.stock = Some(stock + 1);
+            env.storage()
+                .instance()
+                .set(&DataKey::RewardAvailability(redemption.reward_id), &availability);
+        }
+
+        let redemption_key = UserRedemption(redemption.user.clone(), redemption.reward_id);
+        let user_redemptions: u32 = env.storage().persistent().get(&redemption_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&redemption_key, &user_redemptions.saturating_sub(1));
+
+        env.events().publish(
+            (Symbol::new(env, "redemption_cancelled"), redemption.user.clone()),
+            ((redemption_id, caller.clone()),),
+        );
+
+        Ok(())
+    }
+
+    /// Get a redemption voucher by ID
+    pub fn get_redemption(env: &Env, redemption_id: u32) -> Result<Redemption, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Redemption(redemption_id))
+            .ok_or(Error::RedemptionNotFound)
+    }
+
+    /// Get the number of redemption vouchers issued to a user
+    pub fn get_user_redemption_count(env: &Env, user: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserVoucherCount(user.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of a user's redemption vouchers, oldest first
+    /// `cursor` is the index of the first voucher; pass `cursor + len` to fetch the next page
+    pub fn get_user_redemptions(env: &Env, user: &Address, cursor: u32, limit: u32) -> Vec<Redemption> {
+        let end = cursor
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(Self::get_user_redemption_count(env, user));
+
+        let mut redemptions = Vec::new(env);
+        for index in cursor..end {
+            let id: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::UserVoucher(user.clone(), index));
+            if let Some(Ok(redemption)) = id.map(|id| Self::get_redemption(env, id)) {
+                redemptions.push_back(redemption);
+            }
+        }
+        redemptions
+    }
+
+    fn get_issued_redemption(env: &Env, redemption_id: u32) -> Result<Redemption, Error> {
+        let redemption = Self::get_redemption(env, redemption_id)?;
+        if redemption.status != RedemptionStatus::Issued {
+            return Err(Error::InvalidRedemptionStatus);
+        }
+        Ok(redemption)
+    }
+
+    /// Require the caller to be the admin or the voucher's partner merchant, while active
+    fn verify_merchant(env: &Env, caller: &Address, redemption: &Redemption) -> Result<(), Error> {
+        caller.require_auth();
+
+        if *caller == crate::admin::AdminModule::get_admin(env)? {
+            return Ok(());
+        }
+        if redemption.merchant.as_ref() != Some(caller) {
+            return Err(Error::Unauthorized);
+        }
+        match PartnerManager::get_partner(env, caller) {
+            Ok(partner) if partner.active => Ok(()),
+            _ => Err(Error::Unauthorized),
+        }
+    }
+
     fn calculate_discount_internal(env: &Env, purchase_amount: i128, discount_bps: u32) -> Result<i128, Error> {
         let max_redemption_bps = crate::admin::AdminModule::get_max_redemption_percentage(env);
         let discount_value = (purchase_amount * discount_bps as i128) / 10000;
@@ -153,7 +423,10 @@ impl RewardManager {
 
         for reward_id in 0..total_rewards {
             if let Ok(reward) = Self::get_reward(env, reward_id) {
-                rewards.push_back(reward);
+                // Skip inactive, out-of-window and sold-out rewards
+                if Self::check_availability(env, reward_id).is_ok() {
+                    rewards.push_back(reward);
+                }
             }
         }
 
@@ -1,13 +1,19 @@
 #![cfg(test)]
 
 use crate::{
-    types::{LevelCriteria, LevelRequirements, LoyaltyLevel, Milestone, MilestoneRequirement, Reward, RewardType},
+    types::{
+        LevelCriteria, LevelRequirements, LoyaltyLevel, Milestone, MilestoneRequirement,
+        RedemptionStatus, Reward, RewardAvailability, RewardType, TierPolicy, TokenConfig,
+        TransactionType, TransferRules,
+    },
+    Error,
     LoyaltyRewards, LoyaltyRewardsClient,
 };
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Ledger},
-    Address, Env, Symbol,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env, String, Symbol,
 };
 
 // Helper struct to setup test environment
@@ -149,6 +155,96 @@ fn test_points_expiration() {
     assert_eq!(test.client.get_lifetime_points(&user), 100);
 }
 
+#[test]
+fn test_points_lots_fifo_and_expiry() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+
+    // Lot A expires on day 30, lot B on day 100 and lot C on day 20
+    test.client.set_points_expiry(&30);
+    test.client.add_points(&user, &100, &symbol_short!("a"));
+    test.advance_time(10);
+    test.client.set_points_expiry(&90);
+    test.client.add_points(&user, &200, &symbol_short!("b"));
+    test.client.set_points_expiry(&10);
+    test.client.add_points(&user, &50, &symbol_short!("c"));
+    assert_eq!(test.client.get_points_balance(&user), 350);
+
+    // Spending consumes the soonest-to-expire lots first: all of C, then 70 from A
+    let reward = Reward {
+        id: 0, name: symbol_short!("spend"), description: Symbol::new(&test.env, "SpendReward"),
+        points_cost: 120, reward_type: RewardType::Product(symbol_short!("mug")),
+        min_level: LoyaltyLevel::Bronze, max_per_user: 0,
+    };
+    test.client.create_reward(&reward);
+    test.client.redeem_reward(&user, &0, &None);
+    assert_eq!(test.client.get_points_balance(&user), 230);
+
+    let expiring = test.client.get_expiring_points(&user, &30);
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring.get(0).unwrap().amount, 30);
+
+    // Once A expires only B is left, and the expiry pass burns the rest of A
+    test.advance_time(21);
+    assert_eq!(test.client.get_points_balance(&user), 200);
+    assert_eq!(test.client.expire_points(&user), 30);
+    assert_eq!(test.client.expire_points(&user), 0);
+    assert_eq!(test.client.get_points_balance(&user), 200);
+    assert_eq!(test.client.get_lifetime_points(&user), 350);
+}
+
+#[test]
+fn test_points_lots_merge_by_expiry_day() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+
+    // Credits of the same type expiring on the same day share one lot
+    test.env.ledger().with_mut(|li| li.timestamp = 1_000);
+    test.client.set_points_expiry(&30);
+    for _ in 0..5 {
+        test.client.add_points(&user, &10, &symbol_short!("bonus"));
+        test.env.ledger().with_mut(|li| li.timestamp += 60);
+    }
+    let lots = test.client.get_expiring_points(&user, &31);
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots.get(0).unwrap().amount, 50);
+
+    // Purchase points stay apart from bonus points, and a later day gets its own lot
+    test.client.record_purchase_points(&user, &1000, &None, &None);
+    test.advance_time(1);
+    test.client.add_points(&user, &10, &symbol_short!("bonus"));
+    let lots = test.client.get_expiring_points(&user, &32);
+    assert_eq!(lots.len(), 3);
+    assert_eq!(test.client.get_points_balance(&user), 70);
+}
+
+#[test]
+fn test_transaction_history_pagination() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+
+    for _ in 0..3 {
+        test.client.record_purchase_points(&user, &1000, &None, &None);
+    }
+    test.client.add_points(&user, &100, &symbol_short!("bonus"));
+    assert_eq!(test.client.get_transactions(&user, &0, &10).len(), 4);
+
+    // Pages follow the cursor and stop at the end of the history
+    let first_page = test.client.get_transactions(&user, &0, &3);
+    assert_eq!(first_page.len(), 3);
+    assert_eq!(first_page.get(0).unwrap().transaction_type, TransactionType::Earned);
+    let second_page = test.client.get_transactions(&user, &3, &3);
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page.get(0).unwrap().transaction_type, TransactionType::Bonus);
+    assert!(test.client.get_transactions(&user, &4, &3).is_empty());
+
+    // Page sizes are capped
+    for _ in 0..60 {
+        test.client.add_points(&user, &1, &symbol_short!("bonus"));
+    }
+    assert_eq!(test.client.get_transactions(&user, &0, &100).len(), 50);
+}
+
 #[test]
 fn test_point_limits_per_transaction() {
     let test = LoyaltyTest::setup();
@@ -167,6 +263,307 @@ fn test_point_limits_per_transaction() {
     assert_eq!(balance, points_earned);
 }
 
+#[test]
+fn test_transfer_points() {
+    let test = LoyaltyTest::setup();
+    let sender = test.create_user();
+    let friend = Address::generate(&test.env);
+
+    test.client.record_purchase_points(&sender, &40_000, &None, &None);
+    test.client.add_points(&sender, &500, &symbol_short!("promo"));
+
+    // Transfers are disabled until the admin enables them
+    let result = test.client.try_transfer_points(&sender, &friend, &100);
+    assert_eq!(result, Err(Ok(Error::TransfersDisabled)));
+
+    let rules = TransferRules {
+        enabled: true,
+        min_level: LoyaltyLevel::Bronze,
+        fee_points: 10,
+        daily_cap: 500,
+        allow_promotional: false,
+        min_amount: 10,
+    };
+    test.client.set_transfer_rules(&rules);
+    assert_eq!(test.client.get_transfer_rules(), rules);
+
+    // Only the 400 purchase points can be sent, not the promotional ones
+    let result = test.client.try_transfer_points(&sender, &friend, &450);
+    assert_eq!(result, Err(Ok(Error::InsufficientPoints)));
+    let result = test.client.try_transfer_points(&sender, &sender, &100);
+    assert_eq!(result, Err(Ok(Error::InvalidRecipient)));
+    let result = test.client.try_transfer_points(&sender, &friend, &9);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    // The recipient is registered on receipt and the fee is burned from the sender
+    test.client.transfer_points(&sender, &friend, &150);
+    assert_eq!(test.client.get_points_balance(&sender), 740);
+    assert_eq!(test.client.get_points_balance(&friend), 150);
+    assert_eq!(test.client.get_lifetime_points(&friend), 0);
+
+    let sender_history = test.client.get_transactions(&sender, &2, &10);
+    assert_eq!(sender_history.get(0).unwrap().transaction_type, TransactionType::TransferSent);
+    assert_eq!(sender_history.get(0).unwrap().amount, -150);
+    assert_eq!(sender_history.get(1).unwrap().amount, -10);
+    let friend_history = test.client.get_transactions(&friend, &0, &10);
+    assert_eq!(friend_history.get(0).unwrap().transaction_type, TransactionType::TransferReceived);
+
+    // The daily cap resets the next day
+    let result = test.client.try_transfer_points(&sender, &friend, &360);
+    assert_eq!(result, Err(Ok(Error::TransferLimitExceeded)));
+    test.advance_time(1);
+    test.client.transfer_points(&sender, &friend, &10);
+
+    // Senders can be required to reach a minimum level
+    test.client.set_transfer_rules(&TransferRules { min_level: LoyaltyLevel::Silver, ..rules });
+    let result = test.client.try_transfer_points(&sender, &friend, &10);
+    assert_eq!(result, Err(Ok(Error::InsufficientLoyaltyLevel)));
+}
+
+#[test]
+fn test_partner_merchants() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let prepaid = Address::generate(&test.env);
+    let settled = Address::generate(&test.env);
+    let token_id = test.env.register_stellar_asset_contract_v2(test.admin.clone()).address();
+    let token = TokenClient::new(&test.env, &token_id);
+    StellarAssetClient::new(&test.env, &token_id).mint(&settled, &1_000_000);
+
+    test.client.register_partner(&prepaid, &symbol_short!("cafe"), &50, &None, &0);
+    test.client.register_partner(&settled, &symbol_short!("books"), &10, &Some(token_id), &1000);
+
+    // Prepaid partners can only award points they have bought
+    let result = test.client.try_partner_award_points(&prepaid, &user, &1000);
+    assert_eq!(result, Err(Ok(Error::PartnerBudgetExceeded)));
+    assert_eq!(test.client.fund_partner_budget(&prepaid, &100), 100);
+    assert_eq!(test.client.partner_award_points(&prepaid, &user, &1000), 20);
+    assert_eq!(test.client.get_partner(&prepaid).budget, 80);
+
+    // Settled partners accrue a liability instead, at their own ratio, up to their credit limit
+    let result = test.client.try_partner_award_points(&settled, &user, &5000);
+    assert_eq!(result, Err(Ok(Error::PartnerCreditExceeded)));
+    test.client.set_partner_credit_limit(&settled, &600);
+    assert_eq!(test.client.partner_award_points(&settled, &user, &5000), 500);
+    assert_eq!(test.client.get_points_balance(&user), 520);
+    let result = test.client.try_partner_award_points(&settled, &user, &2000);
+    assert_eq!(result, Err(Ok(Error::PartnerCreditExceeded)));
+
+    // Customers can burn their points at any partner
+    test.client.partner_redeem_points(&prepaid, &user, &300);
+    assert_eq!(test.client.get_points_balance(&user), 220);
+    let account = test.client.get_partner_account(&prepaid);
+    assert_eq!((account.awarded, account.redeemed, account.net_points), (20, 300, -280));
+
+    // The settled partner pays for the points it issued
+    assert_eq!(test.client.settle_partner(&settled), 500_000);
+    assert_eq!(token.balance(&test.client.address), 500_000);
+    let account = test.client.get_partner_account(&settled);
+    assert_eq!((account.net_points, account.settled), (0, 500));
+    let result = test.client.try_settle_partner(&prepaid);
+    assert_eq!(result, Err(Ok(Error::SettlementNotConfigured)));
+
+    // The admin can collect from a partner that does not settle, through its allowance
+    assert_eq!(test.client.partner_award_points(&settled, &user, &3000), 300);
+    token.approve(&settled, &test.client.address, &300_000, &1000);
+    assert_eq!(test.client.force_settle_partner(&settled), 300_000);
+    assert_eq!(token.balance(&test.client.address), 800_000);
+    assert_eq!(test.client.get_partner_account(&settled).settled, 800);
+
+    test.client.set_partner_active(&settled, &false);
+    let result = test.client.try_partner_award_points(&settled, &user, &5000);
+    assert_eq!(result, Err(Ok(Error::PartnerInactive)));
+}
+
+#[test]
+fn test_points_token_interface() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let shop = Address::generate(&test.env);
+    let spender = Address::generate(&test.env);
+    let token = TokenClient::new(&test.env, &test.client.address);
+
+    test.client.record_purchase_points(&user, &30_000, &None, &None);
+
+    // The token interface is off until the admin enables it
+    assert_eq!(token.try_balance(&user), Err(Ok(Error::TokenModeDisabled.into())));
+    test.client.set_token_config(&TokenConfig {
+        enabled: true,
+        name: String::from_str(&test.env, "StarShop Points"),
+        symbol: String::from_str(&test.env, "SSP"),
+    });
+    assert_eq!(token.balance(&user), 300);
+    assert_eq!(token.balance(&shop), 0);
+    assert_eq!(token.decimals(), 0);
+    assert_eq!(token.symbol(), String::from_str(&test.env, "SSP"));
+
+    // Token transfers follow the transfer rules
+    assert_eq!(token.try_transfer(&user, &shop, &100), Err(Ok(Error::TransfersDisabled.into())));
+    test.client.set_transfer_rules(&TransferRules {
+        enabled: true,
+        min_level: LoyaltyLevel::Bronze,
+        fee_points: 0,
+        daily_cap: 0,
+        allow_promotional: false,
+        min_amount: 0,
+    });
+    token.transfer(&user, &shop, &100);
+    assert_eq!(token.balance(&user), 200);
+    assert_eq!(token.balance(&shop), 100);
+
+    // Spenders can burn or move points up to their allowance
+    let expiration_ledger = test.env.ledger().sequence() + 100;
+    token.approve(&user, &spender, &50, &expiration_ledger);
+    assert_eq!(token.allowance(&user, &spender), 50);
+    token.burn_from(&spender, &user, &30);
+    assert_eq!(token.balance(&user), 170);
+    assert_eq!(token.allowance(&user, &spender), 20);
+    let result = token.try_transfer_from(&spender, &user, &shop, &30);
+    assert_eq!(result, Err(Ok(Error::InsufficientAllowance.into())));
+
+    token.burn(&user, &20);
+    assert_eq!(token.balance(&user), 150);
+    assert_eq!(test.client.get_lifetime_points(&user), 300);
+}
+
+#[test]
+fn test_contract_holder_pays_out_points() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let escrow = Address::generate(&test.env);
+    let payee = Address::generate(&test.env);
+    let token = TokenClient::new(&test.env, &test.client.address);
+
+    test.client.record_purchase_points(&user, &30_000, &None, &None);
+    test.client.add_points(&user, &50, &symbol_short!("promo"));
+    test.client.set_token_config(&TokenConfig {
+        enabled: true,
+        name: String::from_str(&test.env, "StarShop Points"),
+        symbol: String::from_str(&test.env, "SSP"),
+    });
+    test.client.set_transfer_rules(&TransferRules {
+        enabled: true,
+        min_level: LoyaltyLevel::Bronze,
+        fee_points: 5,
+        daily_cap: 150,
+        allow_promotional: false,
+        min_amount: 0,
+    });
+
+    // Promotional points cannot be transferred, so they are left out of the balance
+    assert_eq!(token.balance(&user), 300);
+    token.transfer(&user, &escrow, &150);
+    assert_eq!(token.balance(&user), 145);
+    assert_eq!(token.balance(&escrow), 150);
+
+    // The escrow is held to the rules like any user until it is exempted
+    test.client.set_transfer_rules(&TransferRules {
+        enabled: true,
+        min_level: LoyaltyLevel::Silver,
+        fee_points: 5,
+        daily_cap: 150,
+        allow_promotional: false,
+        min_amount: 0,
+    });
+    let result = token.try_transfer(&escrow, &payee, &150);
+    assert_eq!(result, Err(Ok(Error::InsufficientLoyaltyLevel.into())));
+
+    // An exempt holder pays out everything it received, with no fee
+    test.client.set_transfer_exempt(&escrow, &true);
+    assert!(test.client.is_transfer_exempt(&escrow));
+    token.transfer(&escrow, &payee, &150);
+    assert_eq!(token.balance(&escrow), 0);
+    assert_eq!(token.balance(&payee), 150);
+}
+
+#[test]
+fn test_reward_inventory_and_vouchers() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let partner = Address::generate(&test.env);
+    let stranger = Address::generate(&test.env);
+    let day = 24 * 60 * 60;
+
+    test.client.create_reward(&Reward {
+        id: 0, // Ignored
+        name: symbol_short!("mug"),
+        description: Symbol::new(&test.env, "LimitedMug"),
+        points_cost: 1000,
+        reward_type: RewardType::Product(symbol_short!("mug")),
+        min_level: LoyaltyLevel::Bronze,
+        max_per_user: 0,
+    });
+    test.client.add_points(&user, &5000, &symbol_short!("bonus"));
+    let original_expiry = test.client.get_expiring_points(&user, &3650).get(0).unwrap().expiration;
+
+    // The cafe handles the mug's vouchers
+    let other_partner = Address::generate(&test.env);
+    test.client.register_partner(&partner, &symbol_short!("cafe"), &50, &None, &0);
+    test.client.register_partner(&other_partner, &symbol_short!("bakery"), &50, &None, &0);
+    test.client.set_reward_merchant(&0, &partner);
+
+    // Two mugs, available from day 10 until day 20
+    test.client.set_reward_availability(&0, &RewardAvailability {
+        active: true,
+        stock: Some(2),
+        starts_at: 10 * day,
+        ends_at: 20 * day,
+    });
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::RewardUnavailable)));
+    assert_eq!(test.client.get_available_rewards().len(), 0);
+
+    // Bronze members get a special price
+    test.advance_time(10);
+    test.client.set_reward_level_price(&0, &LoyaltyLevel::Bronze, &800);
+    assert_eq!(test.client.get_available_rewards().len(), 1);
+    test.client.redeem_reward(&user, &0, &None);
+    test.client.redeem_reward(&user, &0, &None);
+    assert_eq!(test.client.get_points_balance(&user), 3400);
+    assert_eq!(test.client.get_reward_availability(&0).stock, Some(0));
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::RewardOutOfStock)));
+
+    // Each redemption issues a voucher
+    let vouchers = test.client.get_user_redemptions(&user, &0, &10);
+    assert_eq!(vouchers.len(), 2);
+    assert_eq!(vouchers.get(1).unwrap().points_cost, 800);
+    assert_eq!(vouchers.get(1).unwrap().status, RedemptionStatus::Issued);
+
+    // Vouchers are paged from a cursor
+    let page = test.client.get_user_redemptions(&user, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, vouchers.get(1).unwrap().id);
+
+    // Only the admin or the voucher's partner merchant can process vouchers
+    assert_eq!(test.client.get_redemption(&0).merchant, Some(partner.clone()));
+    let result = test.client.try_fulfill_redemption(&stranger, &0);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = test.client.try_fulfill_redemption(&other_partner, &0);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    let result = test.client.try_cancel_redemption(&other_partner, &1);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    test.client.fulfill_redemption(&test.admin, &0);
+    assert_eq!(test.client.get_redemption(&0).status, RedemptionStatus::Fulfilled);
+    let result = test.client.try_cancel_redemption(&test.admin, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidRedemptionStatus)));
+
+    // Cancelling refunds the points with their original expiry and restocks the reward
+    test.client.cancel_redemption(&partner, &1);
+    assert_eq!(test.client.get_redemption(&1).status, RedemptionStatus::Cancelled);
+    assert_eq!(test.client.get_points_balance(&user), 4200);
+    assert_eq!(test.client.get_lifetime_points(&user), 5000);
+    assert_eq!(test.client.get_reward_availability(&0).stock, Some(1));
+    let lots = test.client.get_expiring_points(&user, &3650);
+    assert_eq!(lots.len(), 1);
+    assert_eq!(lots.get(0).unwrap().expiration, original_expiry);
+
+    // The reward closes at the end of its window
+    test.advance_time(10);
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::RewardUnavailable)));
+}
+
 // 2. Reward Redemption Tests
 
 #[test]
@@ -300,9 +697,65 @@ fn test_reward_redemption_xlm() {
     };
     test.client.create_reward(&reward);
     test.client.add_points(&user, &3000, &symbol_short!("bonus"));
+
+    // XLM is paid through its Stellar Asset Contract, which must be configured
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::NativeTokenNotConfigured)));
+    let xlm_id = test.env.register_stellar_asset_contract_v2(test.admin.clone()).address();
+    let xlm = TokenClient::new(&test.env, &xlm_id);
+    test.client.set_native_token(&xlm_id);
+
+    // An empty pool cannot pay out, and no points are taken
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::InsufficientPoolBalance)));
+    assert_eq!(test.client.get_points_balance(&user), 3000);
+
+    StellarAssetClient::new(&test.env, &xlm_id).mint(&test.admin, &15_0000000);
+    assert_eq!(test.client.fund_reward_pool(&xlm_id, &15_0000000), 15_0000000);
     test.client.redeem_reward(&user, &0, &None);
     let balance = test.client.get_points_balance(&user);
     assert_eq!(balance, 1000);
+    assert_eq!(xlm.balance(&user), 10_0000000);
+    assert_eq!(test.client.get_reward_pool(&xlm_id).balance, 5_0000000);
+    let voucher = test.client.get_redemption(&0);
+    assert_eq!(voucher.status, RedemptionStatus::Fulfilled);
+}
+
+#[test]
+fn test_token_reward_pool() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let treasury = Address::generate(&test.env);
+    let token_id = test.env.register_stellar_asset_contract_v2(test.admin.clone()).address();
+    let token = TokenClient::new(&test.env, &token_id);
+    StellarAssetClient::new(&test.env, &token_id).mint(&test.admin, &1000);
+
+    test.client.create_reward(&Reward {
+        id: 0, // Ignored
+        name: symbol_short!("token"),
+        description: Symbol::new(&test.env, "TokenReward"),
+        points_cost: 100,
+        reward_type: RewardType::Token(token_id.clone(), 300),
+        min_level: LoyaltyLevel::Bronze,
+        max_per_user: 0,
+    });
+    test.client.add_points(&user, &1000, &symbol_short!("bonus"));
+
+    test.client.fund_reward_pool(&token_id, &1000);
+    test.client.set_pool_low_balance_threshold(&token_id, &500);
+    test.client.redeem_reward(&user, &0, &None);
+    test.client.redeem_reward(&user, &0, &None);
+    assert_eq!(token.balance(&user), 600);
+    let pool = test.client.get_reward_pool(&token_id);
+    assert_eq!((pool.balance, pool.low_balance_threshold), (400, 500));
+
+    // Withdrawals are limited to what is left in the pool
+    let result = test.client.try_withdraw_reward_pool(&token_id, &500, &treasury);
+    assert_eq!(result, Err(Ok(Error::InsufficientPoolBalance)));
+    assert_eq!(test.client.withdraw_reward_pool(&token_id, &400, &treasury), 0);
+    assert_eq!(token.balance(&treasury), 400);
+    let result = test.client.try_redeem_reward(&user, &0, &None);
+    assert_eq!(result, Err(Ok(Error::InsufficientPoolBalance)));
 }
 
 // 3. Loyalty Level Tests
@@ -360,6 +813,61 @@ fn test_prevent_level_downgrade() {
     assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Silver);
 }
 
+#[test]
+fn test_tier_requalification_and_status_match() {
+    let test = LoyaltyTest::setup();
+    let user = test.create_user();
+    let day = 24 * 60 * 60;
+
+    // Levels are kept on the last 12 months of activity, checked yearly with a 30-day grace
+    test.client.set_tier_policy(&TierPolicy {
+        qualification_months: 12,
+        requalification_days: 365,
+        grace_days: 30,
+    });
+    for _ in 0..5 {
+        test.client.record_purchase_points(&user, &40_000, &None, &None);
+    }
+    test.advance_time(31);
+    assert!(test.client.check_and_update_level(&user));
+    assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Silver);
+
+    // Nothing happens before the requalification date
+    test.advance_time(200);
+    assert!(!test.client.requalify_level(&user));
+
+    // The old purchases have left the window, so the grace period starts
+    test.advance_time(170);
+    assert_eq!(test.client.get_qualifying_activity(&user).points, 0);
+    assert!(!test.client.requalify_level(&user));
+    assert_eq!(test.client.get_tier_status(&user).grace_ends_at, 431 * day);
+    assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Silver);
+
+    // Requalifying during the grace period keeps the level for another year
+    for _ in 0..5 {
+        test.client.record_purchase_points(&user, &40_000, &None, &None);
+    }
+    assert!(!test.client.requalify_level(&user));
+    let status = test.client.get_tier_status(&user);
+    assert_eq!((status.qualified_at, status.grace_ends_at), (401 * day, 0));
+
+    // Otherwise the level drops once the grace period is over
+    test.advance_time(366);
+    assert!(!test.client.requalify_level(&user));
+    test.advance_time(31);
+    assert!(test.client.requalify_level(&user));
+    assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Bronze);
+
+    // A status match grants a level until it expires
+    test.client.grant_status_match(&user, &LoyaltyLevel::Gold, &60);
+    assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Gold);
+    assert!(!test.client.requalify_level(&user));
+    test.advance_time(61);
+    assert!(test.client.requalify_level(&user));
+    assert_eq!(test.client.get_user_level(&user), LoyaltyLevel::Bronze);
+    assert_eq!(test.client.get_tier_status(&user).match_expires_at, 0);
+}
+
 #[test]
 fn test_tier_based_rewards() {
     let test = LoyaltyTest::setup();
@@ -0,0 +1,178 @@
+use crate::points::PointsManager;
+use crate::transfers::TransferManager;
+use crate::types::{AllowanceValue, DataKey, Error, TokenConfig};
+use soroban_sdk::{panic_with_error, symbol_short, Address, Env, String};
+
+/// Points have no fractional part
+const DECIMALS: u32 = 0;
+
+pub struct PointsToken;
+
+impl PointsToken {
+    /// Configure the SEP-41 token mode
+    pub fn set_token_config(env: &Env, config: TokenConfig) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        env.storage().instance().set(&DataKey::TokenConfig, &config);
+
+        Ok(())
+    }
+
+    /// Get the SEP-41 token mode settings
+    pub fn get_token_config(env: &Env) -> Result<TokenConfig, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenConfig)
+            .ok_or(Error::TokenModeDisabled)
+    }
+
+    /// Get the token settings, panicking if token mode is off
+    fn enabled_config(env: &Env) -> TokenConfig {
+        match Self::get_token_config(env) {
+            Ok(config) if config.enabled => config,
+            _ => panic_with_error!(env, Error::TokenModeDisabled),
+        }
+    }
+
+    pub fn allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+        Self::enabled_config(env);
+        Self::read_allowance(env, from, spender).amount
+    }
+
+    pub fn approve(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        Self::enabled_config(env);
+        from.require_auth();
+
+        if amount < 0 || (amount > 0 && expiration_ledger < env.ledger().sequence()) {
+            panic_with_error!(env, Error::InvalidAmount);
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        let allowance = AllowanceValue {
+            amount,
+            expiration_ledger,
+        };
+        env.storage().temporary().set(&key, &allowance);
+        if amount > 0 {
+            // Keep the allowance around until it expires
+            let live_for = expiration_ledger - env.ledger().sequence();
+            env.storage().temporary().extend_ttl(&key, live_for, live_for);
+        }
+
+        env.events().publish(
+            (symbol_short!("approve"), from.clone(), spender.clone()),
+            (amount, expiration_ledger),
+        );
+    }
+
+    /// Transferable balance: unexpired points `transfer` can move, or zero for unknown addresses
+    /// Promotional points are left out unless the transfer rules allow them or the holder is exempt
+    pub fn balance(env: &Env, id: &Address) -> i128 {
+        Self::enabled_config(env);
+        let include_bonus = TransferManager::get_transfer_rules(env).allow_promotional
+            || TransferManager::is_transfer_exempt(env, id);
+        PointsManager::get_transferable_balance(env, id, include_bonus)
+    }
+
+    /// Transfer points under the transfer rules
+    pub fn transfer(env: &Env, from: &Address, to: &Address, amount: i128) {
+        Self::enabled_config(env);
+        from.require_auth();
+
+        if let Err(error) = TransferManager::move_points(env, from, to, amount) {
+            panic_with_error!(env, error);
+        }
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone()),
+            amount,
+        );
+    }
+
+    pub fn transfer_from(
+        env: &Env,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) {
+        Self::enabled_config(env);
+        spender.require_auth();
+
+        Self::spend_allowance(env, from, spender, amount);
+        if let Err(error) = TransferManager::move_points(env, from, to, amount) {
+            panic_with_error!(env, error);
+        }
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone()),
+            amount,
+        );
+    }
+
+    /// Burn points, as when paying with them
+    pub fn burn(env: &Env, from: &Address, amount: i128) {
+        Self::enabled_config(env);
+        from.require_auth();
+
+        Self::burn_points(env, from, amount);
+    }
+
+    pub fn burn_from(env: &Env, spender: &Address, from: &Address, amount: i128) {
+        Self::enabled_config(env);
+        spender.require_auth();
+
+        Self::spend_allowance(env, from, spender, amount);
+        Self::burn_points(env, from, amount);
+    }
+
+    pub fn decimals(env: &Env) -> u32 {
+        Self::enabled_config(env);
+        DECIMALS
+    }
+
+    pub fn name(env: &Env) -> String {
+        Self::enabled_config(env).name
+    }
+
+    pub fn symbol(env: &Env) -> String {
+        Self::enabled_config(env).symbol
+    }
+
+    fn burn_points(env: &Env, from: &Address, amount: i128) {
+        if let Err(error) = PointsManager::spend_points(env, from, amount, symbol_short!("burn")) {
+            panic_with_error!(env, error);
+        }
+        env.events()
+            .publish((symbol_short!("burn"), from.clone()), amount);
+    }
+
+    /// Read an allowance, treating expired ones as zero
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        match env.storage().temporary().get::<_, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            _ => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let mut allowance = Self::read_allowance(env, from, spender);
+        if allowance.amount < amount {
+            panic_with_error!(env, Error::InsufficientAllowance);
+        }
+        if amount > 0 {
+            allowance.amount -= amount;
+            env.storage()
+                .temporary()
+                .set(&DataKey::Allowance(from.clone(), spender.clone()), &allowance);
+        }
+    }
+}
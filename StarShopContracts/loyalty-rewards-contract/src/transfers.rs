@@ -0,0 +1,193 @@
+use crate::points::PointsManager;
+use crate::types::{
+    DataKey, Error, LoyaltyLevel, PointsTransaction, TransactionType, TransferRules,
+};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+pub struct TransferManager;
+
+impl TransferManager {
+    /// Set the rules for transferring points
+    pub fn set_transfer_rules(env: &Env, rules: TransferRules) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        if rules.fee_points < 0 || rules.daily_cap < 0 || rules.min_amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DataKey::TransferRules, &rules);
+
+        Ok(())
+    }
+
+    /// Get the current transfer rules, disabled by default
+    pub fn get_transfer_rules(env: &Env) -> TransferRules {
+        env.storage()
+            .instance()
+            .get(&DataKey::TransferRules)
+            .unwrap_or(TransferRules {
+                enabled: false,
+                min_level: LoyaltyLevel::Bronze,
+                fee_points: 0,
+                daily_cap: 0,
+                allow_promotional: false,
+                min_amount: 0,
+            })
+    }
+
+    /// Exempt a holder, such as an escrow or marketplace contract, from the minimum level,
+    /// fee, daily cap and promotional restrictions, so it can pay out every point it holds
+    pub fn set_transfer_exempt(env: &Env, holder: &Address, exempt: bool) -> Result<(), Error> {
+        crate::admin::AdminModule::verify_admin(env)?;
+
+        let key = DataKey::TransferExempt(holder.clone());
+        if exempt {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        Ok(())
+    }
+
+    /// Whether a holder moves points without the transfer rules
+    pub fn is_transfer_exempt(env: &Env, holder: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TransferExempt(holder.clone()))
+            .unwrap_or(false)
+    }
+
+    /// Transfer points to another user, who is registered if needed
+    /// The points keep their original expiry, and the fee is burned from the sender
+    pub fn transfer_points(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        from.require_auth();
+        Self::move_points(env, from, to, amount)
+    }
+
+    /// Move points between users under the transfer rules, once the transfer is authorized
+    pub fn move_points(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let rules = Self::get_transfer_rules(env);
+        if !rules.enabled {
+            return Err(Error::TransfersDisabled);
+        }
+        // Dust transfers would pile up lots on the recipient
+        if amount <= 0 || amount < rules.min_amount {
+            return Err(Error::InvalidAmount);
+        }
+        if from == to {
+            return Err(Error::InvalidRecipient);
+        }
+
+        // Exempt holders skip the level, cap and fee rules and may send promotional points
+        let exempt = Self::is_transfer_exempt(env, from);
+        let fee_points = if exempt { 0 } else { rules.fee_points };
+
+        let sender_data = PointsManager::get_user_data(env, from)?;
+        if !exempt && sender_data.level < rules.min_level {
+            return Err(Error::InsufficientLoyaltyLevel);
+        }
+
+        // Enforce the daily cap on points sent, excluding fees
+        let current_time = env.ledger().timestamp();
+        let day = current_time / (24 * 60 * 60);
+        let daily_key = DataKey::DailyTransfers(from.clone(), day);
+        let sent_today: i128 = env.storage().persistent().get(&daily_key).unwrap_or(0);
+        if !exempt && rules.daily_cap > 0 && sent_today + amount > rules.daily_cap {
+            return Err(Error::TransferLimitExceeded);
+        }
+
+        if !PointsManager::user_exists(env, to) {
+            PointsManager::register_user(env, to)?;
+        }
+
+        // Take the transferred points from eligible lots, then the fee from any lot
+        PointsManager::expire_points(env, from)?;
+        let mut sender_lots = PointsManager::get_lots(env, from);
+        let include_bonus = exempt || rules.allow_promotional;
+        let transferred =
+            PointsManager::take_from_lots(env, &mut sender_lots, amount, include_bonus)?;
+        if fee_points > 0 {
+            PointsManager::take_from_lots(env, &mut sender_lots, fee_points, true)?;
+        }
+        PointsManager::set_lots(env, from, &sender_lots);
+
+        let mut recipient_lots = PointsManager::get_lots(env, to);
+        for lot in transferred.iter() {
+            PointsManager::insert_lot(&mut recipient_lots, lot);
+        }
+        PointsManager::set_lots(env, to, &recipient_lots);
+
+        env.storage()
+            .persistent()
+            .set(&daily_key, &(sent_today + amount));
+
+        // Record both sides of the transfer
+        let mut sender_data = PointsManager::get_user_data(env, from)?;
+        let sent = Self::transfer_transaction(
+            current_time,
+            -amount,
+            TransactionType::TransferSent,
+        );
+        PointsManager::record_transaction(env, from, &mut sender_data, &sent);
+        if fee_points > 0 {
+            let fee = PointsTransaction {
+                amount: -fee_points,
+                transaction_type: TransactionType::Spent,
+                timestamp: current_time,
+                description: symbol_short!("xfer_fee"),
+                expiration: 0,
+                product_id: None,
+                category: None,
+            };
+            PointsManager::record_transaction(env, from, &mut sender_data, &fee);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(from.clone()), &sender_data);
+
+        let mut recipient_data = PointsManager::get_user_data(env, to)?;
+        let received = Self::transfer_transaction(
+            current_time,
+            amount,
+            TransactionType::TransferReceived,
+        );
+        PointsManager::record_transaction(env, to, &mut recipient_data, &received);
+        env.storage()
+            .persistent()
+            .set(&DataKey::User(to.clone()), &recipient_data);
+
+        env.events().publish(
+            (Symbol::new(env, "points_transferred"), from.clone(), to.clone()),
+            ((amount, fee_points, current_time),),
+        );
+
+        Ok(())
+    }
+
+    fn transfer_transaction(
+        timestamp: u64,
+        amount: i128,
+        transaction_type: TransactionType,
+    ) -> PointsTransaction {
+        PointsTransaction {
+            amount,
+            transaction_type,
+            timestamp,
+            description: symbol_short!("transfer"),
+            expiration: 0,
+            product_id: None,
+            category: None,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracterror, contracttype, Address, Symbol, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, Vec};
 
 /// Loyalty levels with increasing benefits
 #[contracttype]
@@ -15,8 +15,11 @@ pub enum LoyaltyLevel {
 pub enum TransactionType {
     Earned,  // Points earned from purchases or activities
     Spent,   // Points spent on rewards
-    Expired, // Points that have expired (marker, not used in calculation)
+    Expired, // Points burned by the expiry pass (marker, not used in calculation)
     Bonus,   // Bonus points from milestones or promotions
+    TransferSent,     // Points sent to another user
+    TransferReceived, // Points received from another user
+    Refunded,         // Points returned for a cancelled redemption
 }
 
 /// Transaction record for points
@@ -32,6 +35,16 @@ pub struct PointsTransaction {
     pub category: Option<Symbol>,   // To track category-specific milestones
 }
 
+/// Unspent points from a single credit, all expiring at the same time
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PointsLot {
+    pub amount: i128,                      // Points left in the lot
+    pub transaction_type: TransactionType, // Whether the points were earned or a bonus
+    pub earned_at: u64,                    // When the points were credited
+    pub expiration: u64,                   // When the remaining points expire
+}
+
 /// User data containing all loyalty-related information
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -40,12 +53,79 @@ pub struct UserData {
     pub lifetime_points: i128, // Total points earned over time
     pub level: LoyaltyLevel,   // Current loyalty level
     pub level_updated_at: u64, // When user reached current level
-    pub transactions: Vec<PointsTransaction>, // History of point transactions
+    pub summary: ActivitySummary, // Rolling totals over the transaction history
     pub completed_milestones: Vec<u32>, // IDs of completed milestones
     pub join_date: u64,        // When user joined the program
     pub last_anniversary_awarded: u64, // Timestamp of the last anniversary bonus
 }
 
+/// Rolling totals kept with the user, as the full history lives in paginated storage
+#[contracttype]
+#[derive(Clone, Debug, Default)]
+pub struct ActivitySummary {
+    pub transaction_count: u32, // Transactions recorded, also the index of the next one
+    pub purchase_count: u32,    // Purchases that earned points
+    pub purchase_points: i128,  // Points earned from purchases
+    pub total_spent: i128,      // Points spent on rewards
+    pub total_expired: i128,    // Points burned after expiring
+    pub total_sent: i128,       // Points transferred to other users, excluding fees
+    pub total_received: i128,   // Points received from other users
+    pub last_activity: u64,     // When the latest transaction was recorded
+}
+
+/// Admin-set rules for transferring points between users
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRules {
+    pub enabled: bool,             // Whether transfers are allowed at all
+    pub min_level: LoyaltyLevel,   // Minimum level required to send points
+    pub fee_points: i128,          // Points burned from the sender on each transfer
+    pub daily_cap: i128,           // Max points a user can send per day (0 for unlimited)
+    pub allow_promotional: bool,   // Whether bonus points can be transferred
+    pub min_amount: i128,          // Smallest number of points a single transfer may send
+}
+
+/// Partner merchant allowed to award and redeem loyalty points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Partner {
+    pub address: Address,
+    pub name: Symbol,
+    pub points_ratio: u32,                 // Purchase amount units per point awarded
+    pub budget: i128,                      // Prepaid points left to award (prepaid partners)
+    pub settlement_token: Option<Address>, // Token liabilities are settled in, if not prepaid
+    pub point_value: i128,                 // Settlement token units per point
+    pub credit_limit: i128,                // Unsettled points a settled partner may award
+    pub active: bool,                      // Whether the partner can award or redeem points
+}
+
+/// Running points accounting between the program and a partner
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PartnerAccount {
+    pub awarded: i128,    // Points the partner has awarded to customers
+    pub redeemed: i128,   // Points customers have redeemed at the partner
+    pub net_points: i128, // Unsettled balance: positive if the partner owes the program
+    pub settled: i128,    // Net points settled so far
+}
+
+/// Settings for exposing spendable points as a SEP-41 token
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenConfig {
+    pub enabled: bool,  // Whether the token interface is active
+    pub name: String,   // Token name reported to wallets
+    pub symbol: String, // Token symbol reported to wallets
+}
+
+/// Amount a spender may move on behalf of a user, until a ledger
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
 /// Milestone achievement criteria and rewards
 #[contracttype]
 #[derive(Clone)]
@@ -86,6 +166,33 @@ pub struct LevelCriteria {
     pub min_days_active: u64,  // Minimum days as member
 }
 
+/// Admin-set rules for keeping a loyalty level
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierPolicy {
+    pub qualification_months: u32, // Rolling window of qualifying activity in 30-day months (0 for lifetime totals)
+    pub requalification_days: u64,  // How long a level lasts before it must be requalified (0 for never)
+    pub grace_days: u64,            // Time to requalify after failing, before the downgrade
+}
+
+/// Level qualification state of a user
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierStatus {
+    pub qualified_at: u64,                   // When the current level was last (re)qualified
+    pub grace_ends_at: u64,                  // End of the grace period (0 if not in grace)
+    pub matched_level: LoyaltyLevel,         // Level granted by a status match
+    pub match_expires_at: u64,               // When the status match ends (0 for no match)
+}
+
+/// Qualifying points and purchases, per 30-day month or summed over the window
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QualifyingActivity {
+    pub points: i128,   // Earned and bonus points
+    pub purchases: u32, // Purchases that earned points
+}
+
 /// Reward item that can be redeemed with points
 #[contracttype]
 #[derive(Clone)]
@@ -109,6 +216,48 @@ pub enum RewardType {
     Token(Address, i128), // Other token address and amount
 }
 
+/// Inventory and availability window of a reward
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RewardAvailability {
+    pub active: bool,       // Whether the reward can be redeemed
+    pub stock: Option<u32>, // Units left, None for unlimited
+    pub starts_at: u64,     // When the reward becomes available
+    pub ends_at: u64,       // When the reward stops being available (0 for no end)
+}
+
+/// Lifecycle of a redemption voucher
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RedemptionStatus {
+    Issued,    // Redeemed, waiting for fulfilment
+    Fulfilled, // Delivered by a merchant
+    Cancelled, // Cancelled, points refunded
+}
+
+/// Voucher created for each reward redemption
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Redemption {
+    pub id: u32,
+    pub user: Address,
+    pub reward_id: u32,
+    pub points_cost: i128, // Points paid for the reward
+    pub status: RedemptionStatus,
+    pub issued_at: u64,
+    pub updated_at: u64,
+    pub merchant: Option<Address>, // Partner merchant that handles the voucher, admin only if None
+    pub spent_lots: Vec<PointsLot>, // Lots the points came from, restored if cancelled
+}
+
+/// Funds set aside for paying out XLM and token rewards
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RewardPool {
+    pub balance: i128,               // Amount available for payouts
+    pub low_balance_threshold: i128, // Balance below which a low-balance event is emitted
+}
+
 /// Storage keys for contract data
 #[contracttype]
 pub enum DataKey {
@@ -125,6 +274,29 @@ pub enum DataKey {
     TotalMilestones,            // Counter for total milestones
     TotalRewards,               // Counter for total rewards
     UserRedemption(UserRedemption), // Tracks (user, reward) redemption count
+    PointsLots(Address),        // User's unspent point lots, soonest to expire first
+    Transaction(Address, u32),  // User's transaction history, by index
+    PurchasedProduct(Address, Symbol), // Whether the user has bought a product
+    PurchasedCategory(Address, Symbol), // Whether the user has bought in a category
+    TransferRules,              // Rules for point transfers
+    TransferExempt(Address),    // Contract holder that moves points without the transfer rules
+    DailyTransfers(Address, u64), // Points a user sent on a given day
+    Partner(Address),           // Partner merchant configuration
+    PartnerAccount(Address),    // Partner merchant points accounting
+    TokenConfig,                // SEP-41 token mode settings
+    Allowance(Address, Address), // Points a spender may move for a user
+    RewardAvailability(u32),    // Reward inventory and availability window
+    RewardLevelPrice(u32, LoyaltyLevel), // Reward price for a loyalty level
+    RewardMerchant(u32),        // Partner merchant that handles a reward's vouchers
+    Redemption(u32),            // Redemption voucher
+    UserVoucher(Address, u32),  // IDs of a user's redemption vouchers, by index
+    UserVoucherCount(Address),  // Number of vouchers issued to a user
+    TotalRedemptions,           // Counter for redemption vouchers
+    NativeToken,                // Stellar Asset Contract address of native XLM
+    RewardPool(Address),        // Reward payout pool for a token
+    TierPolicy,                 // Rules for keeping a loyalty level
+    TierStatus(Address),        // User's level qualification state
+    QualifyingActivity(Address, u64), // User's qualifying activity in a 30-day month
 }
 
 /// Key for tracking user redemptions of a specific reward
@@ -156,4 +328,20 @@ pub enum Error {
     CategoryNotFound = 16,         // Category not found
     MilestoneNotEligible = 17,     // Milestone not eligible
     RewardLimitReached = 18,       // Reward limit reached
+    TransfersDisabled = 19,        // Point transfers are turned off
+    TransferLimitExceeded = 20,    // Transfer exceeds the daily cap
+    InvalidRecipient = 21,         // Cannot transfer points to oneself
+    PartnerNotFound = 22,          // Partner merchant not registered
+    PartnerInactive = 23,          // Partner merchant is deactivated
+    PartnerBudgetExceeded = 24,    // Award exceeds the partner's prepaid budget
+    SettlementNotConfigured = 25,  // Partner has no settlement token
+    TokenModeDisabled = 26,        // SEP-41 token interface is turned off
+    InsufficientAllowance = 27,    // Spender allowance too low or expired
+    RewardUnavailable = 28,        // Reward inactive or outside its window
+    RewardOutOfStock = 29,         // No units of the reward left
+    RedemptionNotFound = 30,       // Redemption voucher not found
+    InvalidRedemptionStatus = 31,  // Voucher is no longer issued
+    NativeTokenNotConfigured = 32, // No XLM asset contract set for XLM rewards
+    InsufficientPoolBalance = 33,  // Reward pool cannot cover the payout
+    PartnerCreditExceeded = 34,    // Award exceeds the partner's unsettled credit limit
 }
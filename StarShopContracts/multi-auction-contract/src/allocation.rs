@@ -0,0 +1,85 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::*;
+
+/// Outcome of clearing a multi-unit auction.
+pub struct Settlement {
+    pub allocations: Vec<Allocation>,  // Units awarded per filled bid
+    pub refunds: Vec<(Address, i128)>, // Escrow returned per bid
+    pub proceeds: i128,                // Total owed to the seller
+}
+
+/// Returns the lowest winning unit price once every listed unit is bid for.
+///
+/// `bids` must be ordered by unit price, highest first. While units remain
+/// unclaimed there is no clearing price and `None` is returned.
+pub fn clearing_price(multi_unit: &MultiUnitAuctionData, bids: &Vec<MultiUnitBid>) -> Option<i128> {
+    let mut remaining = multi_unit.quantity;
+
+    for bid in bids.iter() {
+        if bid.quantity >= remaining {
+            return Some(bid.unit_price);
+        }
+
+        remaining -= bid.quantity;
+    }
+
+    None
+}
+
+/// Fills the highest bids first until the listed units run out.
+///
+/// `bids` must be ordered by unit price, highest first. A bid that only
+/// partially fits is filled for the remaining units and the rest of its
+/// escrow is refunded.
+pub fn settle_multi_unit(
+    env: &Env,
+    multi_unit: &MultiUnitAuctionData,
+    bids: &Vec<MultiUnitBid>,
+) -> Settlement {
+    // Work out how many units each bid receives
+    let mut fills: Vec<u32> = Vec::new(env);
+    let mut remaining = multi_unit.quantity;
+    let mut clearing_price = 0i128;
+
+    for bid in bids.iter() {
+        let filled = bid.quantity.min(remaining);
+        if filled > 0 {
+            clearing_price = bid.unit_price; // lowest accepted unit price so far
+        }
+
+        remaining -= filled;
+        fills.push_back(filled);
+    }
+
+    let mut settlement = Settlement {
+        allocations: Vec::new(env),
+        refunds: Vec::new(env),
+        proceeds: 0,
+    };
+
+    for (bid, filled) in bids.iter().zip(fills.iter()) {
+        let price_paid = match multi_unit.pricing {
+            PricingRule::Uniform => clearing_price,
+            PricingRule::PayAsBid => bid.unit_price,
+        };
+
+        let amount_paid = price_paid * filled as i128;
+        let refund = bid.unit_price * bid.quantity as i128 - amount_paid;
+
+        if filled > 0 {
+            settlement.allocations.push_back(Allocation {
+                bidder: bid.bidder.clone(),
+                quantity: filled,
+                unit_price: price_paid,
+            });
+            settlement.proceeds += amount_paid;
+        }
+
+        if refund > 0 {
+            settlement.refunds.push_back((bid.bidder, refund));
+        }
+    }
+
+    settlement
+}
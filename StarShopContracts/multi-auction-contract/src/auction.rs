@@ -1,9 +1,17 @@
-use crate::event::{AuctionCanceled, AuctionCompleted, AuctionCreated, NewBidPlaced};
+use crate::allocation::{clearing_price, settle_multi_unit};
+use crate::event::{
+    AuctionCanceled, AuctionCompleted, AuctionCreated, NewBidPlaced, NewMultiUnitBidPlaced,
+    PauseAuthoritySet, PauseUpdated, PlatformFeeUpdated, RefundWithdrawn,
+};
 use crate::traits::AuctionTrait;
-use crate::{bid::record_bid, errors::AuctionError};
+use crate::{
+    bid::{record_bid, record_multi_unit_bid},
+    errors::{AuctionError, ConditionError, ValidationError},
+};
 use crate::{distribution, types::*};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, panic_with_error, Address, Env, IntoVal, Symbol, Val,
+    contract, contractimpl, contracttype, panic_with_error, vec, Address, Env, IntoVal, Symbol,
+    Val, Vec,
 };
 
 #[contract]
@@ -13,9 +21,49 @@ pub struct AuctionContract;
 #[contracttype]
 #[derive(Clone)]
 enum DataKey {
-    TotalAuctions,        // Key for storing total number of auctions
-    Auction(u32),         // Key for storing a specific Auction by its internal ID
-    HasBid(Address, u32), // Key for storing if a user has made a bid for a specific auction
+    TotalAuctions,            // Key for storing total number of auctions
+    Auction(u32),             // Key for storing a specific Auction by its internal ID
+    HasBid(Address, u32),     // Key for storing if a user has made a bid for a specific auction
+    Refund(Address, Address), // Key for storing a bidder's withdrawable balance per token
+    MultiUnitBids(u32),       // Key for storing the ordered bids of a multi-unit auction
+    AuctionBid(u32, u32),     // Key for storing a bid in an auction's history by its bid ID
+    IndexLen(IndexKey),       // Key for storing the number of auction IDs in an index
+    IndexPage(IndexKey, u32), // Key for storing a page of auction IDs in an index
+    ActivePosition(u32),      // Key for storing an active auction's position in the active index
+    EndingPosition(u32),      // Key for storing an active auction's position in its end time bucket
+    EndingBuckets,            // Key for storing the end time buckets holding active auctions, in order
+    Governance,               // Key for storing the governance address
+    PlatformFee,              // Key for storing the platform fee configuration
+    Paused(PauseScope),       // Key for storing the timestamp a pause lasts until
+    PausedByAuthority(PauseScope), // Key for storing whether a pause was set by the pause authority
+    PauseAuthority,           // Key for storing the address allowed to pause besides governance
+}
+
+/// Enum representing the auction ID indexes, kept in pages in persistent storage.
+#[contracttype]
+#[derive(Clone)]
+enum IndexKey {
+    Owner(Address),        // Auctions created by an owner
+    Bidder(Address),       // Auctions a user has bid on
+    Status(AuctionStatus), // Auctions with a given status
+    EndingIn(u64),         // Active auctions ending within an end time bucket
+}
+
+/// Number of auction IDs stored in each index page.
+const INDEX_PAGE_SIZE: u32 = 50;
+
+/// Length in seconds of the end time buckets active auctions are grouped into.
+const END_TIME_BUCKET: u64 = 60 * 60;
+
+#[contractimpl]
+impl AuctionContract {
+    /// Sets the governance address allowed to manage platform settings at deployment,
+    /// so it cannot be claimed by whoever calls the contract first.
+    pub fn __constructor(env: Env, governance: Address) {
+        env.storage()
+            .instance()
+            .set::<Val, Address>(&DataKey::Governance.into_val(&env), &governance);
+    }
 }
 
 #[contractimpl]
@@ -29,6 +77,7 @@ impl AuctionTrait for AuctionContract {
         auction_conditions: AuctionConditions,
     ) {
         owner.require_auth(); // Ensure caller is authenticated as the auction creator
+        Self::_require_not_paused(&env, "create_auction");
 
         // Validate the item metadata (e.g., name, description, etc.)
         item_metadata.validate_item_data(&env);
@@ -36,6 +85,20 @@ impl AuctionTrait for AuctionContract {
         // Validate custom auction conditions (e.g., end time, floor price)
         auction_conditions.validate_conditions(&env);
 
+        // Lock in the platform fee so later fee changes don't affect this auction
+        let mut auction_conditions = auction_conditions;
+        let default_fee_bps = Self::_get_platform_fee(&env)
+            .map(|platform_fee| platform_fee.fee_bps)
+            .unwrap_or(0);
+
+        match auction_conditions.platform_fee_bps {
+            Some(fee_bps) if fee_bps < default_fee_bps => {
+                panic_with_error!(&env, ValidationError::PlatformFeeBelowDefault)
+            }
+            Some(_) => {}
+            None => auction_conditions.platform_fee_bps = Some(default_fee_bps),
+        }
+
         // Fetch the current number of auctions
         let total_auctions = Self::_get_total_auctions(&env);
         let auction_id = total_auctions + 1;
@@ -62,7 +125,11 @@ impl AuctionTrait for AuctionContract {
         Self::_save_auction(&env, auction_id, &auction);
 
         // Update total auctions count
-        Self::_save_total_auctions(&env, &total_auctions);
+        Self::_save_total_auctions(&env, &auction_id);
+
+        // Index the auction by owner and status
+        Self::_index_owner_auction(&env, &owner, auction_id);
+        Self::_index_active_auction(&env, &auction);
 
         // Emit Auction Created event
         env.events().publish(
@@ -79,6 +146,7 @@ impl AuctionTrait for AuctionContract {
     /// Places a bid on an active auction.
     fn make_bid(env: Env, auction_id: u32, bidder: Address, bid_amount: i128) {
         bidder.require_auth(); // Ensure bidder is authenticated
+        Self::_require_not_paused(&env, "make_bid");
 
         let auction_data = Self::_get_auction(&env, auction_id);
 
@@ -97,8 +165,21 @@ impl AuctionTrait for AuctionContract {
             panic_with_error!(&env, AuctionError::AuctionCompleted);
         }
 
+        // Multi-unit auctions take a quantity with every bid
+        if let AuctionType::MultiUnit(_) = auction_data.auction_conditions.auction_type {
+            panic_with_error!(&env, ConditionError::MultiUnitAuctionRequiresQuantity);
+        }
+
         // Perform the bid and update state
-        record_bid(&env, &mut auction_data, bidder.clone(), bid_amount);
+        let outbid = record_bid(&env, &mut auction_data, bidder.clone(), bid_amount);
+
+        // Credit the outbid bidder so they can withdraw their funds later
+        if let Some((prev_bidder, prev_bid_amount)) = outbid {
+            Self::_credit_refund(&env, &prev_bidder, &auction_data.token, prev_bid_amount);
+        }
+
+        // Append the bid to the auction's bid history
+        Self::_save_bid(&env, auction_id, &auction_data, &bidder, bid_amount, 1);
 
         // Check if bidder is a new participant
         if !Self::_has_bid(&env, &bidder, &auction_id) {
@@ -120,6 +201,85 @@ impl AuctionTrait for AuctionContract {
         );
     }
 
+    /// Places a per-unit bid for a quantity of units on an active multi-unit auction.
+    fn make_multi_unit_bid(
+        env: Env,
+        auction_id: u32,
+        bidder: Address,
+        unit_price: i128,
+        quantity: u32,
+    ) {
+        bidder.require_auth(); // Ensure bidder is authenticated
+        Self::_require_not_paused(&env, "make_multi_unit_bid");
+
+        let auction_data = Self::_get_auction(&env, auction_id);
+
+        if auction_data.is_none() {
+            panic_with_error!(&env, AuctionError::AuctionNotFound); // Auction must exist
+        }
+
+        let mut auction_data = auction_data.unwrap();
+
+        // Reject if the auction was canceled or already completed
+        if auction_data.is_canceled() {
+            panic_with_error!(&env, AuctionError::AuctionCanceled);
+        }
+
+        if auction_data.is_completed() {
+            panic_with_error!(&env, AuctionError::AuctionCompleted);
+        }
+
+        // Perform the bid and keep the bid ladder ordered
+        let mut bids = Self::_get_multi_unit_bids(&env, auction_id);
+        let displaced = record_multi_unit_bid(
+            &env,
+            &mut auction_data,
+            &mut bids,
+            bidder.clone(),
+            unit_price,
+            quantity,
+        );
+
+        // Credit the displaced bidders so they can withdraw their funds later
+        for (prev_bidder, prev_bid_amount) in displaced.iter() {
+            Self::_credit_refund(&env, &prev_bidder, &auction_data.token, prev_bid_amount);
+        }
+
+        // Append the bid to the auction's bid history
+        Self::_save_bid(
+            &env,
+            auction_id,
+            &auction_data,
+            &bidder,
+            unit_price,
+            quantity,
+        );
+
+        // Check if bidder is a new participant
+        if !Self::_has_bid(&env, &bidder, &auction_id) {
+            Self::_register_user_bid(&env, &bidder, &auction_id);
+            auction_data.no_of_participants += 1;
+        }
+
+        // Save updated auction state
+        Self::_save_multi_unit_bids(&env, auction_id, &bids);
+        Self::_save_auction(&env, auction_id, &auction_data);
+
+        // Emit New Multi-Unit Bid event
+        env.events().publish(
+            (
+                Symbol::new(&env, "new_multi_unit_bid_placed"),
+                bidder.clone(),
+            ),
+            NewMultiUnitBidPlaced {
+                auction_id,
+                bidder,
+                unit_price,
+                quantity,
+            },
+        );
+    }
+
     /// Cancels an active auction if it's still cancelable.
     fn cancel_auction(env: Env, auction_id: u32) {
         let auction_data = Self::_get_auction(&env, auction_id);
@@ -150,6 +310,7 @@ impl AuctionTrait for AuctionContract {
         auction_data.auction_status = AuctionStatus::Cancelled;
 
         Self::_save_auction(&env, auction_id, &auction_data);
+        Self::_index_closed_auction(&env, &auction_data);
 
         // Emit Auction Canceled event
         env.events().publish(
@@ -166,7 +327,10 @@ impl AuctionTrait for AuctionContract {
     }
 
     /// Ends an auction if its end conditions are met.
+    /// Anyone can settle an auction, so a winner's funds are never locked by an absent seller.
     fn end_auction(env: Env, auction_id: u32) {
+        Self::_require_not_paused(&env, "end_auction");
+
         let auction_data = Self::_get_auction(&env, auction_id);
 
         if auction_data.is_none() {
@@ -175,8 +339,6 @@ impl AuctionTrait for AuctionContract {
 
         let mut auction_data = auction_data.unwrap();
 
-        auction_data.owner.require_auth(); // Only owner can end the auction
-
         // Prevent double-ending or ending a canceled auction
         if auction_data.is_canceled() {
             panic_with_error!(&env, AuctionError::AuctionCanceled);
@@ -187,22 +349,55 @@ impl AuctionTrait for AuctionContract {
         }
 
         // Check if any of the custom end conditions are met
-        auction_data.check_can_end(&env);
+        let current_price = match &auction_data.auction_conditions.auction_type {
+            AuctionType::MultiUnit(multi_unit) => {
+                clearing_price(multi_unit, &Self::_get_multi_unit_bids(&env, auction_id))
+            }
+            _ => auction_data.curr_bid_amount,
+        };
+        auction_data.check_can_end(&env, current_price);
+
+        let mut allocations = Vec::new(&env);
 
-        // If there is a current bidder, transfer the item and update owner
-        if let Some(curr_bidder) = &auction_data.curr_bidder {
+        if let AuctionType::MultiUnit(multi_unit) = &auction_data.auction_conditions.auction_type {
+            // Fill the top bids and refund whatever was not spent
+            let bids = Self::_get_multi_unit_bids(&env, auction_id);
+            let settlement = settle_multi_unit(&env, multi_unit, &bids);
+
+            for (bidder, refund) in settlement.refunds.iter() {
+                Self::_credit_refund(&env, &bidder, &auction_data.token, refund);
+            }
+
+            // Credit proceeds to the fee recipient, co-sellers and seller
+            if settlement.proceeds > 0 {
+                Self::_credit_proceeds(
+                    &env,
+                    &auction_data,
+                    &auction_data.owner,
+                    settlement.proceeds,
+                );
+                auction_data.curr_bid_amount = Some(settlement.proceeds);
+            }
+
+            allocations = settlement.allocations;
+        } else if let Some(curr_bidder) = &auction_data.curr_bidder {
+            // If there is a current bidder, transfer the item and update owner
             let bid_amount = auction_data.curr_bid_amount.expect("Missing bid amount");
 
             let prev_owner = auction_data.owner;
             auction_data.owner = curr_bidder.clone();
 
-            // Transfer bid amount from contract to seller
-            distribution::transfer_from_contract(
+            // Credit the bid amount to the fee recipient, co-sellers and seller
+            Self::_credit_proceeds(&env, &auction_data, &prev_owner, bid_amount);
+
+            allocations = vec![
                 &env,
-                &auction_data.token,
-                &prev_owner,
-                &bid_amount,
-            );
+                Allocation {
+                    bidder: curr_bidder.clone(),
+                    quantity: 1,
+                    unit_price: bid_amount,
+                },
+            ];
         }
 
         // Update auction status to completed
@@ -210,6 +405,7 @@ impl AuctionTrait for AuctionContract {
 
         // Save updated auction state
         Self::_save_auction(&env, auction_id, &auction_data);
+        Self::_index_closed_auction(&env, &auction_data);
 
         env.events().publish(
             (
@@ -220,6 +416,7 @@ impl AuctionTrait for AuctionContract {
                 auction_id,
                 winner: auction_data.curr_bidder,
                 final_price: auction_data.curr_bid_amount,
+                allocations,
                 timestamp: env.ledger().timestamp(),
             },
         );
@@ -228,9 +425,335 @@ impl AuctionTrait for AuctionContract {
     fn get_auction(env: Env, auction_id: u32) -> Option<Auction> {
         Self::_get_auction(&env, auction_id)
     }
+
+    /// Withdraws the bidder's refundable balance for the given token.
+    fn withdraw(env: Env, bidder: Address, token: Address) -> i128 {
+        bidder.require_auth(); // Only the bidder can withdraw their refunds
+        Self::_require_not_paused(&env, "withdraw");
+
+        let amount = Self::_get_refund(&env, &bidder, &token);
+
+        if amount <= 0 {
+            panic_with_error!(&env, AuctionError::NothingToWithdraw);
+        }
+
+        // Clear the balance before transferring out
+        Self::_remove_refund(&env, &bidder, &token);
+
+        distribution::transfer_from_contract(&env, &token, &bidder, &amount);
+
+        env.events().publish(
+            (Symbol::new(&env, "refund_withdrawn"), bidder.clone()),
+            RefundWithdrawn {
+                bidder,
+                token,
+                amount,
+            },
+        );
+
+        amount
+    }
+
+    fn get_withdrawable_balance(env: Env, bidder: Address, token: Address) -> i128 {
+        Self::_get_refund(&env, &bidder, &token)
+    }
+
+    /// Updates the default platform fee charged on new auctions.
+    fn set_platform_fee(env: Env, recipient: Address, fee_bps: u32) {
+        let governance = env
+            .storage()
+            .instance()
+            .get::<Val, Address>(&DataKey::Governance.into_val(&env));
+
+        match governance {
+            Some(governance) => governance.require_auth(), // Only governance can update fees
+            None => panic_with_error!(&env, AuctionError::NotInitialized),
+        }
+
+        if fee_bps > BPS_DENOMINATOR {
+            panic_with_error!(&env, ValidationError::InvalidFeeBasisPoints);
+        }
+
+        let platform_fee = PlatformFee { recipient, fee_bps };
+
+        env.storage()
+            .instance()
+            .set::<Val, PlatformFee>(&DataKey::PlatformFee.into_val(&env), &platform_fee);
+
+        env.events().publish(
+            (Symbol::new(&env, "platform_fee_updated"),),
+            PlatformFeeUpdated {
+                recipient: platform_fee.recipient,
+                fee_bps,
+            },
+        );
+    }
+
+    fn get_platform_fee(env: Env) -> Option<PlatformFee> {
+        Self::_get_platform_fee(&env)
+    }
+
+    /// Appoints the address allowed to pause besides governance, such as a security council.
+    /// Governance appoints the first one; after that only the current authority can hand over.
+    fn set_pause_authority(env: Env, caller: Address, authority: Address) {
+        match Self::_get_pause_authority(&env) {
+            Some(current) if current != caller => {
+                panic_with_error!(&env, AuctionError::Unauthorized)
+            }
+            Some(_) => caller.require_auth(),
+            None => Self::_require_governance(&env, &caller),
+        }
+
+        env.storage()
+            .instance()
+            .set::<Val, Address>(&DataKey::PauseAuthority.into_val(&env), &authority);
+
+        env.events().publish(
+            (Symbol::new(&env, "pause_authority_set"),),
+            PauseAuthoritySet { authority },
+        );
+    }
+
+    /// Pauses the whole contract or a single function until `until`, after which it lifts itself.
+    fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) {
+        Self::_require_pauser(&env, &caller);
+
+        // A pause must end in the future, and no later than MAX_PAUSE_DURATION from now
+        let now = env.ledger().timestamp();
+        if until <= now || until - now > MAX_PAUSE_DURATION {
+            panic_with_error!(&env, AuctionError::InvalidPauseDuration);
+        }
+
+        // Governance cannot shorten or extend a pause the authority put in place
+        let by_authority = Self::_get_pause_authority(&env).as_ref() == Some(&caller);
+        if !by_authority && Self::_is_authority_pause(&env, &scope) {
+            panic_with_error!(&env, AuctionError::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set::<Val, u64>(&DataKey::Paused(scope.clone()).into_val(&env), &until);
+        env.storage().instance().set::<Val, bool>(
+            &DataKey::PausedByAuthority(scope.clone()).into_val(&env),
+            &by_authority,
+        );
+
+        env.events().publish(
+            (Symbol::new(&env, "pause_updated"),),
+            PauseUpdated {
+                scope,
+                until: Some(until),
+            },
+        );
+    }
+
+    /// Lifts a pause before it expires.
+    fn unpause(env: Env, caller: Address, scope: PauseScope) {
+        Self::_require_pauser(&env, &caller);
+
+        // Nor can it lift one early
+        if Self::_get_pause_authority(&env).as_ref() != Some(&caller)
+            && Self::_is_authority_pause(&env, &scope)
+        {
+            panic_with_error!(&env, AuctionError::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .remove::<Val>(&DataKey::Paused(scope.clone()).into_val(&env));
+        env.storage()
+            .instance()
+            .remove::<Val>(&DataKey::PausedByAuthority(scope.clone()).into_val(&env));
+
+        env.events().publish(
+            (Symbol::new(&env, "pause_updated"),),
+            PauseUpdated { scope, until: None },
+        );
+    }
+
+    fn is_paused(env: Env, function: Symbol) -> bool {
+        Self::_is_scope_paused(&env, PauseScope::Contract)
+            || Self::_is_scope_paused(&env, PauseScope::Function(function))
+    }
+
+    /// Previews the price of a Dutch auction at the given timestamp.
+    fn get_price_at(env: Env, auction_id: u32, timestamp: u64) -> i128 {
+        let auction_data = Self::_get_auction(&env, auction_id);
+
+        if auction_data.is_none() {
+            panic_with_error!(&env, AuctionError::AuctionNotFound);
+        }
+
+        let auction_data = auction_data.unwrap();
+
+        match auction_data
+            .auction_conditions
+            .get_item_price_at(auction_data.start_time, timestamp)
+        {
+            Some(price) => price,
+            None => panic_with_error!(&env, ConditionError::NotDutchAuction),
+        }
+    }
+
+    /// Returns a page of an auction's bid history, oldest bid first.
+    fn get_auction_bids(env: Env, auction_id: u32, start: u32, limit: u32) -> Vec<Bid> {
+        let mut bids = Vec::new(&env);
+
+        let auction_data = match Self::_get_auction(&env, auction_id) {
+            Some(auction_data) => auction_data,
+            None => return bids,
+        };
+
+        // Bid IDs run from 1 to no_of_bids
+        let end = start.saturating_add(limit).min(auction_data.no_of_bids);
+        for bid_id in start.saturating_add(1)..=end {
+            if let Some(bid) = Self::_get_bid(&env, auction_id, bid_id) {
+                bids.push_back(bid);
+            }
+        }
+
+        bids
+    }
+
+    /// Returns a page of the auctions created by an owner.
+    fn get_auctions_by_owner(env: Env, owner: Address, start: u32, limit: u32) -> Vec<Auction> {
+        let auction_ids = Self::_get_index_range(&env, &IndexKey::Owner(owner), start, limit);
+        Self::_load_auctions(&env, &auction_ids)
+    }
+
+    /// Returns a page of the auctions with the given status.
+    fn get_auctions_by_status(
+        env: Env,
+        status: AuctionStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Auction> {
+        let auction_ids = Self::_get_index_range(&env, &IndexKey::Status(status), start, limit);
+        Self::_load_auctions(&env, &auction_ids)
+    }
+
+    /// Returns a page of active auctions, ending soonest first.
+    fn get_auctions_ending_soonest(env: Env, start: u32, limit: u32) -> Vec<Auction> {
+        let mut auctions: Vec<Auction> = Vec::new(&env);
+        let mut skipped = 0u32;
+
+        // Buckets are kept in end time order; skip whole buckets before the page starts
+        for bucket in Self::_get_ending_buckets(&env).iter() {
+            if auctions.len() >= limit {
+                break;
+            }
+
+            let key = IndexKey::EndingIn(bucket);
+            let len = Self::_get_index_len(&env, &key);
+            let offset = start.saturating_sub(skipped);
+            skipped = skipped.saturating_add(len);
+            if offset >= len {
+                continue;
+            }
+
+            // Only the buckets the page covers are loaded and ordered by end time
+            let bucket_ids = Self::_get_index_range(&env, &key, 0, len);
+            let mut bucket_auctions: Vec<Auction> = Vec::new(&env);
+            for auction_data in Self::_load_auctions(&env, &bucket_ids).iter() {
+                let end_time = auction_data.auction_conditions.end_time;
+                let position = bucket_auctions
+                    .iter()
+                    .position(|active| active.auction_conditions.end_time > end_time)
+                    .map(|index| index as u32)
+                    .unwrap_or(bucket_auctions.len());
+                bucket_auctions.insert(position, auction_data);
+            }
+
+            for auction_data in bucket_auctions.iter().skip(offset as usize) {
+                if auctions.len() >= limit {
+                    break;
+                }
+                auctions.push_back(auction_data);
+            }
+        }
+
+        auctions
+    }
+
+    /// Returns a bidder's positions for a page of the auctions they have bid on,
+    /// leaving out auctions that are no longer active.
+    fn get_bidder_positions(env: Env, bidder: Address, start: u32, limit: u32) -> Vec<BidPosition> {
+        let mut positions = Vec::new(&env);
+
+        let key = IndexKey::Bidder(bidder.clone());
+        let auction_ids = Self::_get_index_range(&env, &key, start, limit);
+        for auction_data in Self::_load_auctions(&env, &auction_ids).iter() {
+            if auction_data.auction_status == AuctionStatus::Active {
+                positions.push_back(Self::_get_position(&env, &auction_data, &bidder));
+            }
+        }
+
+        positions
+    }
 }
 
 impl AuctionContract {
+    /// Internal helper to ensure the caller is the governance address.
+    fn _require_governance(env: &Env, caller: &Address) {
+        let governance = env
+            .storage()
+            .instance()
+            .get::<Val, Address>(&DataKey::Governance.into_val(env));
+
+        match governance {
+            Some(governance) if governance == *caller => caller.require_auth(),
+            Some(_) => panic_with_error!(env, AuctionError::Unauthorized),
+            None => panic_with_error!(env, AuctionError::NotInitialized),
+        }
+    }
+
+    /// Internal helper to ensure the caller is governance or the pause authority.
+    fn _require_pauser(env: &Env, caller: &Address) {
+        if Self::_get_pause_authority(env).as_ref() == Some(caller) {
+            caller.require_auth();
+            return;
+        }
+        Self::_require_governance(env, caller);
+    }
+
+    /// Internal helper to fetch the pause authority, if governance has appointed one.
+    fn _get_pause_authority(env: &Env) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get::<Val, Address>(&DataKey::PauseAuthority.into_val(env))
+    }
+
+    /// Internal helper to check if a pause scope is in effect and was set by the pause authority.
+    fn _is_authority_pause(env: &Env, scope: &PauseScope) -> bool {
+        let by_authority = env
+            .storage()
+            .instance()
+            .get::<Val, bool>(&DataKey::PausedByAuthority(scope.clone()).into_val(env))
+            .unwrap_or(false);
+
+        by_authority && Self::_is_scope_paused(env, scope.clone())
+    }
+
+    /// Internal helper to check if a pause scope is still in effect.
+    fn _is_scope_paused(env: &Env, scope: PauseScope) -> bool {
+        let until = env
+            .storage()
+            .instance()
+            .get::<Val, u64>(&DataKey::Paused(scope).into_val(env));
+
+        match until {
+            Some(until) => env.ledger().timestamp() < until,
+            None => false,
+        }
+    }
+
+    /// Internal helper to panic if a function is paused.
+    fn _require_not_paused(env: &Env, function: &str) {
+        if Self::is_paused(env.clone(), Symbol::new(env, function)) {
+            panic_with_error!(env, AuctionError::ContractPaused);
+        }
+    }
+
     /// Internal helper to fetch a auction from storage.
     fn _get_auction(env: &Env, auction_id: u32) -> Option<Auction> {
         env.storage()
@@ -245,6 +768,18 @@ impl AuctionContract {
             .set::<Val, Auction>(&DataKey::Auction(auction_id).into_val(env), auction);
     }
 
+    /// Internal helper to get the platform fee configuration.
+    fn _get_platform_fee(env: &Env) -> Option<PlatformFee> {
+        env.storage()
+            .instance()
+            .get::<Val, PlatformFee>(&DataKey::PlatformFee.into_val(env))
+    }
+
+    /// Internal helper to get the current platform fee recipient.
+    fn _get_fee_recipient(env: &Env) -> Option<Address> {
+        Self::_get_platform_fee(env).map(|platform_fee| platform_fee.recipient)
+    }
+
     /// Internal helper to get total number of auctions
     fn _get_total_auctions(env: &Env) -> u32 {
         env.storage()
@@ -273,6 +808,317 @@ impl AuctionContract {
             &DataKey::HasBid(bidder.clone(), auction_id.clone()).into_val(env),
             &true,
         );
+
+        // Index the auction under the bidder for position queries
+        Self::_push_index(env, &IndexKey::Bidder(bidder.clone()), *auction_id);
+    }
+
+    /// Internal helper to get a bidder's withdrawable balance for a token.
+    fn _get_refund(env: &Env, bidder: &Address, token: &Address) -> i128 {
+        env.storage()
+            .instance()
+            .get::<Val, i128>(&DataKey::Refund(bidder.clone(), token.clone()).into_val(env))
+            .unwrap_or(0)
+    }
+
+    /// Internal helper to add an outbid amount to a bidder's withdrawable balance.
+    fn _credit_refund(env: &Env, bidder: &Address, token: &Address, amount: i128) {
+        let balance = Self::_get_refund(env, bidder, token) + amount;
+
+        env.storage().instance().set::<Val, i128>(
+            &DataKey::Refund(bidder.clone(), token.clone()).into_val(env),
+            &balance,
+        );
+    }
+
+    /// Internal helper to credit auction proceeds to each party's withdrawable balance.
+    fn _credit_proceeds(env: &Env, auction_data: &Auction, seller: &Address, amount: i128) {
+        let payouts = distribution::split_proceeds(
+            env,
+            seller,
+            &amount,
+            &auction_data.auction_conditions,
+            Self::_get_fee_recipient(env),
+        );
+
+        for (recipient, payout) in payouts.iter() {
+            Self::_credit_refund(env, &recipient, &auction_data.token, payout);
+        }
+    }
+
+    /// Internal helper to clear a bidder's withdrawable balance for a token.
+    fn _remove_refund(env: &Env, bidder: &Address, token: &Address) {
+        env.storage()
+            .instance()
+            .remove::<Val>(&DataKey::Refund(bidder.clone(), token.clone()).into_val(env));
+    }
+
+    /// Internal helper to get the ordered bids of a multi-unit auction.
+    fn _get_multi_unit_bids(env: &Env, auction_id: u32) -> Vec<MultiUnitBid> {
+        env.storage()
+            .persistent()
+            .get::<Val, Vec<MultiUnitBid>>(&DataKey::MultiUnitBids(auction_id).into_val(env))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Internal helper to save the ordered bids of a multi-unit auction.
+    fn _save_multi_unit_bids(env: &Env, auction_id: u32, bids: &Vec<MultiUnitBid>) {
+        env.storage()
+            .persistent()
+            .set::<Val, Vec<MultiUnitBid>>(&DataKey::MultiUnitBids(auction_id).into_val(env), bids);
+    }
+
+    /// Internal helper to fetch a bid from an auction's history.
+    fn _get_bid(env: &Env, auction_id: u32, bid_id: u32) -> Option<Bid> {
+        env.storage()
+            .persistent()
+            .get::<Val, Bid>(&DataKey::AuctionBid(auction_id, bid_id).into_val(env))
+    }
+
+    /// Internal helper to append the latest bid to an auction's history.
+    fn _save_bid(
+        env: &Env,
+        auction_id: u32,
+        auction_data: &Auction,
+        bidder: &Address,
+        bid_amount: i128,
+        quantity: u32,
+    ) {
+        // The bid was already counted, so its ID is the current bid count
+        let bid = Bid {
+            id: auction_data.no_of_bids,
+            bidder: bidder.clone(),
+            bid_amount,
+            quantity,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .persistent()
+            .set::<Val, Bid>(&DataKey::AuctionBid(auction_id, bid.id).into_val(env), &bid);
+    }
+
+    /// Internal helper to get the number of auction IDs in an index.
+    fn _get_index_len(env: &Env, key: &IndexKey) -> u32 {
+        env.storage()
+            .persistent()
+            .get::<Val, u32>(&DataKey::IndexLen(key.clone()).into_val(env))
+            .unwrap_or(0)
+    }
+
+    /// Internal helper to get one page of an index.
+    fn _get_index_page(env: &Env, key: &IndexKey, page: u32) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get::<Val, Vec<u32>>(&DataKey::IndexPage(key.clone(), page).into_val(env))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Internal helper to save one page of an index, removing it once empty.
+    fn _save_index_page(env: &Env, key: &IndexKey, page: u32, auction_ids: &Vec<u32>) {
+        let page_key = DataKey::IndexPage(key.clone(), page).into_val(env);
+        if auction_ids.is_empty() {
+            env.storage().persistent().remove::<Val>(&page_key);
+        } else {
+            env.storage()
+                .persistent()
+                .set::<Val, Vec<u32>>(&page_key, auction_ids);
+        }
+    }
+
+    /// Internal helper to save the number of auction IDs in an index.
+    fn _save_index_len(env: &Env, key: &IndexKey, len: u32) {
+        env.storage()
+            .persistent()
+            .set::<Val, u32>(&DataKey::IndexLen(key.clone()).into_val(env), &len);
+    }
+
+    /// Internal helper to read a range of an index, loading only the pages it covers.
+    fn _get_index_range(env: &Env, key: &IndexKey, start: u32, limit: u32) -> Vec<u32> {
+        let mut auction_ids = Vec::new(env);
+        let end = start
+            .saturating_add(limit)
+            .min(Self::_get_index_len(env, key));
+
+        let mut position = start;
+        while position < end {
+            let page = position / INDEX_PAGE_SIZE;
+            let page_ids = Self::_get_index_page(env, key, page);
+            let page_end = end.min((page + 1) * INDEX_PAGE_SIZE);
+            for index in position..page_end {
+                auction_ids.push_back(page_ids.get_unchecked(index % INDEX_PAGE_SIZE));
+            }
+            position = page_end;
+        }
+
+        auction_ids
+    }
+
+    /// Internal helper to append an auction ID to an index, returning its position.
+    fn _push_index(env: &Env, key: &IndexKey, auction_id: u32) -> u32 {
+        let position = Self::_get_index_len(env, key);
+        let page = position / INDEX_PAGE_SIZE;
+
+        let mut page_ids = Self::_get_index_page(env, key, page);
+        page_ids.push_back(auction_id);
+        Self::_save_index_page(env, key, page, &page_ids);
+        Self::_save_index_len(env, key, position + 1);
+
+        position
+    }
+
+    /// Internal helper to remove the auction ID at a position by moving the last ID into it.
+    /// Returns the moved auction ID, if any.
+    fn _swap_remove_index(env: &Env, key: &IndexKey, position: u32) -> Option<u32> {
+        let last = Self::_get_index_len(env, key) - 1;
+        let last_page = last / INDEX_PAGE_SIZE;
+        let mut last_page_ids = Self::_get_index_page(env, key, last_page);
+        let last_id = last_page_ids.pop_back_unchecked();
+
+        let moved = if position == last {
+            None
+        } else if position / INDEX_PAGE_SIZE == last_page {
+            last_page_ids.set(position % INDEX_PAGE_SIZE, last_id);
+            Some(last_id)
+        } else {
+            let page = position / INDEX_PAGE_SIZE;
+            let mut page_ids = Self::_get_index_page(env, key, page);
+            page_ids.set(position % INDEX_PAGE_SIZE, last_id);
+            Self::_save_index_page(env, key, page, &page_ids);
+            Some(last_id)
+        };
+
+        Self::_save_index_page(env, key, last_page, &last_page_ids);
+        Self::_save_index_len(env, key, last);
+
+        moved
+    }
+
+    /// Internal helper to index an auction under its owner.
+    fn _index_owner_auction(env: &Env, owner: &Address, auction_id: u32) {
+        Self::_push_index(env, &IndexKey::Owner(owner.clone()), auction_id);
+    }
+
+    /// Internal helper to add a new auction to the active index and its end time bucket.
+    /// Both indexes are unordered; each auction's positions are stored so it can be
+    /// removed without scanning them.
+    fn _index_active_auction(env: &Env, auction: &Auction) {
+        let position = Self::_push_index(env, &IndexKey::Status(AuctionStatus::Active), auction.id);
+        env.storage().persistent().set::<Val, u32>(
+            &DataKey::ActivePosition(auction.id).into_val(env),
+            &position,
+        );
+
+        let bucket = auction.auction_conditions.end_time / END_TIME_BUCKET;
+        let position = Self::_push_index(env, &IndexKey::EndingIn(bucket), auction.id);
+        env.storage().persistent().set::<Val, u32>(
+            &DataKey::EndingPosition(auction.id).into_val(env),
+            &position,
+        );
+
+        // The first auction in a bucket opens it in the ordered bucket list
+        if position == 0 {
+            let mut buckets = Self::_get_ending_buckets(env);
+            let index = buckets
+                .iter()
+                .position(|open| open > bucket)
+                .map(|index| index as u32)
+                .unwrap_or(buckets.len());
+            buckets.insert(index, bucket);
+            Self::_save_ending_buckets(env, &buckets);
+        }
+    }
+
+    /// Internal helper to move an auction from the active indexes to its final status index.
+    fn _index_closed_auction(env: &Env, auction: &Auction) {
+        let position_key = DataKey::ActivePosition(auction.id).into_val(env);
+        if let Some(position) = env.storage().persistent().get::<Val, u32>(&position_key) {
+            let active_key = IndexKey::Status(AuctionStatus::Active);
+            if let Some(moved_id) = Self::_swap_remove_index(env, &active_key, position) {
+                env.storage()
+                    .persistent()
+                    .set::<Val, u32>(&DataKey::ActivePosition(moved_id).into_val(env), &position);
+            }
+            env.storage().persistent().remove::<Val>(&position_key);
+        }
+
+        let position_key = DataKey::EndingPosition(auction.id).into_val(env);
+        if let Some(position) = env.storage().persistent().get::<Val, u32>(&position_key) {
+            let bucket = auction.auction_conditions.end_time / END_TIME_BUCKET;
+            let ending_key = IndexKey::EndingIn(bucket);
+            if let Some(moved_id) = Self::_swap_remove_index(env, &ending_key, position) {
+                env.storage()
+                    .persistent()
+                    .set::<Val, u32>(&DataKey::EndingPosition(moved_id).into_val(env), &position);
+            }
+            env.storage().persistent().remove::<Val>(&position_key);
+
+            // Close the bucket once its last auction leaves it
+            if Self::_get_index_len(env, &ending_key) == 0 {
+                let mut buckets = Self::_get_ending_buckets(env);
+                if let Some(index) = buckets.first_index_of(bucket) {
+                    buckets.remove(index);
+                    Self::_save_ending_buckets(env, &buckets);
+                }
+            }
+        }
+
+        Self::_push_index(env, &IndexKey::Status(auction.auction_status.clone()), auction.id);
+    }
+
+    /// Internal helper to get the end time buckets holding active auctions, in order.
+    fn _get_ending_buckets(env: &Env) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get::<Val, Vec<u64>>(&DataKey::EndingBuckets.into_val(env))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Internal helper to save the end time buckets holding active auctions.
+    fn _save_ending_buckets(env: &Env, buckets: &Vec<u64>) {
+        env.storage()
+            .persistent()
+            .set::<Val, Vec<u64>>(&DataKey::EndingBuckets.into_val(env), buckets);
+    }
+
+    /// Internal helper to load the auctions for a list of IDs.
+    fn _load_auctions(env: &Env, auction_ids: &Vec<u32>) -> Vec<Auction> {
+        let mut auctions = Vec::new(env);
+
+        for auction_id in auction_ids.iter() {
+            if let Some(auction_data) = Self::_get_auction(env, auction_id) {
+                auctions.push_back(auction_data);
+            }
+        }
+
+        auctions
+    }
+
+    /// Internal helper to work out a bidder's position in an active auction.
+    fn _get_position(env: &Env, auction_data: &Auction, bidder: &Address) -> BidPosition {
+        let (is_leading, current_price) = match &auction_data.auction_conditions.auction_type {
+            AuctionType::MultiUnit(multi_unit) => {
+                // Leading if the bidder would be allocated any units right now,
+                // priced at the clearing price
+                let bids = Self::_get_multi_unit_bids(env, auction_data.id);
+                let is_leading = settle_multi_unit(env, multi_unit, &bids)
+                    .allocations
+                    .iter()
+                    .any(|allocation| allocation.bidder == *bidder);
+
+                (is_leading, clearing_price(multi_unit, &bids))
+            }
+            _ => (
+                auction_data.curr_bidder.as_ref() == Some(bidder),
+                auction_data.curr_bid_amount,
+            ),
+        };
+
+        BidPosition {
+            auction_id: auction_data.id,
+            is_leading,
+            current_price,
+        }
     }
 
     /// Internal helper to check if a user has bid for an auction
@@ -1,35 +1,103 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
-use crate::{distribution, types::*};
+use crate::{allocation, distribution, errors::ConditionError, types::*};
 
+/// Records a new bid and returns the outbid bidder and amount, if any.
+///
+/// The outbid amount stays in the contract and must be credited to the
+/// bidder's withdrawable balance by the caller.
 pub fn record_bid(
     env: &Env,
     auction_data: &mut Auction,
     new_bidder: Address,
     new_bid_amount: i128,
-) {
+) -> Option<(Address, i128)> {
     // Check if user can bid
     auction_data.check_can_bid(env, &new_bid_amount);
 
     // Transfer tokens to contract
     distribution::transfer_to_contract(&env, &auction_data.token, &new_bidder, &new_bid_amount);
 
-    // Check if prev bidder and refund
-    if let Some(prev_bidder) = &auction_data.curr_bidder {
+    // Check if prev bidder to be refunded
+    let outbid = auction_data.curr_bidder.clone().map(|prev_bidder| {
         let prev_bid_amount = auction_data
             .curr_bid_amount
             .expect("Missing previous bid amount");
 
-        distribution::transfer_from_contract(
-            &env,
-            &auction_data.token,
-            &prev_bidder,
-            &prev_bid_amount,
-        );
-    }
+        (prev_bidder, prev_bid_amount)
+    });
 
     // Update curr_bids and increment bid_count
     auction_data.curr_bidder = Option::Some(new_bidder.clone());
     auction_data.curr_bid_amount = Option::Some(new_bid_amount.clone());
     auction_data.no_of_bids += 1;
+
+    outbid
+}
+
+/// Records a per-unit bid on a multi-unit auction and returns the bids it
+/// displaced.
+///
+/// The full `unit_price * quantity` is escrowed, and the bid is inserted into
+/// `bids` ordered by unit price (highest first), keeping earlier bids ahead of
+/// later bids at the same price. Bids that no longer win any unit are removed
+/// from `bids`, so the ladder never holds more bids than are needed to cover
+/// the listed units; their escrow must be credited back by the caller.
+pub fn record_multi_unit_bid(
+    env: &Env,
+    auction_data: &mut Auction,
+    bids: &mut Vec<MultiUnitBid>,
+    new_bidder: Address,
+    unit_price: i128,
+    quantity: u32,
+) -> Vec<(Address, i128)> {
+    // Check if user can bid, judging the target price by the clearing price
+    let multi_unit = match &auction_data.auction_conditions.auction_type {
+        AuctionType::MultiUnit(multi_unit) => multi_unit.clone(),
+        _ => panic_with_error!(&env, ConditionError::NotMultiUnitAuction),
+    };
+    let clearing_price = allocation::clearing_price(&multi_unit, bids);
+    auction_data.check_can_bid_multi_unit(env, &unit_price, quantity, clearing_price);
+
+    // Escrow the whole bid in the contract
+    let escrow_amount = unit_price * quantity as i128;
+    distribution::transfer_to_contract(env, &auction_data.token, &new_bidder, &escrow_amount);
+
+    // Find the position after every bid with an equal or higher unit price
+    let mut position = bids.len();
+    for (index, bid) in bids.iter().enumerate() {
+        if bid.unit_price < unit_price {
+            position = index as u32;
+            break;
+        }
+    }
+
+    bids.insert(
+        position,
+        MultiUnitBid {
+            bidder: new_bidder,
+            unit_price,
+            quantity,
+        },
+    );
+
+    auction_data.no_of_bids += 1;
+
+    // Drop the bids left past the point where every unit is covered
+    let mut displaced = Vec::new(env);
+    let mut remaining = multi_unit.quantity;
+    let mut index = 0;
+    while index < bids.len() {
+        let bid = bids.get_unchecked(index);
+
+        if remaining == 0 {
+            displaced.push_back((bid.bidder, bid.unit_price * bid.quantity as i128));
+            bids.remove(index);
+        } else {
+            remaining -= bid.quantity.min(remaining);
+            index += 1;
+        }
+    }
+
+    displaced
 }
@@ -39,6 +39,13 @@ impl Auction {
     }
 
     pub fn check_can_bid(&self, env: &Env, bid_amount: &i128) {
+        self.check_bid_conditions(env, bid_amount, self.curr_bid_amount)
+    }
+
+    /// Checks a bid against the auction conditions, evaluating the target
+    /// price against `current_price` (the clearing price for multi-unit
+    /// auctions, the current bid otherwise).
+    fn check_bid_conditions(&self, env: &Env, bid_amount: &i128, current_price: Option<i128>) {
         let conditions = &self.auction_conditions;
 
         // Check if the auction has ended based on the current time
@@ -56,9 +63,9 @@ impl Auction {
 
         // Check if the target price has been reached
         if let Some(target_price) = conditions.on_target_price {
-            if let Some(curr_bid_amount) = self.curr_bid_amount {
+            if let Some(curr_bid_amount) = current_price {
                 match conditions.auction_type {
-                    AuctionType::Regular => {
+                    AuctionType::Regular | AuctionType::MultiUnit(_) => {
                         if curr_bid_amount >= target_price {
                             panic_with_error!(&env, ConditionError::TargetPriceReached);
                         }
@@ -159,10 +166,50 @@ impl Auction {
                     panic_with_error!(&env, ConditionError::BidMustMatchDutchPrice)
                 }
             }
+            AuctionType::MultiUnit(_) => {
+                // Multi-unit auction: unit price must be higher than the starting price,
+                // and higher than the clearing price once every unit is bid for
+                if starting_price >= bid_amount {
+                    panic_with_error!(&env, ConditionError::BidMustBeHigherThanStartingPrice)
+                }
+
+                if let Some(clearing_price) = current_price {
+                    if clearing_price >= bid_amount {
+                        panic_with_error!(&env, ConditionError::BidMustBeHigherThanMaxBid)
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn check_can_bid_multi_unit(
+        &self,
+        env: &Env,
+        unit_price: &i128,
+        quantity: u32,
+        clearing_price: Option<i128>,
+    ) {
+        // Only multi-unit auctions accept quantity bids
+        let multi_unit = match &self.auction_conditions.auction_type {
+            AuctionType::MultiUnit(multi_unit) => multi_unit,
+            _ => panic_with_error!(&env, ConditionError::NotMultiUnitAuction),
+        };
+
+        if quantity == 0 {
+            panic_with_error!(&env, ConditionError::BidQuantityMustBeGreaterThanZero)
+        }
+
+        // A single bid cannot ask for more units than are listed
+        if quantity > multi_unit.quantity {
+            panic_with_error!(&env, ConditionError::BidQuantityExceedsAvailableUnits)
         }
+
+        self.check_bid_conditions(env, unit_price, clearing_price);
     }
 
-    pub fn check_can_end(&self, env: &Env) {
+    /// Checks whether the auction may be ended now. `current_price` is the
+    /// clearing price for multi-unit auctions and the current bid otherwise.
+    pub fn check_can_end(&self, env: &Env, current_price: Option<i128>) {
         let conditions = &self.auction_conditions;
         let current_time = env.ledger().timestamp();
 
@@ -181,9 +228,9 @@ impl Auction {
 
         // Check if the target price has been reached
         if let Some(target_price) = conditions.on_target_price {
-            if let Some(curr_bid_amount) = self.curr_bid_amount {
+            if let Some(curr_bid_amount) = current_price {
                 match conditions.auction_type {
-                    AuctionType::Regular => {
+                    AuctionType::Regular | AuctionType::MultiUnit(_) => {
                         if curr_bid_amount < target_price {
                             panic_with_error!(&env, ConditionError::TargetPriceNotReached);
                         } else {
@@ -272,20 +319,102 @@ impl DutchAuctionData {
     ) -> i128 {
         let current_time = env.ledger().timestamp();
 
-        // If the current time is past the end time, return the floor price
-        if current_time >= end_time {
+        self.calculate_price_at(start_price, start_time, end_time, current_time)
+    }
+
+    pub fn calculate_price_at(
+        &self,
+        start_price: i128,
+        start_time: u64,
+        end_time: u64,
+        timestamp: u64,
+    ) -> i128 {
+        // If the timestamp is past the end time, return the floor price
+        if timestamp >= end_time {
             return self.floor_price;
         }
 
-        // Calculate the price decrease based on elapsed time
+        // Nothing has decayed before the auction starts
+        if timestamp <= start_time {
+            return start_price;
+        }
+
         let duration = end_time - start_time;
-        let elapsed = current_time - start_time;
+        let elapsed = timestamp - start_time;
         let price_diff = start_price - self.floor_price;
 
-        let price_decrease = (price_diff as i128 * elapsed as i128) / duration as i128;
+        let price = match &self.curve {
+            PriceCurve::Linear => start_price - price_diff * elapsed as i128 / duration as i128,
+            PriceCurve::Exponential(half_life) => {
+                // Halve the distance to the floor once per half-life
+                let halvings = elapsed / half_life;
+                if halvings >= 127 {
+                    return self.floor_price;
+                }
+
+                let diff = price_diff >> halvings;
+
+                // Interpolate linearly within the current half-life
+                let remainder = (elapsed % half_life) as i128;
+                let diff = diff - (diff - (diff >> 1)) * remainder / *half_life as i128;
+
+                self.floor_price + diff
+            }
+            PriceCurve::Stepwise(step_seconds) => {
+                // Only drop at the start of each step
+                let stepped = elapsed - elapsed % step_seconds;
+                start_price - price_diff * stepped as i128 / duration as i128
+            }
+            PriceCurve::Piecewise(schedule) => {
+                // Use the latest scheduled price at or before the timestamp
+                let mut price = start_price;
+                for point in schedule.iter() {
+                    if point.timestamp > timestamp {
+                        break;
+                    }
+                    price = point.price;
+                }
+                price
+            }
+        };
+
+        // Never drop below the floor price
+        price.max(self.floor_price)
+    }
+
+    pub fn validate_curve(&self, env: &Env, start_price: i128) {
+        match &self.curve {
+            PriceCurve::Linear => {}
+            PriceCurve::Exponential(interval) | PriceCurve::Stepwise(interval) => {
+                if *interval == 0 {
+                    panic_with_error!(
+                        &env,
+                        ValidationError::DutchCurveIntervalMustBeGreaterThanZero
+                    );
+                }
+            }
+            PriceCurve::Piecewise(schedule) => {
+                if schedule.is_empty() {
+                    panic_with_error!(&env, ValidationError::DutchPriceScheduleInvalid);
+                }
+
+                // Timestamps must increase and prices must fall within [floor, start]
+                let mut prev: Option<PricePoint> = None;
+                for point in schedule.iter() {
+                    if point.price < self.floor_price || point.price > start_price {
+                        panic_with_error!(&env, ValidationError::DutchPriceScheduleInvalid);
+                    }
+
+                    if let Some(prev) = prev {
+                        if point.timestamp <= prev.timestamp || point.price > prev.price {
+                            panic_with_error!(&env, ValidationError::DutchPriceScheduleInvalid);
+                        }
+                    }
 
-        // Return the current price
-        start_price - price_decrease
+                    prev = Some(point);
+                }
+            }
+        }
     }
 }
 
@@ -349,6 +478,31 @@ impl AuctionConditions {
             }
         }
 
+        // Validate settlement fees and proceeds splits
+        if let Some(platform_fee_bps) = self.platform_fee_bps {
+            if platform_fee_bps > BPS_DENOMINATOR {
+                panic_with_error!(&env, ValidationError::InvalidFeeBasisPoints);
+            }
+        }
+
+        if let Some(proceeds_recipients) = &self.proceeds_recipients {
+            if proceeds_recipients.len() > MAX_PROCEEDS_RECIPIENTS {
+                panic_with_error!(&env, ValidationError::TooManyProceedsRecipients);
+            }
+
+            let mut total_share_bps = 0u32;
+            for share in proceeds_recipients.iter() {
+                if share.share_bps == 0 {
+                    panic_with_error!(&env, ValidationError::InvalidFeeBasisPoints);
+                }
+                total_share_bps = total_share_bps.saturating_add(share.share_bps);
+            }
+
+            if total_share_bps > BPS_DENOMINATOR {
+                panic_with_error!(&env, ValidationError::ProceedsSharesExceedTotal);
+            }
+        }
+
         // Validate Dutch auction-specific conditions
         if let AuctionType::Dutch(dutch_auction) = &self.auction_type {
             if dutch_auction.floor_price == 0 {
@@ -357,6 +511,18 @@ impl AuctionConditions {
                     ValidationError::DutchAuctionFloorPriceMustBeGreaterThanZero
                 );
             }
+
+            dutch_auction.validate_curve(env, self.starting_price);
+        }
+
+        // Validate multi-unit auction-specific conditions
+        if let AuctionType::MultiUnit(multi_unit) = &self.auction_type {
+            if multi_unit.quantity == 0 {
+                panic_with_error!(
+                    &env,
+                    ValidationError::MultiUnitQuantityMustBeGreaterThanZero
+                );
+            }
         }
     }
 
@@ -368,4 +534,18 @@ impl AuctionConditions {
             0
         }
     }
+
+    // Only useful for previewing dutch auction prices
+    pub fn get_item_price_at(&self, start_time: u64, timestamp: u64) -> Option<i128> {
+        if let AuctionType::Dutch(dutch_data) = &self.auction_type {
+            Some(dutch_data.calculate_price_at(
+                self.starting_price,
+                start_time,
+                self.end_time,
+                timestamp,
+            ))
+        } else {
+            None
+        }
+    }
 }
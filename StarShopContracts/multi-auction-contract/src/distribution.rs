@@ -1,4 +1,6 @@
-use soroban_sdk::{token, Address, Env};
+use soroban_sdk::{token, Address, Env, Vec};
+
+use crate::types::{AuctionConditions, BPS_DENOMINATOR};
 
 // Transfer tokens from contract
 pub fn transfer_from_contract(env: &Env, token: &Address, to: &Address, amount: &i128) {
@@ -9,3 +11,46 @@ pub fn transfer_from_contract(env: &Env, token: &Address, to: &Address, amount:
 pub fn transfer_to_contract(env: &Env, token: &Address, from: &Address, amount: &i128) {
     token::Client::new(env, token).transfer(from, &env.current_contract_address(), amount);
 }
+
+// Split auction proceeds: the platform fee first, then each recipient's share
+// of what is left, and the remainder to the seller. Returns what each party is
+// owed; the caller credits it to their withdrawable balance, so one party that
+// cannot receive tokens never blocks settlement
+pub fn split_proceeds(
+    env: &Env,
+    seller: &Address,
+    amount: &i128,
+    conditions: &AuctionConditions,
+    fee_recipient: Option<Address>,
+) -> Vec<(Address, i128)> {
+    let mut payouts = Vec::new(env);
+    let mut remaining = *amount;
+
+    // Platform fee
+    if let (Some(fee_bps), Some(fee_recipient)) = (conditions.platform_fee_bps, fee_recipient) {
+        let fee = amount * fee_bps as i128 / BPS_DENOMINATOR as i128;
+        if fee > 0 {
+            payouts.push_back((fee_recipient, fee));
+            remaining -= fee;
+        }
+    }
+
+    // Co-sellers and royalties share the proceeds after the fee
+    let net_amount = remaining;
+    if let Some(proceeds_recipients) = &conditions.proceeds_recipients {
+        for share in proceeds_recipients.iter() {
+            let share_amount = net_amount * share.share_bps as i128 / BPS_DENOMINATOR as i128;
+            if share_amount > 0 {
+                payouts.push_back((share.recipient, share_amount));
+                remaining -= share_amount;
+            }
+        }
+    }
+
+    // Seller receives the rest
+    if remaining > 0 {
+        payouts.push_back((seller.clone(), remaining));
+    }
+
+    payouts
+}
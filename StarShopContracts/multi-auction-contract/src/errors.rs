@@ -15,6 +15,13 @@ pub enum ValidationError {
     EndTimeInPast = 111,
     DutchAuctionFloorPriceMustBeGreaterThanZero = 112,
     MaximumParticipantsMustBeGreaterThanZero = 113,
+    MultiUnitQuantityMustBeGreaterThanZero = 114,
+    DutchCurveIntervalMustBeGreaterThanZero = 115,
+    DutchPriceScheduleInvalid = 116,
+    InvalidFeeBasisPoints = 117,
+    TooManyProceedsRecipients = 118,
+    ProceedsSharesExceedTotal = 119,
+    PlatformFeeBelowDefault = 120,
 }
 
 #[contracterror]
@@ -25,6 +32,12 @@ pub enum AuctionError {
     AuctionCanceled = 202,
     AuctionCompleted = 203,
     CannotCancelAuction = 204,
+    NothingToWithdraw = 205,
+    AlreadyInitialized = 206,
+    NotInitialized = 207,
+    Unauthorized = 208,
+    ContractPaused = 209,
+    InvalidPauseDuration = 210,
 }
 
 #[contracterror]
@@ -52,4 +65,9 @@ pub enum ConditionError {
     MinNumParticipantsNotReached = 319,
     MaxNumParticipantsNotReached = 320,
     NoBidsRegisteredYet = 321,
+    BidQuantityMustBeGreaterThanZero = 322,
+    BidQuantityExceedsAvailableUnits = 323,
+    MultiUnitAuctionRequiresQuantity = 324,
+    NotMultiUnitAuction = 325,
+    NotDutchAuction = 326,
 }
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::types::{Allocation, PauseScope};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -17,6 +19,15 @@ pub struct NewBidPlaced {
     pub bid_amount: i128,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewMultiUnitBidPlaced {
+    pub auction_id: u32,
+    pub bidder: Address,
+    pub unit_price: i128,
+    pub quantity: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AuctionCanceled {
@@ -31,5 +42,34 @@ pub struct AuctionCompleted {
     pub auction_id: u32,
     pub winner: Option<Address>,
     pub final_price: Option<i128>,
+    pub allocations: Vec<Allocation>,
     pub timestamp: u64,
 }
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundWithdrawn {
+    pub bidder: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformFeeUpdated {
+    pub recipient: Address,
+    pub fee_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseAuthoritySet {
+    pub authority: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseUpdated {
+    pub scope: PauseScope,
+    pub until: Option<u64>,
+}
@@ -1,5 +1,6 @@
 #![no_std]
 
+pub mod allocation;
 pub mod auction;
 pub mod bid;
 pub mod checks;
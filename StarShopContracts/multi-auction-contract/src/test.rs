@@ -2,16 +2,20 @@
 
 // use super::*;
 use crate::auction::{AuctionContract, AuctionContractClient};
-use crate::types::{AuctionStatus, AuctionType, DutchAuctionData, ItemMetadata};
-use soroban_sdk::testutils::{Address as _, Ledger};
+use crate::types::{
+    AuctionStatus, AuctionType, DutchAuctionData, ItemMetadata, MultiUnitAuctionData, PauseScope,
+    PriceCurve, PricePoint, PricingRule, ProceedsShare, MAX_PAUSE_DURATION,
+};
+use soroban_sdk::testutils::{Address as _, IssuerFlags, Ledger, MockAuth, MockAuthInvoke};
 use soroban_sdk::token::{StellarAssetClient, TokenClient};
-use soroban_sdk::{token, Address, Env, String};
+use soroban_sdk::{token, vec, Address, Env, IntoVal, String, Symbol};
 
 use crate::utils::AuctionConditionsBuilder;
 
 struct Auction {
     env: Env,
     owner: Address,
+    governance: Address,
     item_metadata: ItemMetadata,
     client: AuctionContractClient<'static>,
     token: TokenClient<'static>,
@@ -36,7 +40,8 @@ impl Auction {
 
         env.mock_all_auths();
 
-        let auction_address = env.register(AuctionContract, ());
+        let governance = Address::generate(&env);
+        let auction_address = env.register(AuctionContract, (governance.clone(),));
         let client = AuctionContractClient::new(&env, &auction_address);
         let owner = Address::generate(&env);
 
@@ -51,6 +56,7 @@ impl Auction {
             env,
             client,
             owner,
+            governance,
             item_metadata,
             token,
             token_admin,
@@ -262,7 +268,10 @@ fn test_auction_creation_failed_invalid_floor_value() {
         ..
     } = Auction::new();
 
-    let dutch_data = DutchAuctionData { floor_price: 0 };
+    let dutch_data = DutchAuctionData {
+        floor_price: 0,
+        curve: PriceCurve::Linear,
+    };
     let auction_conditions =
         AuctionConditionsBuilder::new(AuctionType::Dutch(dutch_data), 1000, 100).build();
 
@@ -626,7 +635,10 @@ fn test_bid_dutch_auction() {
 
     env.ledger().set_timestamp(0);
 
-    let dutch_data = DutchAuctionData { floor_price: 500 };
+    let dutch_data = DutchAuctionData {
+        floor_price: 500,
+        curve: PriceCurve::Linear,
+    };
 
     let auction_conditions =
         AuctionConditionsBuilder::new(AuctionType::Dutch(dutch_data), 1000, 1000).build();
@@ -688,7 +700,10 @@ fn test_bid_fail_dutch_auction_not_equal_to_dutch_bid() {
 
     env.ledger().set_timestamp(0);
 
-    let dutch_data = DutchAuctionData { floor_price: 500 };
+    let dutch_data = DutchAuctionData {
+        floor_price: 500,
+        curve: PriceCurve::Linear,
+    };
 
     let auction_conditions =
         AuctionConditionsBuilder::new(AuctionType::Dutch(dutch_data), 1000, 1000).build();
@@ -730,7 +745,10 @@ fn test_multiple_bid_fail_dutch_auction() {
 
     env.ledger().set_timestamp(0);
 
-    let dutch_data = DutchAuctionData { floor_price: 500 };
+    let dutch_data = DutchAuctionData {
+        floor_price: 500,
+        curve: PriceCurve::Linear,
+    };
 
     let auction_conditions =
         AuctionConditionsBuilder::new(AuctionType::Dutch(dutch_data), 1000, 1000).build();
@@ -1351,3 +1369,1008 @@ fn test_end_regular_auction_when_no_bids() {
         "Auction status should match"
     );
 }
+
+#[test]
+fn test_outbid_bidder_withdraws_refund() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let auction_id = 1;
+
+    let bidder1 = Address::generate(&env);
+    let bid_amount1 = 1000;
+    token_admin.mint(&bidder1, &bid_amount1);
+    client.make_bid(&auction_id, &bidder1, &bid_amount1);
+
+    let bidder2 = Address::generate(&env);
+    let bid_amount2 = 1500;
+    token_admin.mint(&bidder2, &bid_amount2);
+    client.make_bid(&auction_id, &bidder2, &bid_amount2);
+
+    // Refund is credited, not transferred in-line
+    assert_eq!(token.balance(&bidder1), 0);
+    assert_eq!(
+        client.get_withdrawable_balance(&bidder1, &token.address),
+        bid_amount1
+    );
+
+    let withdrawn = client.withdraw(&bidder1, &token.address);
+
+    assert_eq!(withdrawn, bid_amount1);
+    assert_eq!(token.balance(&bidder1), bid_amount1);
+    assert_eq!(client.get_withdrawable_balance(&bidder1, &token.address), 0);
+    assert_eq!(token.balance(&client.address), bid_amount2);
+}
+
+#[test]
+#[should_panic(expected = "#205")]
+fn test_withdraw_fail_nothing_to_withdraw() {
+    let Auction {
+        client, token, env, ..
+    } = Auction::new();
+
+    let bidder = Address::generate(&env);
+
+    client.withdraw(&bidder, &token.address);
+}
+
+#[test]
+fn test_end_auction_is_permissionless() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let auction_id = 1;
+
+    let bidder = Address::generate(&env);
+    let bid_amount = 1000;
+    token_admin.mint(&bidder, &bid_amount);
+    client.make_bid(&auction_id, &bidder, &bid_amount);
+
+    env.ledger().set_timestamp(1200);
+
+    client.end_auction(&auction_id);
+
+    // No authorization was required to settle the auction
+    assert!(
+        env.auths().is_empty(),
+        "end_auction should not require auth"
+    );
+    assert_eq!(
+        client.get_withdrawable_balance(&owner, &token.address),
+        bid_amount
+    );
+
+    let auction = client.get_auction(&auction_id).unwrap();
+    assert!(auction.owner == bidder, "Auction owner should match");
+    assert!(
+        auction.auction_status == AuctionStatus::Completed,
+        "Auction status should match"
+    );
+}
+
+fn create_multi_unit_auction(auction: &Auction, pricing: PricingRule) -> u32 {
+    let auction_type = AuctionType::MultiUnit(MultiUnitAuctionData {
+        quantity: 3,
+        pricing,
+    });
+    let auction_conditions = AuctionConditionsBuilder::new(auction_type, 1000, 100).build();
+
+    auction.client.create_auction(
+        &auction.owner,
+        &auction.token.address,
+        &auction.item_metadata,
+        &auction_conditions,
+    );
+
+    1
+}
+
+fn place_multi_unit_bid(
+    auction: &Auction,
+    auction_id: u32,
+    unit_price: i128,
+    quantity: u32,
+) -> Address {
+    let bidder = Address::generate(&auction.env);
+    auction
+        .token_admin
+        .mint(&bidder, &(unit_price * quantity as i128));
+    auction
+        .client
+        .make_multi_unit_bid(&auction_id, &bidder, &unit_price, &quantity);
+
+    bidder
+}
+
+#[test]
+fn test_end_multi_unit_auction_uniform_price() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::Uniform);
+
+    let bidder3 = place_multi_unit_bid(&auction, auction_id, 120, 1);
+    let bidder1 = place_multi_unit_bid(&auction, auction_id, 150, 2);
+    let bidder2 = place_multi_unit_bid(&auction, auction_id, 200, 2);
+
+    auction.env.ledger().set_timestamp(1200);
+    auction.client.end_auction(&auction_id);
+
+    // bidder2 gets 2 units, bidder1 gets 1 unit, all at the clearing price of 150
+    assert_eq!(
+        auction
+            .client
+            .get_withdrawable_balance(&auction.owner, &auction.token.address),
+        450
+    );
+
+    let client = &auction.client;
+    let token = &auction.token.address;
+    assert_eq!(client.get_withdrawable_balance(&bidder2, token), 100);
+    assert_eq!(client.get_withdrawable_balance(&bidder1, token), 150);
+    assert_eq!(client.get_withdrawable_balance(&bidder3, token), 120);
+
+    let auction_data = client.get_auction(&auction_id).unwrap();
+    assert!(
+        auction_data.owner == auction.owner,
+        "Seller keeps ownership"
+    );
+    assert_eq!(auction_data.curr_bid_amount, Some(450));
+    assert!(
+        auction_data.auction_status == AuctionStatus::Completed,
+        "Auction status should match"
+    );
+}
+
+#[test]
+fn test_end_multi_unit_auction_pay_as_bid() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::PayAsBid);
+
+    let bidder1 = place_multi_unit_bid(&auction, auction_id, 150, 2);
+    let bidder2 = place_multi_unit_bid(&auction, auction_id, 200, 2);
+
+    auction.env.ledger().set_timestamp(1200);
+    auction.client.end_auction(&auction_id);
+
+    // bidder2 pays 2 x 200, bidder1 pays 1 x 150 and is refunded the unfilled unit
+    assert_eq!(
+        auction
+            .client
+            .get_withdrawable_balance(&auction.owner, &auction.token.address),
+        550
+    );
+
+    let client = &auction.client;
+    let token = &auction.token.address;
+    assert_eq!(client.get_withdrawable_balance(&bidder2, token), 0);
+    assert_eq!(client.get_withdrawable_balance(&bidder1, token), 150);
+}
+
+#[test]
+fn test_multi_unit_bid_displaces_outbid_bids() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::PayAsBid);
+
+    let bidder1 = place_multi_unit_bid(&auction, auction_id, 120, 1);
+    let bidder2 = place_multi_unit_bid(&auction, auction_id, 150, 2);
+
+    // Every unit is covered, nothing has been displaced yet
+    let client = &auction.client;
+    let token = &auction.token.address;
+    assert_eq!(client.get_withdrawable_balance(&bidder1, token), 0);
+
+    // bidder1 no longer wins any unit and is credited back straight away
+    let bidder3 = place_multi_unit_bid(&auction, auction_id, 200, 1);
+    assert_eq!(client.get_withdrawable_balance(&bidder1, token), 120);
+    assert_eq!(client.get_withdrawable_balance(&bidder2, token), 0);
+
+    client.withdraw(&bidder1, token);
+    assert_eq!(auction.token.balance(&bidder1), 120);
+
+    // bidder2 is now only partially filled and stays on the ladder
+    let positions = client.get_bidder_positions(&bidder2, &0, &10);
+    assert!(positions.get(0).unwrap().is_leading);
+    assert_eq!(positions.get(0).unwrap().current_price, Some(150));
+
+    let positions = client.get_bidder_positions(&bidder1, &0, &10);
+    assert!(!positions.get(0).unwrap().is_leading);
+
+    auction.env.ledger().set_timestamp(1200);
+    client.end_auction(&auction_id);
+
+    // bidder3 pays 200, bidder2 pays 2 x 150
+    assert_eq!(client.get_withdrawable_balance(&auction.owner, token), 500);
+    assert_eq!(client.get_withdrawable_balance(&bidder3, token), 0);
+    assert_eq!(client.get_withdrawable_balance(&bidder2, token), 0);
+}
+
+#[test]
+#[should_panic(expected = "#307")]
+fn test_bid_fail_multi_unit_at_clearing_price() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::Uniform);
+
+    place_multi_unit_bid(&auction, auction_id, 200, 2);
+    place_multi_unit_bid(&auction, auction_id, 150, 1);
+
+    // Every unit is bid for, so a bid at the clearing price would win nothing
+    place_multi_unit_bid(&auction, auction_id, 150, 1);
+}
+
+#[test]
+#[should_panic(expected = "#324")]
+fn test_bid_fail_multi_unit_auction_without_quantity() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::Uniform);
+
+    let bidder = Address::generate(&auction.env);
+    auction.token_admin.mint(&bidder, &1000);
+
+    auction.client.make_bid(&auction_id, &bidder, &1000);
+}
+
+#[test]
+#[should_panic(expected = "#323")]
+fn test_bid_fail_multi_unit_quantity_exceeds_units() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction(&auction, PricingRule::Uniform);
+
+    place_multi_unit_bid(&auction, auction_id, 150, 4);
+}
+
+fn create_multi_unit_auction_with_target(auction: &Auction, target_price: i128) -> u32 {
+    let auction_type = AuctionType::MultiUnit(MultiUnitAuctionData {
+        quantity: 3,
+        pricing: PricingRule::Uniform,
+    });
+    let auction_conditions = AuctionConditionsBuilder::new(auction_type, 1000, 100)
+        .on_target_price(target_price)
+        .build();
+
+    auction.client.create_auction(
+        &auction.owner,
+        &auction.token.address,
+        &auction.item_metadata,
+        &auction_conditions,
+    );
+
+    1
+}
+
+#[test]
+fn test_end_multi_unit_auction_on_target_clearing_price() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction_with_target(&auction, 150);
+
+    // Units not yet fully bid for, so there is no clearing price
+    place_multi_unit_bid(&auction, auction_id, 200, 2);
+    assert!(auction.client.try_end_auction(&auction_id).is_err());
+
+    // All units bid for, but the lowest winning unit price is below target
+    place_multi_unit_bid(&auction, auction_id, 120, 1);
+    assert!(auction.client.try_end_auction(&auction_id).is_err());
+
+    // The clearing price rises to 160, which meets the target
+    place_multi_unit_bid(&auction, auction_id, 160, 1);
+    auction.client.end_auction(&auction_id);
+
+    assert_eq!(
+        auction
+            .client
+            .get_withdrawable_balance(&auction.owner, &auction.token.address),
+        480
+    );
+}
+
+#[test]
+#[should_panic(expected = "#302")]
+fn test_bid_fail_multi_unit_target_clearing_price_reached() {
+    let auction = Auction::new();
+    let auction_id = create_multi_unit_auction_with_target(&auction, 150);
+
+    place_multi_unit_bid(&auction, auction_id, 200, 2);
+    place_multi_unit_bid(&auction, auction_id, 150, 1);
+
+    // Clearing price already meets the target, so bidding is closed
+    place_multi_unit_bid(&auction, auction_id, 300, 1);
+}
+
+#[test]
+fn test_get_auction_bids_paginated() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let auction_id = 1;
+
+    let bidder1 = Address::generate(&env);
+    token_admin.mint(&bidder1, &1000);
+    env.ledger().set_timestamp(10);
+    client.make_bid(&auction_id, &bidder1, &200);
+
+    let bidder2 = Address::generate(&env);
+    token_admin.mint(&bidder2, &1000);
+    env.ledger().set_timestamp(20);
+    client.make_bid(&auction_id, &bidder2, &300);
+
+    env.ledger().set_timestamp(30);
+    client.make_bid(&auction_id, &bidder1, &400);
+
+    let bids = client.get_auction_bids(&auction_id, &0, &10);
+    assert_eq!(bids.len(), 3);
+    assert_eq!(bids.get(0).unwrap().bidder, bidder1);
+    assert_eq!(bids.get(0).unwrap().bid_amount, 200);
+    assert_eq!(bids.get(0).unwrap().timestamp, 10);
+    assert_eq!(bids.get(2).unwrap().bid_amount, 400);
+
+    let page = client.get_auction_bids(&auction_id, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().bidder, bidder2);
+    assert_eq!(page.get(0).unwrap().timestamp, 20);
+
+    assert_eq!(client.get_auction_bids(&auction_id, &3, &10).len(), 0);
+    assert_eq!(
+        client.get_auction_bids(&auction_id, &u32::MAX, &10).len(),
+        0
+    );
+}
+
+#[test]
+fn test_auction_indexes_by_owner_status_and_end_time() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        env,
+        ..
+    } = Auction::new();
+
+    let other_owner = Address::generate(&env);
+
+    let late = AuctionConditionsBuilder::new(AuctionType::Regular, 3000, 100).build();
+    let early = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+    let middle = AuctionConditionsBuilder::new(AuctionType::Regular, 2000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &late);
+    client.create_auction(&other_owner, &token.address, &item_metadata, &early);
+    client.create_auction(&owner, &token.address, &item_metadata, &middle);
+
+    let owned = client.get_auctions_by_owner(&owner, &0, &10);
+    assert_eq!(owned.len(), 2);
+    assert_eq!(owned.get(0).unwrap().id, 1);
+    assert_eq!(owned.get(1).unwrap().id, 3);
+
+    let ending_soonest = client.get_auctions_ending_soonest(&0, &10);
+    assert_eq!(ending_soonest.len(), 3);
+    assert_eq!(ending_soonest.get(0).unwrap().id, 2);
+    assert_eq!(ending_soonest.get(1).unwrap().id, 3);
+    assert_eq!(ending_soonest.get(2).unwrap().id, 1);
+
+    client.cancel_auction(&3);
+
+    // The active index itself is unordered
+    let active = client.get_auctions_by_status(&AuctionStatus::Active, &0, &10);
+    assert_eq!(active.len(), 2);
+    assert_eq!(active.get(0).unwrap().id, 1);
+    assert_eq!(active.get(1).unwrap().id, 2);
+
+    let cancelled = client.get_auctions_by_status(&AuctionStatus::Cancelled, &0, &10);
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled.get(0).unwrap().id, 3);
+
+    let page = client.get_auctions_ending_soonest(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 1);
+}
+
+#[test]
+fn test_auctions_ending_soonest_across_end_time_buckets() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    // Auctions 2 and 3 share an hour-long bucket, 1 and 4 each have their own
+    for end_time in [7210, 3620, 3605, 100] {
+        let conditions = AuctionConditionsBuilder::new(AuctionType::Regular, end_time, 100).build();
+        client.create_auction(&owner, &token.address, &item_metadata, &conditions);
+    }
+
+    let page = client.get_auctions_ending_soonest(&1, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().id, 3);
+    assert_eq!(page.get(1).unwrap().id, 2);
+
+    let page = client.get_auctions_ending_soonest(&3, &5);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().id, 1);
+
+    // A closed auction leaves its bucket, and an emptied bucket is skipped
+    client.cancel_auction(&4);
+    let page = client.get_auctions_ending_soonest(&0, &1);
+    assert_eq!(page.get(0).unwrap().id, 3);
+
+    // Bidder positions page the bidder's auctions before loading them
+    let bidder = Address::generate(&env);
+    token_admin.mint(&bidder, &1000);
+    client.make_bid(&1, &bidder, &200);
+    client.make_bid(&2, &bidder, &200);
+    client.make_bid(&3, &bidder, &200);
+
+    let positions = client.get_bidder_positions(&bidder, &1, &1);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().auction_id, 2);
+}
+
+#[test]
+fn test_auction_indexes_span_pages() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        ..
+    } = Auction::new();
+
+    // Indexes are stored in pages of 50 auction IDs
+    for end_time in 1..=55 {
+        let conditions =
+            AuctionConditionsBuilder::new(AuctionType::Regular, end_time * 100, 100).build();
+        client.create_auction(&owner, &token.address, &item_metadata, &conditions);
+    }
+
+    let owned = client.get_auctions_by_owner(&owner, &48, &4);
+    assert_eq!(owned.len(), 4);
+    assert_eq!(owned.get(0).unwrap().id, 49);
+    assert_eq!(owned.get(3).unwrap().id, 52);
+
+    // Closing an auction moves the last active auction into its place
+    client.cancel_auction(&2);
+    client.cancel_auction(&55);
+    client.cancel_auction(&54);
+    let active = client.get_auctions_by_status(&AuctionStatus::Active, &0, &100);
+    assert_eq!(active.len(), 52);
+    assert_eq!(active.get(1).unwrap().id, 53);
+    assert_eq!(active.get(51).unwrap().id, 52);
+
+    let ending_soonest = client.get_auctions_ending_soonest(&0, &3);
+    assert_eq!(ending_soonest.get(0).unwrap().id, 1);
+    assert_eq!(ending_soonest.get(1).unwrap().id, 3);
+    assert_eq!(ending_soonest.get(2).unwrap().id, 4);
+    assert_eq!(
+        client
+            .get_auctions_by_status(&AuctionStatus::Cancelled, &0, &10)
+            .len(),
+        3
+    );
+}
+
+#[test]
+fn test_get_bidder_positions() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let bidder1 = Address::generate(&env);
+    let bidder2 = Address::generate(&env);
+    token_admin.mint(&bidder1, &1000);
+    token_admin.mint(&bidder2, &1000);
+
+    client.make_bid(&1, &bidder1, &200);
+    client.make_bid(&2, &bidder1, &200);
+    client.make_bid(&2, &bidder2, &300);
+
+    let positions = client.get_bidder_positions(&bidder1, &0, &10);
+    assert_eq!(positions.len(), 2);
+    assert!(positions.get(0).unwrap().is_leading);
+    assert!(!positions.get(1).unwrap().is_leading);
+    assert_eq!(positions.get(1).unwrap().current_price, Some(300));
+
+    // Completed auctions are no longer open positions
+    env.ledger().set_timestamp(1200);
+    client.end_auction(&1);
+
+    let positions = client.get_bidder_positions(&bidder1, &0, &10);
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions.get(0).unwrap().auction_id, 2);
+}
+
+fn create_dutch_auction(auction: &Auction, curve: PriceCurve) -> u32 {
+    auction.env.ledger().set_timestamp(0);
+
+    let dutch_data = DutchAuctionData {
+        floor_price: 200,
+        curve,
+    };
+    let auction_conditions =
+        AuctionConditionsBuilder::new(AuctionType::Dutch(dutch_data), 1000, 1000).build();
+
+    auction.client.create_auction(
+        &auction.owner,
+        &auction.token.address,
+        &auction.item_metadata,
+        &auction_conditions,
+    );
+
+    1
+}
+
+#[test]
+fn test_dutch_price_linear_curve() {
+    let auction = Auction::new();
+    let auction_id = create_dutch_auction(&auction, PriceCurve::Linear);
+
+    let client = &auction.client;
+    assert_eq!(client.get_price_at(&auction_id, &0), 1000);
+    assert_eq!(client.get_price_at(&auction_id, &500), 600);
+    assert_eq!(client.get_price_at(&auction_id, &1000), 200);
+}
+
+#[test]
+fn test_dutch_price_exponential_curve() {
+    let auction = Auction::new();
+    let auction_id = create_dutch_auction(&auction, PriceCurve::Exponential(100));
+
+    let client = &auction.client;
+    assert_eq!(client.get_price_at(&auction_id, &0), 1000);
+    assert_eq!(client.get_price_at(&auction_id, &100), 600);
+    assert_eq!(client.get_price_at(&auction_id, &150), 500);
+    assert_eq!(client.get_price_at(&auction_id, &200), 400);
+    // Approaches the floor price, reaching it at the end time
+    assert_eq!(client.get_price_at(&auction_id, &999), 201);
+    assert_eq!(client.get_price_at(&auction_id, &1000), 200);
+}
+
+#[test]
+fn test_dutch_price_stepwise_curve() {
+    let auction = Auction::new();
+    let auction_id = create_dutch_auction(&auction, PriceCurve::Stepwise(250));
+
+    let client = &auction.client;
+    assert_eq!(client.get_price_at(&auction_id, &249), 1000);
+    assert_eq!(client.get_price_at(&auction_id, &300), 800);
+    assert_eq!(client.get_price_at(&auction_id, &499), 800);
+    assert_eq!(client.get_price_at(&auction_id, &500), 600);
+}
+
+#[test]
+fn test_dutch_price_piecewise_curve_and_bid() {
+    let auction = Auction::new();
+    let schedule = vec![
+        &auction.env,
+        PricePoint {
+            timestamp: 100,
+            price: 900,
+        },
+        PricePoint {
+            timestamp: 400,
+            price: 500,
+        },
+    ];
+    let auction_id = create_dutch_auction(&auction, PriceCurve::Piecewise(schedule));
+
+    let client = &auction.client;
+    assert_eq!(client.get_price_at(&auction_id, &50), 1000);
+    assert_eq!(client.get_price_at(&auction_id, &100), 900);
+    assert_eq!(client.get_price_at(&auction_id, &399), 900);
+    assert_eq!(client.get_price_at(&auction_id, &400), 500);
+    assert_eq!(client.get_price_at(&auction_id, &2000), 200);
+
+    // Bids must match the scheduled price
+    let bidder = Address::generate(&auction.env);
+    auction.token_admin.mint(&bidder, &500);
+    auction.env.ledger().set_timestamp(450);
+    client.make_bid(&auction_id, &bidder, &500);
+
+    let auction_data = client.get_auction(&auction_id).unwrap();
+    assert_eq!(auction_data.curr_bid_amount, Some(500));
+}
+
+#[test]
+#[should_panic(expected = "#115")]
+fn test_auction_creation_failed_invalid_exponential_half_life() {
+    let auction = Auction::new();
+    create_dutch_auction(&auction, PriceCurve::Exponential(0));
+}
+
+#[test]
+#[should_panic(expected = "#116")]
+fn test_auction_creation_failed_rising_price_schedule() {
+    let auction = Auction::new();
+    let schedule = vec![
+        &auction.env,
+        PricePoint {
+            timestamp: 100,
+            price: 500,
+        },
+        PricePoint {
+            timestamp: 400,
+            price: 900,
+        },
+    ];
+    create_dutch_auction(&auction, PriceCurve::Piecewise(schedule));
+}
+
+#[test]
+#[should_panic(expected = "#326")]
+fn test_get_price_at_fail_not_dutch_auction() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    client.get_price_at(&1, &500);
+}
+
+#[test]
+fn test_end_auction_pays_platform_fee_and_proceeds_shares() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        env,
+        ..
+    } = Auction::new();
+
+    let treasury = Address::generate(&env);
+    client.set_platform_fee(&treasury, &250);
+
+    let co_seller = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let recipients = vec![
+        &env,
+        ProceedsShare {
+            recipient: co_seller.clone(),
+            share_bps: 5000,
+        },
+        ProceedsShare {
+            recipient: creator.clone(),
+            share_bps: 1000,
+        },
+    ];
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100)
+        .proceeds_recipients(recipients)
+        .build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let auction_id = 1;
+    assert_eq!(
+        client
+            .get_auction(&auction_id)
+            .unwrap()
+            .auction_conditions
+            .platform_fee_bps,
+        Some(250)
+    );
+
+    // Fee changes don't affect auctions that were already created
+    client.set_platform_fee(&treasury, &1000);
+
+    let bidder = Address::generate(&env);
+    token_admin.mint(&bidder, &1000);
+    client.make_bid(&auction_id, &bidder, &1000);
+
+    env.ledger().set_timestamp(1200);
+    client.end_auction(&auction_id);
+
+    // 2.5% fee, then 50% and 10% of the remaining 975, rest to the seller
+    let token_id = &token.address;
+    assert_eq!(client.get_withdrawable_balance(&treasury, token_id), 25);
+    assert_eq!(client.get_withdrawable_balance(&co_seller, token_id), 487);
+    assert_eq!(client.get_withdrawable_balance(&creator, token_id), 97);
+    assert_eq!(client.get_withdrawable_balance(&owner, token_id), 391);
+
+    // Each party pulls its own share
+    for party in [&treasury, &co_seller, &creator, &owner] {
+        client.withdraw(party, token_id);
+    }
+    assert_eq!(token.balance(&treasury), 25);
+    assert_eq!(token.balance(&co_seller), 487);
+    assert_eq!(token.balance(&creator), 97);
+    assert_eq!(token.balance(&owner), 391);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_end_auction_when_recipient_cannot_receive() {
+    let Auction {
+        client,
+        item_metadata,
+        owner,
+        env,
+        ..
+    } = Auction::new();
+
+    // A revocable asset lets the co-seller's balance be frozen, so it cannot receive tokens
+    let sac = env.register_stellar_asset_contract_v2(owner.clone());
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let token = TokenClient::new(&env, &sac.address());
+    let token_admin = StellarAssetClient::new(&env, &sac.address());
+
+    let co_seller = Address::generate(&env);
+    token_admin.set_authorized(&co_seller, &false);
+
+    let recipients = vec![
+        &env,
+        ProceedsShare {
+            recipient: co_seller.clone(),
+            share_bps: 5000,
+        },
+    ];
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100)
+        .proceeds_recipients(recipients)
+        .build();
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    let bidder = Address::generate(&env);
+    token_admin.mint(&bidder, &1000);
+    client.make_bid(&1, &bidder, &1000);
+
+    // Settlement still completes and the seller can collect their share
+    env.ledger().set_timestamp(1200);
+    client.end_auction(&1);
+    assert!(
+        client.get_auction(&1).unwrap().auction_status == AuctionStatus::Completed,
+        "Auction status should match"
+    );
+
+    client.withdraw(&owner, &token.address);
+    assert_eq!(token.balance(&owner), 500);
+    assert!(client.try_withdraw(&co_seller, &token.address).is_err());
+    assert_eq!(
+        client.get_withdrawable_balance(&co_seller, &token.address),
+        500
+    );
+}
+
+#[test]
+fn test_governance_pause_expires() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        token_admin,
+        governance,
+        env,
+        ..
+    } = Auction::new();
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+
+    // Pausing bids leaves auction creation available
+    let make_bid = Symbol::new(&env, "make_bid");
+    client.pause(&governance, &PauseScope::Function(make_bid.clone()), &500);
+    assert!(client.is_paused(&make_bid));
+    assert!(!client.is_paused(&Symbol::new(&env, "create_auction")));
+
+    let bidder = Address::generate(&env);
+    token_admin.mint(&bidder, &1000);
+    assert!(client.try_make_bid(&1, &bidder, &200).is_err());
+
+    // A contract-wide pause can be lifted early
+    client.pause(&governance, &PauseScope::Contract, &500);
+    assert!(client.is_paused(&Symbol::new(&env, "withdraw")));
+    client.unpause(&governance, &PauseScope::Contract);
+    assert!(!client.is_paused(&Symbol::new(&env, "withdraw")));
+
+    // The bid pause lifts itself once it expires
+    env.ledger().set_timestamp(500);
+    assert!(!client.is_paused(&make_bid));
+    client.make_bid(&1, &bidder, &200);
+    assert_eq!(token.balance(&bidder), 800);
+}
+
+#[test]
+#[should_panic(expected = "#208")]
+fn test_pause_fail_not_governance() {
+    let Auction { client, env, .. } = Auction::new();
+
+    client.pause(&Address::generate(&env), &PauseScope::Contract, &500);
+}
+
+#[test]
+#[should_panic(expected = "#210")]
+fn test_pause_fail_exceeds_max_duration() {
+    let Auction {
+        client,
+        env,
+        governance,
+        ..
+    } = Auction::new();
+
+    let until = env.ledger().timestamp() + MAX_PAUSE_DURATION + 1;
+    client.pause(&governance, &PauseScope::Contract, &until);
+}
+
+#[test]
+fn test_pause_authority_keeps_control_of_its_pauses() {
+    let Auction {
+        client,
+        env,
+        governance,
+        ..
+    } = Auction::new();
+
+    let council = Address::generate(&env);
+    let new_council = Address::generate(&env);
+    client.set_pause_authority(&governance, &council);
+
+    // Once appointed, governance can no longer replace the security council
+    assert!(client.try_set_pause_authority(&governance, &governance).is_err());
+
+    // Governance cannot lift or shorten a pause the council put in place
+    let make_bid = Symbol::new(&env, "make_bid");
+    client.pause(&council, &PauseScope::Contract, &500);
+    assert!(client.try_unpause(&governance, &PauseScope::Contract).is_err());
+    assert!(client.try_pause(&governance, &PauseScope::Contract, &100).is_err());
+    assert!(client.is_paused(&make_bid));
+
+    // Governance still pauses single functions on its own
+    client.pause(&governance, &PauseScope::Function(make_bid.clone()), &100);
+    client.unpause(&governance, &PauseScope::Function(make_bid.clone()));
+
+    // The council lifts its own pause and hands over to a successor
+    client.unpause(&council, &PauseScope::Contract);
+    assert!(!client.is_paused(&make_bid));
+    client.set_pause_authority(&council, &new_council);
+    assert!(client.try_pause(&council, &PauseScope::Contract, &500).is_err());
+    client.pause(&new_council, &PauseScope::Contract, &500);
+    assert!(client.is_paused(&make_bid));
+}
+
+#[test]
+#[should_panic(expected = "#209")]
+fn test_create_auction_fail_contract_paused() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        governance,
+        ..
+    } = Auction::new();
+
+    client.pause(&governance, &PauseScope::Contract, &500);
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100).build();
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+}
+
+#[test]
+fn test_set_platform_fee_requires_governance_set_at_deployment() {
+    let Auction {
+        client,
+        governance,
+        env,
+        ..
+    } = Auction::new();
+
+    let treasury = Address::generate(&env);
+
+    // Without the governance signature the fee cannot be changed
+    env.set_auths(&[]);
+    assert!(client.try_set_platform_fee(&treasury, &250).is_err());
+
+    env.mock_auths(&[MockAuth {
+        address: &governance,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "set_platform_fee",
+            args: (treasury.clone(), 250u32).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    client.set_platform_fee(&treasury, &250);
+    assert_eq!(client.get_platform_fee().unwrap().fee_bps, 250);
+}
+
+#[test]
+#[should_panic(expected = "#120")]
+fn test_auction_creation_failed_fee_below_platform_default() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        env,
+        ..
+    } = Auction::new();
+
+    client.set_platform_fee(&Address::generate(&env), &250);
+
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100)
+        .platform_fee_bps(100)
+        .build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+}
+
+#[test]
+#[should_panic(expected = "#119")]
+fn test_auction_creation_failed_proceeds_shares_exceed_total() {
+    let Auction {
+        client,
+        item_metadata,
+        token,
+        owner,
+        env,
+        ..
+    } = Auction::new();
+
+    let recipients = vec![
+        &env,
+        ProceedsShare {
+            recipient: Address::generate(&env),
+            share_bps: 6000,
+        },
+        ProceedsShare {
+            recipient: Address::generate(&env),
+            share_bps: 5000,
+        },
+    ];
+    let auction_conditions = AuctionConditionsBuilder::new(AuctionType::Regular, 1000, 100)
+        .proceeds_recipients(recipients)
+        .build();
+
+    client.create_auction(&owner, &token.address, &item_metadata, &auction_conditions);
+}
@@ -1,5 +1,5 @@
 use crate::types::*;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 /// Interface for the Auction contract.
 pub trait AuctionTrait {
@@ -14,9 +14,63 @@ pub trait AuctionTrait {
 
     fn make_bid(env: Env, auction_id: u32, bidder: Address, bid_amount: i128);
 
+    /// Bid a per-unit price for a quantity of units on a multi-unit auction.
+    fn make_multi_unit_bid(
+        env: Env,
+        auction_id: u32,
+        bidder: Address,
+        unit_price: i128,
+        quantity: u32,
+    );
+
     fn cancel_auction(env: Env, auction_id: u32);
 
     fn end_auction(env: Env, auction_id: u32);
 
     fn get_auction(env: Env, auction_id: u32) -> Option<Auction>;
+
+    /// Withdraw refunds or auction proceeds owed to an address for the given token.
+    fn withdraw(env: Env, bidder: Address, token: Address) -> i128;
+
+    fn get_withdrawable_balance(env: Env, bidder: Address, token: Address) -> i128;
+
+    /// Update the default platform fee (governance only).
+    fn set_platform_fee(env: Env, recipient: Address, fee_bps: u32);
+
+    fn get_platform_fee(env: Env) -> Option<PlatformFee>;
+
+    /// Appoint the address allowed to pause besides governance (governance first, then the authority itself).
+    fn set_pause_authority(env: Env, caller: Address, authority: Address);
+
+    /// Pause the contract or a single function until `until` (governance or pause authority).
+    fn pause(env: Env, caller: Address, scope: PauseScope, until: u64);
+
+    /// Lift a pause before it expires (governance or pause authority).
+    fn unpause(env: Env, caller: Address, scope: PauseScope);
+
+    /// Whether a function is paused on its own or by a contract-wide pause.
+    fn is_paused(env: Env, function: Symbol) -> bool;
+
+    /// Price of a Dutch auction at the given timestamp, following its price curve.
+    fn get_price_at(env: Env, auction_id: u32, timestamp: u64) -> i128;
+
+    /// Paginated bid history of an auction, oldest bid first.
+    fn get_auction_bids(env: Env, auction_id: u32, start: u32, limit: u32) -> Vec<Bid>;
+
+    /// Paginated list of auctions created by an owner.
+    fn get_auctions_by_owner(env: Env, owner: Address, start: u32, limit: u32) -> Vec<Auction>;
+
+    /// Paginated list of auctions with the given status.
+    fn get_auctions_by_status(
+        env: Env,
+        status: AuctionStatus,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Auction>;
+
+    /// Paginated list of active auctions, ending soonest first.
+    fn get_auctions_ending_soonest(env: Env, start: u32, limit: u32) -> Vec<Auction>;
+
+    /// A bidder's positions in a page of the auctions they bid on, skipping inactive ones.
+    fn get_bidder_positions(env: Env, bidder: Address, start: u32, limit: u32) -> Vec<BidPosition>;
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -22,7 +22,18 @@ pub struct Auction {
 pub struct Bid {
     pub id: u32,
     pub bidder: Address,
-    pub bid_amount: i128,
+    pub bid_amount: i128, // Unit price for multi-unit auctions
+    pub quantity: u32,
+    pub timestamp: u64,
+}
+
+// A bidder's stake in an active auction
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BidPosition {
+    pub auction_id: u32,
+    pub is_leading: bool, // Bidder would win (or be allocated units) if the auction ended now
+    pub current_price: Option<i128>, // Highest bid, or the clearing price for multi-unit auctions
 }
 
 // Extendable Metadata to pass any required information
@@ -39,12 +50,62 @@ pub enum AuctionType {
     Regular,
     Reverse,
     Dutch(DutchAuctionData),
+    MultiUnit(MultiUnitAuctionData),
 }
 
 #[contracttype]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct DutchAuctionData {
     pub floor_price: i128,
+    pub curve: PriceCurve, // How the price falls from the starting price to the floor price
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PriceCurve {
+    Linear,                     // Falls evenly until the end time
+    Exponential(u64),           // Distance to the floor price halves every X seconds
+    Stepwise(u64),              // Follows the linear curve, but only drops every X seconds
+    Piecewise(Vec<PricePoint>), // Holds each scheduled price from its timestamp onwards
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PricePoint {
+    pub timestamp: u64,
+    pub price: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MultiUnitAuctionData {
+    pub quantity: u32,        // Number of identical units for sale
+    pub pricing: PricingRule, // How winning bidders are charged
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PricingRule {
+    Uniform,  // Every winner pays the lowest accepted unit price
+    PayAsBid, // Every winner pays their own unit price
+}
+
+// A per-unit bid on a multi-unit auction
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MultiUnitBid {
+    pub bidder: Address,
+    pub unit_price: i128,
+    pub quantity: u32,
+}
+
+// Units awarded to a bidder at settlement
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Allocation {
+    pub bidder: Address,
+    pub quantity: u32,
+    pub unit_price: i128,
 }
 
 #[contracttype]
@@ -61,8 +122,32 @@ pub struct AuctionConditions {
     pub on_fixed_sequence_number: Option<u32>, // Close at a specific ledger sequence number
     pub on_minimum_participants: Option<u32>,  // Close after X unique bidders
     pub on_maximum_participants: Option<u32>,  // Stop auction after X unique bidders
+
+    // Settlement
+    pub platform_fee_bps: Option<u32>, // Platform fee in basis points (defaults to the platform fee)
+    pub proceeds_recipients: Option<Vec<ProceedsShare>>, // Shares of the proceeds after the fee
 }
 
+// A share of auction proceeds, e.g. a co-seller or a creator royalty
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ProceedsShare {
+    pub recipient: Address,
+    pub share_bps: u32, // Share of the proceeds after the platform fee, in basis points
+}
+
+// Platform-wide fee configuration, managed by governance
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PlatformFee {
+    pub recipient: Address,
+    pub fee_bps: u32,
+}
+
+pub const BPS_DENOMINATOR: u32 = 10_000;
+pub const MAX_PROCEEDS_RECIPIENTS: u32 = 10;
+pub const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60; // Longest pause, in seconds
+
 #[contracttype]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum AuctionStatus {
@@ -70,3 +155,10 @@ pub enum AuctionStatus {
     Cancelled,
     Completed,
 }
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PauseScope {
+    Contract,         // Every guarded function
+    Function(Symbol), // A single guarded function, by name
+}
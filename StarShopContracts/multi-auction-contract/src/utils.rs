@@ -1,3 +1,5 @@
+use soroban_sdk::Vec;
+
 use crate::types::*;
 
 pub struct AuctionConditionsBuilder {
@@ -10,6 +12,8 @@ pub struct AuctionConditionsBuilder {
     on_fixed_sequence_number: Option<u32>,
     on_minimum_participants: Option<u32>,
     on_maximum_participants: Option<u32>,
+    platform_fee_bps: Option<u32>,
+    proceeds_recipients: Option<Vec<ProceedsShare>>,
 }
 
 impl AuctionConditionsBuilder {
@@ -25,6 +29,8 @@ impl AuctionConditionsBuilder {
             on_fixed_sequence_number: None,
             on_minimum_participants: None,
             on_maximum_participants: None,
+            platform_fee_bps: None,
+            proceeds_recipients: None,
         }
     }
 
@@ -60,6 +66,16 @@ impl AuctionConditionsBuilder {
         self
     }
 
+    pub fn platform_fee_bps(mut self, fee_bps: u32) -> Self {
+        self.platform_fee_bps = Some(fee_bps);
+        self
+    }
+
+    pub fn proceeds_recipients(mut self, recipients: Vec<ProceedsShare>) -> Self {
+        self.proceeds_recipients = Some(recipients);
+        self
+    }
+
     /// Finally, build the AuctionConditions.
     pub fn build(self) -> AuctionConditions {
         AuctionConditions {
@@ -72,6 +88,8 @@ impl AuctionConditionsBuilder {
             on_fixed_sequence_number: self.on_fixed_sequence_number,
             on_minimum_participants: self.on_minimum_participants,
             on_maximum_participants: self.on_maximum_participants,
+            platform_fee_bps: self.platform_fee_bps,
+            proceeds_recipients: self.proceeds_recipients,
         }
     }
 }
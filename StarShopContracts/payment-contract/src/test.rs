@@ -9,7 +9,7 @@ use crate::{
 use soroban_sdk::token::{StellarAssetClient as TokenAdmin, TokenClient};
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke},
     Address, Env, IntoVal, Symbol,
 };
 
@@ -168,6 +168,39 @@ fn test_upgrade_not_initialized() {
     assert_eq!(client.upgrade(&new_wasm_hash), ());
 }
 
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_upgrade_requires_upgrade_authority() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentContract, ());
+    let client = PaymentContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let new_wasm_hash = install_new_wasm(&env);
+
+    // The admin is the upgrade authority until it hands the role over
+    client.initialize(&admin);
+    assert_eq!(client.get_upgrade_authority(), admin);
+    client.set_upgrade_authority(&governance);
+    assert_eq!(client.get_upgrade_authority(), governance);
+
+    // The admin alone can no longer upgrade the contract
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "upgrade",
+                args: (new_wasm_hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&new_wasm_hash);
+}
+
 #[test]
 fn test_succesful_transfer_admin() {
     let env = Env::default();
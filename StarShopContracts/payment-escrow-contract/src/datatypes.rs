@@ -1,6 +1,6 @@
 use soroban_sdk::{
     Address,
-    contracterror, contracttype, String
+    contracterror, contracttype, String, Symbol
 };
 
 
@@ -15,6 +15,21 @@ pub enum DataKey {
     ResolvedDisputes,
     SellerRegId(Address),
     PaymentCounter,
+    PauseAuthority,
+    Paused(PauseScope),
+    PausedByAuthority(PauseScope),
+    UpgradeAuthority,
+}
+
+// Longest pause that may be requested, in seconds
+pub const MAX_PAUSE_DURATION: u64 = 7 * 24 * 60 * 60;
+
+// Scope of an emergency pause
+#[contracttype]
+#[derive(Clone, PartialEq, Debug)]
+pub enum PauseScope {
+    Contract,          // Every guarded function
+    Function(Symbol),  // A single guarded function, by name
 }
 
 // Error definitions
@@ -41,6 +56,7 @@ pub enum PaymentEscrowError {
     NotSeller = 17,
     ArbitratorAlreadyExists = 18,
     PaymentDisputed = 19,
+    ContractPaused = 20,
 }
 
 // Status Enum
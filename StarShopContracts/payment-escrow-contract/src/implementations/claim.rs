@@ -38,6 +38,9 @@ impl ClaimInterface for PaymentEscrowContract {
         // This ensures only the intended claimer can execute the claim operation
         claimer.require_auth();
 
+        // Emergency check - refuse to move funds while governance has paused this function
+        PaymentEscrowContract::ensure_not_paused(&env, "claim_payment")?;
+
         // Retrieve the payment details from persistent storage
         // This contains all payment information including status, amounts, and timestamps
         let payment: Payment = env
@@ -71,6 +71,9 @@ impl PaymentInterface for PaymentEscrowContract {
         // This ensures only the intended buyer can create payments
         buyer.require_auth();
 
+        // Emergency check - refuse to move funds while governance has paused this function
+        PaymentEscrowContract::ensure_not_paused(&env, "create_payment")?;
+
         // Input validation: ensure payment amount is positive
         // This prevents creating payments with zero or negative amounts
         if amount <= 0 {
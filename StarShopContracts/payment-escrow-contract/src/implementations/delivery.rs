@@ -42,6 +42,9 @@ impl DeliveryInterface for PaymentEscrowContract {
         // This ensures only the intended buyer can confirm delivery
         buyer.require_auth();
 
+        // Emergency check - refuse to move funds while governance has paused this function
+        PaymentEscrowContract::ensure_not_paused(&env, "buyer_confirm_delivery")?;
+
         // Retrieve the payment details from persistent storage
         // This contains all payment information including current status and participants
         let payment: Payment = env
@@ -130,6 +133,9 @@ impl DeliveryInterface for PaymentEscrowContract {
         // This ensures only the intended seller can confirm delivery
         seller.require_auth();
 
+        // Emergency check - refuse to move funds while governance has paused this function
+        PaymentEscrowContract::ensure_not_paused(&env, "seller_confirm_delivery")?;
+
         // Retrieve the payment details from persistent storage
         // This contains all payment information including current status and participants
         let payment: Payment = env
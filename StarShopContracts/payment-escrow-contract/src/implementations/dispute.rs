@@ -136,6 +136,9 @@ impl DisputeInterface for PaymentEscrowContract {
         // This ensures only authorized arbitrators can resolve disputes
         arbitrator.require_auth();
 
+        // Emergency check - refuse to move funds while governance has paused this function
+        PaymentEscrowContract::ensure_not_paused(&env, "resolve_dispute")?;
+
         // Retrieve the payment details from persistent storage
         // This contains all payment information needed for resolution
         let payment: Payment = env
@@ -2,4 +2,5 @@ pub mod create;
 pub mod delivery;
 pub mod dispute;
 pub mod claim;
-pub mod arbitrator;
\ No newline at end of file
+pub mod arbitrator;
+pub mod pause;
\ No newline at end of file
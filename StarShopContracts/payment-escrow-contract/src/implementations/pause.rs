@@ -0,0 +1,209 @@
+use crate::{
+    datatypes::{DataKey, PauseScope, PaymentEscrowError, MAX_PAUSE_DURATION},
+    interface::PauseInterface,
+    PaymentEscrowContract, PaymentEscrowContractArgs, PaymentEscrowContractClient,
+};
+use soroban_sdk::{contractimpl, symbol_short, Address, Env, Symbol, Vec};
+
+/// Implementation of the PauseInterface trait for PaymentEscrowContract
+/// This module handles emergency pauses, which the arbitrators or the governance
+/// contract can apply to the whole contract or to single functions.
+#[contractimpl]
+impl PauseInterface for PaymentEscrowContract {
+
+    /// Sets the address allowed to pause the contract besides the arbitrators
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator, or the current pause authority once one is set
+    /// * `authority` - The pause authority, usually the governance contract
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    fn set_pause_authority(
+        env: Env,
+        caller: Address,
+        authority: Address,
+    ) -> Result<(), PaymentEscrowError> {
+        // Authentication - caller must authorize this transaction
+        caller.require_auth();
+
+        // Authorization check: once appointed, only the pause authority can replace itself
+        match Self::get_pause_authority(&env) {
+            Some(current) => {
+                if current != caller {
+                    return Err(PaymentEscrowError::UnauthorizedAccess);
+                }
+            }
+            None => {
+                // Otherwise only current arbitrators can appoint the pause authority
+                let arbitrators: Vec<Address> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::Arbitrator)
+                    .ok_or(PaymentEscrowError::NotInitialized)?;
+                if !arbitrators.contains(&caller) {
+                    return Err(PaymentEscrowError::NotArbitrator);
+                }
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PauseAuthority, &authority);
+
+        // Emit an event for transparency and off-chain tracking
+        env.events().publish(
+            (symbol_short!("pauseauth"), authority),
+            caller,
+        );
+
+        Ok(())
+    }
+
+    /// Pauses the whole contract or a single function until a timestamp
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator or the pause authority
+    /// * `scope` - The whole contract or a single function
+    /// * `until` - Timestamp at which the pause lifts itself
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    fn pause(
+        env: Env,
+        caller: Address,
+        scope: PauseScope,
+        until: u64,
+    ) -> Result<(), PaymentEscrowError> {
+        Self::require_pauser(&env, &caller)?;
+
+        // Input validation: the pause must end in the future so it can expire on its own,
+        // and no later than MAX_PAUSE_DURATION from now
+        let now = env.ledger().timestamp();
+        if until <= now || until - now > MAX_PAUSE_DURATION {
+            return Err(PaymentEscrowError::NotValid);
+        }
+
+        // Authorization check: an arbitrator cannot re-time a pause the authority set,
+        // since arbitrators are party to the disputes a governance pause may be guarding
+        let by_authority = Self::get_pause_authority(&env).as_ref() == Some(&caller);
+        if !by_authority && Self::is_authority_pause(&env, &scope) {
+            return Err(PaymentEscrowError::UnauthorizedAccess);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Paused(scope.clone()), &until);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PausedByAuthority(scope.clone()), &by_authority);
+
+        env.events().publish((symbol_short!("paused"), scope), until);
+
+        Ok(())
+    }
+
+    /// Lifts a pause before it expires
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator or the pause authority
+    /// * `scope` - The scope that was paused
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), PaymentEscrowError> {
+        Self::require_pauser(&env, &caller)?;
+
+        // Authorization check: nor can an arbitrator release funds early by lifting it
+        if Self::get_pause_authority(&env).as_ref() != Some(&caller)
+            && Self::is_authority_pause(&env, &scope)
+        {
+            return Err(PaymentEscrowError::UnauthorizedAccess);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Paused(scope.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PausedByAuthority(scope.clone()));
+
+        env.events().publish((symbol_short!("unpaused"), scope), caller);
+
+        Ok(())
+    }
+
+    /// Checks whether a function is paused on its own or by a contract-wide pause
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `function` - The function name
+    /// 
+    /// # Returns
+    /// * `bool` - True if calls to the function are currently refused
+    fn is_paused(env: Env, function: Symbol) -> bool {
+        Self::is_scope_paused(&env, PauseScope::Contract)
+            || Self::is_scope_paused(&env, PauseScope::Function(function))
+    }
+}
+
+impl PaymentEscrowContract {
+    /// Fails with `ContractPaused` if a function is currently paused
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `function` - The name of the function being called
+    pub(crate) fn ensure_not_paused(env: &Env, function: &str) -> Result<(), PaymentEscrowError> {
+        if Self::is_paused(env.clone(), Symbol::new(env, function)) {
+            return Err(PaymentEscrowError::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Checks whether a pause scope is still in effect
+    fn is_scope_paused(env: &Env, scope: PauseScope) -> bool {
+        let until: Option<u64> = env.storage().persistent().get(&DataKey::Paused(scope));
+        match until {
+            Some(until) => env.ledger().timestamp() < until,
+            None => false,
+        }
+    }
+
+    /// Checks whether a pause scope is still in effect and was set by the pause authority
+    fn is_authority_pause(env: &Env, scope: &PauseScope) -> bool {
+        let by_authority: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PausedByAuthority(scope.clone()))
+            .unwrap_or(false);
+        by_authority && Self::is_scope_paused(env, scope.clone())
+    }
+
+    /// Returns the current pause authority, if one has been set
+    fn get_pause_authority(env: &Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::PauseAuthority)
+    }
+
+    /// Ensures the caller is an arbitrator or the pause authority
+    fn require_pauser(env: &Env, caller: &Address) -> Result<(), PaymentEscrowError> {
+        // Authentication - caller must authorize this transaction
+        caller.require_auth();
+
+        if Self::get_pause_authority(env).as_ref() == Some(caller) {
+            return Ok(());
+        }
+
+        let arbitrators: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Arbitrator)
+            .ok_or(PaymentEscrowError::NotInitialized)?;
+        if !arbitrators.contains(caller) {
+            return Err(PaymentEscrowError::UnauthorizedAccess);
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
-use crate::datatypes::{DisputeDecision, Payment, PaymentEscrowError, PaymentStatus, DeliveryDetails};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::datatypes::{DisputeDecision, Payment, PaymentEscrowError, PaymentStatus, DeliveryDetails, PauseScope};
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
 /// PaymentInterface trait defines core payment management functionality
 /// This trait handles payment creation, retrieval, and basic payment operations
@@ -262,4 +262,66 @@ pub trait ArbitratorInterface {
     /// * Changing arbitrator addresses
     /// * Replacing compromised arbitrator keys
     fn transfer_arbitrator_rights(env: Env, old_arbitrator: Address, new_arbitrator: Address) -> Result<(), PaymentEscrowError>;
-}
\ No newline at end of file
+}
+
+
+
+
+
+/// PauseInterface trait defines the emergency pause controls
+/// This trait lets arbitrators or the pause authority (usually the governance contract)
+/// halt fund movements for the whole contract or a single function, with automatic expiry.
+pub trait PauseInterface {
+    /// Sets the address allowed to pause the contract besides the arbitrators
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator, or the current pause authority once one is set
+    /// * `authority` - The pause authority, usually the governance contract
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    fn set_pause_authority(env: Env, caller: Address, authority: Address) -> Result<(), PaymentEscrowError>;
+
+    /// Pauses the whole contract or a single function until a timestamp
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator or the pause authority
+    /// * `scope` - The whole contract or a single function
+    /// * `until` - Timestamp at which the pause lifts itself
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    /// 
+    /// # Business Logic
+    /// * Payment creation, delivery confirmations, claims and dispute resolutions are guarded
+    /// * Buyers can still open disputes while the contract is paused
+    /// * A pause lasts at most `MAX_PAUSE_DURATION`
+    /// * Arbitrators cannot override a pause set by the pause authority
+    fn pause(env: Env, caller: Address, scope: PauseScope, until: u64) -> Result<(), PaymentEscrowError>;
+
+    /// Lifts a pause before it expires
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `caller` - An arbitrator or the pause authority
+    /// * `scope` - The scope that was paused
+    /// 
+    /// # Returns
+    /// * `Result<(), PaymentEscrowError>` - Success or error
+    /// 
+    /// # Business Logic
+    /// * Only the pause authority can lift a pause it set
+    fn unpause(env: Env, caller: Address, scope: PauseScope) -> Result<(), PaymentEscrowError>;
+
+    /// Checks whether a function is paused on its own or by a contract-wide pause
+    /// 
+    /// # Arguments
+    /// * `env` - The Soroban environment
+    /// * `function` - The function name
+    /// 
+    /// # Returns
+    /// * `bool` - True if calls to the function are currently refused
+    fn is_paused(env: Env, function: Symbol) -> bool;
+}
@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Env, Address, Vec, BytesN};
+use soroban_sdk::{contract, contractimpl, symbol_short, Env, Address, Vec, BytesN};
 use crate::datatypes::{DataKey, PaymentEscrowError};
 
 /// Payment Escrow Contract
@@ -70,7 +70,8 @@ impl PaymentEscrowContract {
     /// 
     /// This function allows the contract to be upgraded with new WASM code while
     /// preserving all existing state. The upgrade requires authorization from all
-    /// current arbitrators to ensure security and prevent unauthorized upgrades.
+    /// current arbitrators to ensure security and prevent unauthorized upgrades,
+    /// or from the upgrade authority (e.g. governance) once one has been set.
     /// 
     /// # Arguments
     /// * `e` - The Soroban environment
@@ -78,6 +79,7 @@ impl PaymentEscrowContract {
     /// 
     /// # Security Features
     /// - Requires authorization from all arbitrators (consensus mechanism)
+    ///   or from the upgrade authority
     /// - Preserves all existing contract state during upgrade
     /// - Prevents unauthorized upgrades that could compromise the system
     /// 
@@ -92,6 +94,58 @@ impl PaymentEscrowContract {
     /// - All arbitrators must be available to authorize the upgrade
     /// - Upgrade should be thoroughly tested before deployment
     pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) -> Result<(), PaymentEscrowError> {
+        // Require the upgrade authority, or all arbitrators if none is set
+        Self::require_upgrade_authority(&e)?;
+
+        // Deploy the new WASM code to the contract
+        // This updates the contract's code while preserving all state
+        e.deployer().update_current_contract_wasm(new_wasm_hash);
+        
+        Ok(())
+    }
+
+    /// Hand upgrade rights to an upgrade authority
+    /// 
+    /// Once set, the authority (typically the governance contract) alone authorizes
+    /// upgrades, so an upgrade requires a passed governance proposal instead of
+    /// arbitrator consensus. Changing the authority again requires its authorization.
+    /// 
+    /// # Arguments
+    /// * `e` - The Soroban environment
+    /// * `authority` - The address that will authorize upgrades
+    /// 
+    /// # Returns
+    /// * `Ok(())` - Upgrade authority successfully set
+    /// * `Err(PaymentEscrowError::NotInitialized)` - Contract not initialized
+    pub fn set_upgrade_authority(e: Env, authority: Address) -> Result<(), PaymentEscrowError> {
+        // Require the current upgrade authority, or all arbitrators if none is set
+        Self::require_upgrade_authority(&e)?;
+
+        e.storage().persistent().set(&DataKey::UpgradeAuthority, &authority);
+
+        // Emit an event for transparency and off-chain tracking
+        e.events().publish((symbol_short!("upgauth"),), authority);
+
+        Ok(())
+    }
+
+    /// Get the upgrade authority, if one has been set
+    /// 
+    /// # Returns
+    /// * `Option<Address>` - The upgrade authority, or `None` while arbitrators authorize upgrades
+    pub fn get_upgrade_authority(e: Env) -> Option<Address> {
+        e.storage().persistent().get(&DataKey::UpgradeAuthority)
+    }
+}
+
+impl PaymentEscrowContract {
+    /// Require authorization from the upgrade authority, falling back to all arbitrators
+    fn require_upgrade_authority(e: &Env) -> Result<(), PaymentEscrowError> {
+        if let Some(authority) = Self::get_upgrade_authority(e.clone()) {
+            authority.require_auth();
+            return Ok(());
+        }
+
         // Retrieve the current list of arbitrators from storage
         let arbitrators: Vec<Address> = e.storage().persistent().get(&DataKey::Arbitrator)
                    .ok_or(PaymentEscrowError::NotInitialized)?;
@@ -105,10 +159,6 @@ impl PaymentEscrowContract {
         // This ensures that no single arbitrator can upgrade the contract alone
         arbitrators.iter().for_each(|a| a.require_auth());
 
-        // Deploy the new WASM code to the contract
-        // This updates the contract's code while preserving all state
-        e.deployer().update_current_contract_wasm(new_wasm_hash);
-        
         Ok(())
     }
 }
@@ -10,12 +10,12 @@ extern crate std;
 // For now, we'll test the upgrade function without WASM files
 
 use crate::{
-    datatypes::{DisputeDecision, PaymentStatus},
+    datatypes::{DisputeDecision, PauseScope, PaymentEscrowError, PaymentStatus, MAX_PAUSE_DURATION},
     PaymentEscrowContract, PaymentEscrowContractClient,
 };
 use soroban_sdk::{
     symbol_short,
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, MockAuth, MockAuthInvoke},
     Address, Env, IntoVal, String, Symbol,
     BytesN
 };
@@ -879,4 +879,135 @@ fn test_contract_upgrade() {
     assert_eq!(token_client.balance(&buyer), 900);
     assert_eq!(token_client.balance(&seller), 0); 
 
-}
\ No newline at end of file
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_upgrade_requires_upgrade_authority() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentEscrowContract, ());
+    let client = PaymentEscrowContractClient::new(&env, &contract_id);
+
+    let arbitrator = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let new_wasm_hash = BytesN::from_array(&env, &[1; 32]);
+
+    // Arbitrators hand upgrade rights to governance
+    client.init(&arbitrator);
+    assert_eq!(client.get_upgrade_authority(), None);
+    client.set_upgrade_authority(&governance);
+    assert_eq!(client.get_upgrade_authority(), Some(governance));
+
+    // The arbitrators alone can no longer upgrade the contract
+    client
+        .mock_auths(&[MockAuth {
+            address: &arbitrator,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "upgrade",
+                args: (new_wasm_hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&new_wasm_hash);
+}
+
+
+#[test]
+fn test_pause_by_authority() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentEscrowContract, ());
+    let client = PaymentEscrowContractClient::new(&env, &contract_id);
+
+    // Initialize the contract and appoint governance as pause authority
+    let arbitrator = Address::generate(&env);
+    let governance = Address::generate(&env);
+    client.init(&arbitrator);
+    client.set_pause_authority(&arbitrator, &governance);
+
+    let token_admin = Address::generate(&env);
+    let stellar_asset = env.register_stellar_asset_contract_v2(token_admin.clone());
+    let token_contract_id = stellar_asset.address();
+    let token = TokenAdmin::new(&env, &token_contract_id);
+
+    let buyer = Address::generate(&env);
+    let seller = Address::generate(&env);
+    let description = String::from_str(&env, "Test payment");
+    token.mint(&buyer, &1000);
+
+    // Only arbitrators and the pause authority can pause
+    let result = client.try_pause(&buyer, &PauseScope::Contract, &100);
+    assert_eq!(result, Err(Ok(PaymentEscrowError::UnauthorizedAccess)));
+
+    // Payment creation is refused while paused
+    let create_payment = Symbol::new(&env, "create_payment");
+    client.pause(&governance, &PauseScope::Function(create_payment.clone()), &100);
+    assert!(client.is_paused(&create_payment));
+    assert!(!client.is_paused(&Symbol::new(&env, "claim_payment")));
+    let result = client.try_create_payment(
+        &buyer,
+        &seller,
+        &100,
+        &token_contract_id,
+        &30,
+        &description,
+    );
+    assert_eq!(result, Err(Ok(PaymentEscrowError::ContractPaused)));
+
+    // The pause lifts itself once it expires
+    env.ledger().with_mut(|li| {
+        li.timestamp = 100;
+    });
+    assert!(!client.is_paused(&create_payment));
+    client.create_payment(&buyer, &seller, &100, &token_contract_id, &30, &description);
+
+    // A contract-wide pause can be lifted early by an arbitrator
+    client.pause(&arbitrator, &PauseScope::Contract, &200);
+    assert!(client.is_paused(&Symbol::new(&env, "buyer_confirm_delivery")));
+    client.unpause(&arbitrator, &PauseScope::Contract);
+    assert!(!client.is_paused(&Symbol::new(&env, "buyer_confirm_delivery")));
+}
+
+#[test]
+fn test_pause_authority_keeps_control_of_its_pauses() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentEscrowContract, ());
+    let client = PaymentEscrowContractClient::new(&env, &contract_id);
+
+    let arbitrator = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let new_governance = Address::generate(&env);
+    client.init(&arbitrator);
+    client.set_pause_authority(&arbitrator, &governance);
+
+    // Once set, arbitrators can no longer replace the pause authority
+    let result = client.try_set_pause_authority(&arbitrator, &arbitrator);
+    assert_eq!(result, Err(Ok(PaymentEscrowError::UnauthorizedAccess)));
+
+    // Pauses are capped at the maximum duration
+    let now = env.ledger().timestamp();
+    let result = client.try_pause(&governance, &PauseScope::Contract, &(now + MAX_PAUSE_DURATION + 1));
+    assert_eq!(result, Err(Ok(PaymentEscrowError::NotValid)));
+
+    // Arbitrators cannot lift or shorten a pause set by the authority
+    let create_payment = Symbol::new(&env, "create_payment");
+    client.pause(&governance, &PauseScope::Contract, &(now + MAX_PAUSE_DURATION));
+    let result = client.try_unpause(&arbitrator, &PauseScope::Contract);
+    assert_eq!(result, Err(Ok(PaymentEscrowError::UnauthorizedAccess)));
+    let result = client.try_pause(&arbitrator, &PauseScope::Contract, &(now + 1));
+    assert_eq!(result, Err(Ok(PaymentEscrowError::UnauthorizedAccess)));
+    assert!(client.is_paused(&create_payment));
+
+    // The authority can lift its own pause and hand over to a successor
+    client.unpause(&governance, &PauseScope::Contract);
+    assert!(!client.is_paused(&create_payment));
+    client.set_pause_authority(&governance, &new_governance);
+    let result = client.try_pause(&governance, &PauseScope::Contract, &(now + 100));
+    assert_eq!(result, Err(Ok(PaymentEscrowError::UnauthorizedAccess)));
+}
@@ -133,23 +133,44 @@ impl ProductFollowContract {
     }
 
     // Upgrades the contract with new WASM code
+    // Requires the upgrade authority, which is the admin until governance takes over
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), ProductFollowError> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("admin"))
-            .ok_or(ProductFollowError::NotInitialized)?;
+        let authority = Self::get_upgrade_authority(env.clone())?;
 
-        admin.require_auth();
+        authority.require_auth();
         env.deployer()
             .update_current_contract_wasm(new_wasm_hash.clone());
 
         env.events()
-            .publish((symbol_short!("upgrade"),), (admin, new_wasm_hash));
+            .publish((symbol_short!("upgrade"),), (authority, new_wasm_hash));
 
         Ok(())
     }
 
+    /// Hands upgrade rights to a new authority, such as the governance contract
+    /// Must be authorized by the current upgrade authority
+    pub fn set_upgrade_authority(env: Env, authority: Address) -> Result<(), ProductFollowError> {
+        let current_authority = Self::get_upgrade_authority(env.clone())?;
+
+        current_authority.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("upg_auth"), &authority);
+
+        env.events()
+            .publish((symbol_short!("upg_auth"),), (current_authority, authority));
+
+        Ok(())
+    }
+
+    /// Returns the address allowed to upgrade the contract
+    pub fn get_upgrade_authority(env: Env) -> Result<Address, ProductFollowError> {
+        match env.storage().instance().get(&symbol_short!("upg_auth")) {
+            Some(authority) => Ok(authority),
+            None => Self::get_admin(env),
+        }
+    }
+
     /// Returns the current admin address
     pub fn get_admin(env: Env) -> Result<Address, ProductFollowError> {
         env.storage()
@@ -6,7 +6,10 @@ use crate::{
 };
 
 use super::*;
-use soroban_sdk::{testutils::Address as TestAddress, vec, Env, Vec};
+use soroban_sdk::{
+    testutils::{Address as TestAddress, MockAuth, MockAuthInvoke},
+    vec, Env, IntoVal, Vec,
+};
 
 #[test]
 #[should_panic(expected = "Unauthorized function call for address")]
@@ -440,3 +443,34 @@ fn test_validate_user_preferences() {
     );
     assert_eq!(retrieved_preferences.priority, NotificationPriority::Medium);
 }
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn test_upgrade_requires_upgrade_authority() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(ProductFollowContract, ());
+    let client = ProductFollowContractClient::new(&env, &contract_id);
+    let admin = <Address>::generate(&env);
+    let governance = <Address>::generate(&env);
+    let new_wasm_hash = BytesN::from_array(&env, &[1; 32]);
+
+    // The admin is the upgrade authority until it hands the role over
+    client.initialize(&admin);
+    assert_eq!(client.get_upgrade_authority(), admin);
+    client.set_upgrade_authority(&governance);
+    assert_eq!(client.get_upgrade_authority(), governance);
+
+    // The admin alone can no longer upgrade the contract
+    client
+        .mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "upgrade",
+                args: (new_wasm_hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&new_wasm_hash);
+}